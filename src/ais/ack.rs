@@ -0,0 +1,348 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Kind of an addressed AIS message, which determines the acknowledgement message type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AddressedMessageKind {
+    /// Binary addressed message (type 6) acknowledged with type 7
+    Binary,
+
+    /// Addressed safety related message (type 12) acknowledged with type 13
+    SafetyRelated,
+}
+
+/// Identifies an addressed AIS message and its acknowledgement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AddressedMessageKey {
+    /// Kind of the message
+    pub kind: AddressedMessageKind,
+
+    /// MMSI of the station which sent the message
+    pub source_mmsi: u32,
+
+    /// MMSI of the station the message was addressed to and which acknowledges it
+    pub destination_mmsi: u32,
+
+    /// Sequence number (0-3)
+    pub sequence_number: u8,
+}
+
+/// Correlates addressed AIS messages (types 6 and 12) with their acknowledgements (types 7 and
+/// 13). The tracker is fed with the parsed messages with `update()`, typically with all the
+/// messages returned by `NmeaParser`, and it keeps the state independently of the parser.
+///
+/// A message is pending until an acknowledgement with the same source, destination and
+/// sequence number is seen. Acknowledgements received before the message itself are kept and
+/// matched when the message arrives. Entries expire after the given number of updates so that
+/// reused sequence numbers and lost messages don't accumulate.
+#[derive(Clone, Debug)]
+pub struct AckTracker {
+    /// Number of updates after which entries expire
+    max_age: u64,
+
+    /// Number of `update()` calls so far
+    update_count: u64,
+
+    /// Messages waiting for acknowledgement with the update count when they were seen
+    pending: HashMap<AddressedMessageKey, u64>,
+
+    /// Acknowledged messages with the update count of the acknowledgement
+    acknowledged: HashMap<AddressedMessageKey, u64>,
+
+    /// Acknowledgements without a known message with the update count when they were seen
+    unmatched_acks: HashMap<AddressedMessageKey, u64>,
+}
+
+impl AckTracker {
+    /// Construct a tracker. Entries are kept for `max_age` further updates after they were last
+    /// changed and dropped on the next one.
+    pub fn new(max_age: u64) -> AckTracker {
+        AckTracker {
+            max_age,
+            update_count: 0,
+            pending: HashMap::new(),
+            acknowledged: HashMap::new(),
+            unmatched_acks: HashMap::new(),
+        }
+    }
+
+    /// Feed a parsed message to the tracker. Messages other than AIS types 6, 7, 12 and 13
+    /// only age the entries.
+    pub fn update(&mut self, msg: &ParsedMessage) {
+        self.update_count += 1;
+        self.expire();
+
+        match msg {
+            ParsedMessage::BinaryAddressedMessage(bam) => self.record_message(
+                AddressedMessageKey {
+                    kind: AddressedMessageKind::Binary,
+                    source_mmsi: bam.mmsi,
+                    destination_mmsi: bam.destination_mmsi,
+                    sequence_number: bam.sequence_number,
+                },
+                bam.retransmit_flag,
+            ),
+            ParsedMessage::AddressedSafetyRelatedMessage(asrm) => self.record_message(
+                AddressedMessageKey {
+                    kind: AddressedMessageKind::SafetyRelated,
+                    source_mmsi: asrm.source_mmsi,
+                    destination_mmsi: asrm.destination_mmsi,
+                    sequence_number: asrm.sequence_number,
+                },
+                asrm.retransmit_flag,
+            ),
+            ParsedMessage::BinaryAcknowledge(ack) => {
+                self.record_acknowledgement(AddressedMessageKind::Binary, ack)
+            }
+            ParsedMessage::SafetyRelatedAcknowledgement(ack) => {
+                self.record_acknowledgement(AddressedMessageKind::SafetyRelated, ack)
+            }
+            _ => {}
+        }
+    }
+
+    /// Messages waiting for acknowledgement in key order.
+    pub fn pending(&self) -> Vec<AddressedMessageKey> {
+        Self::sorted_keys(&self.pending)
+    }
+
+    /// Acknowledged messages in key order.
+    pub fn acknowledged(&self) -> Vec<AddressedMessageKey> {
+        Self::sorted_keys(&self.acknowledged)
+    }
+
+    /// Record an addressed message. A retransmission of an already acknowledged message keeps
+    /// it acknowledged, otherwise the same key means the sequence number has been reused.
+    fn record_message(&mut self, key: AddressedMessageKey, retransmit: bool) {
+        if self.unmatched_acks.remove(&key).is_some() {
+            self.pending.remove(&key);
+            self.acknowledged.insert(key, self.update_count);
+        } else if !(retransmit && self.acknowledged.contains_key(&key)) {
+            self.acknowledged.remove(&key);
+            self.pending.insert(key, self.update_count);
+        }
+    }
+
+    /// Match the stations acknowledged by a type 7 or 13 message with pending messages.
+    fn record_acknowledgement(
+        &mut self,
+        kind: AddressedMessageKind,
+        ack: &SafetyRelatedAcknowledgement,
+    ) {
+        for (source_mmsi, sequence_number) in ack.acknowledgements() {
+            let key = AddressedMessageKey {
+                kind,
+                source_mmsi,
+                destination_mmsi: ack.mmsi,
+                sequence_number,
+            };
+            if self.pending.remove(&key).is_some() {
+                self.acknowledged.insert(key, self.update_count);
+            } else if !self.acknowledged.contains_key(&key) {
+                self.unmatched_acks.insert(key, self.update_count);
+            }
+        }
+    }
+
+    /// Drop entries which haven't changed within `max_age` updates.
+    fn expire(&mut self) {
+        let update_count = self.update_count;
+        let max_age = self.max_age;
+        for map in [
+            &mut self.pending,
+            &mut self.acknowledged,
+            &mut self.unmatched_acks,
+        ] {
+            map.retain(|_, seen| update_count - *seen <= max_age);
+        }
+    }
+
+    fn sorted_keys(map: &HashMap<AddressedMessageKey, u64>) -> Vec<AddressedMessageKey> {
+        let mut keys: Vec<AddressedMessageKey> = map.keys().copied().collect();
+        keys.sort();
+        keys
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: u32 = 211217560;
+    const DESTINATION: u32 = 211378120;
+
+    fn binary(sequence_number: u8, retransmit_flag: bool) -> ParsedMessage {
+        ParsedMessage::BinaryAddressedMessage(BinaryAddressedMessage {
+            mmsi: SOURCE,
+            destination_mmsi: DESTINATION,
+            sequence_number,
+            retransmit_flag,
+            ..Default::default()
+        })
+    }
+
+    fn safety_related(sequence_number: u8) -> ParsedMessage {
+        ParsedMessage::AddressedSafetyRelatedMessage(AddressedSafetyRelatedMessage {
+            source_mmsi: SOURCE,
+            destination_mmsi: DESTINATION,
+            sequence_number,
+            ..Default::default()
+        })
+    }
+
+    fn ack(message_type: u8, sequence_number: u8) -> SafetyRelatedAcknowledgement {
+        SafetyRelatedAcknowledgement {
+            message_type,
+            mmsi: DESTINATION,
+            acknowledgement_count: 1,
+            mmsi1: SOURCE,
+            mmsi1_seq: sequence_number,
+            ..Default::default()
+        }
+    }
+
+    fn key(kind: AddressedMessageKind, sequence_number: u8) -> AddressedMessageKey {
+        AddressedMessageKey {
+            kind,
+            source_mmsi: SOURCE,
+            destination_mmsi: DESTINATION,
+            sequence_number,
+        }
+    }
+
+    #[test]
+    fn test_ack_after_message() {
+        let mut t = AckTracker::new(10);
+        t.update(&binary(1, false));
+        t.update(&safety_related(1));
+        assert_eq!(
+            t.pending(),
+            vec![
+                key(AddressedMessageKind::Binary, 1),
+                key(AddressedMessageKind::SafetyRelated, 1)
+            ]
+        );
+        assert!(t.acknowledged().is_empty());
+
+        // Type 13 acknowledges only the safety related message
+        t.update(&ParsedMessage::SafetyRelatedAcknowledgement(ack(13, 1)));
+        assert_eq!(t.pending(), vec![key(AddressedMessageKind::Binary, 1)]);
+        assert_eq!(
+            t.acknowledged(),
+            vec![key(AddressedMessageKind::SafetyRelated, 1)]
+        );
+
+        // Wrong sequence number
+        t.update(&ParsedMessage::BinaryAcknowledge(ack(7, 2)));
+        assert_eq!(t.pending(), vec![key(AddressedMessageKind::Binary, 1)]);
+
+        t.update(&ParsedMessage::BinaryAcknowledge(ack(7, 1)));
+        assert!(t.pending().is_empty());
+        assert_eq!(t.acknowledged().len(), 2);
+    }
+
+    #[test]
+    fn test_ack_before_message() {
+        let mut t = AckTracker::new(10);
+        t.update(&ParsedMessage::BinaryAcknowledge(ack(7, 3)));
+        assert!(t.pending().is_empty());
+        assert!(t.acknowledged().is_empty());
+
+        t.update(&binary(3, false));
+        assert!(t.pending().is_empty());
+        assert_eq!(t.acknowledged(), vec![key(AddressedMessageKind::Binary, 3)]);
+    }
+
+    #[test]
+    fn test_duplicate_ack() {
+        let mut t = AckTracker::new(10);
+        t.update(&binary(0, false));
+        t.update(&ParsedMessage::BinaryAcknowledge(ack(7, 0)));
+        t.update(&ParsedMessage::BinaryAcknowledge(ack(7, 0)));
+        assert_eq!(t.acknowledged(), vec![key(AddressedMessageKind::Binary, 0)]);
+
+        // The duplicate isn't kept as an early acknowledgement of the next message
+        t.update(&binary(0, false));
+        assert_eq!(t.pending(), vec![key(AddressedMessageKind::Binary, 0)]);
+        assert!(t.acknowledged().is_empty());
+    }
+
+    #[test]
+    fn test_sequence_number_reuse() {
+        let mut t = AckTracker::new(10);
+        t.update(&binary(2, false));
+        t.update(&ParsedMessage::BinaryAcknowledge(ack(7, 2)));
+
+        // Retransmission of the acknowledged message
+        t.update(&binary(2, true));
+        assert!(t.pending().is_empty());
+        assert_eq!(t.acknowledged(), vec![key(AddressedMessageKind::Binary, 2)]);
+
+        // New message with the same sequence number
+        t.update(&binary(2, false));
+        assert_eq!(t.pending(), vec![key(AddressedMessageKind::Binary, 2)]);
+        assert!(t.acknowledged().is_empty());
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut t = AckTracker::new(2);
+        t.update(&binary(1, false));
+        t.update(&ParsedMessage::Incomplete);
+        t.update(&ParsedMessage::Incomplete);
+        assert_eq!(t.pending().len(), 1);
+        t.update(&ParsedMessage::Incomplete);
+        assert!(t.pending().is_empty());
+
+        // Expired early acknowledgement doesn't match
+        t.update(&ParsedMessage::BinaryAcknowledge(ack(7, 1)));
+        t.update(&ParsedMessage::Incomplete);
+        t.update(&ParsedMessage::Incomplete);
+        t.update(&binary(1, false));
+        assert_eq!(t.pending(), vec![key(AddressedMessageKind::Binary, 1)]);
+    }
+
+    #[test]
+    fn test_parsed_sentences() {
+        let mut p = NmeaParser::new();
+        let mut t = AckTracker::new(100);
+        for s in [
+            "!AIVDM,1,1,,A,<39KdV8jIGtP85<<?,0*23",
+            "!AIVDM,1,1,,A,739UOj0jFs9R,0*6F",
+        ] {
+            t.update(&p.parse_sentence(s).unwrap());
+        }
+        assert_eq!(
+            t.pending(),
+            vec![key(AddressedMessageKind::SafetyRelated, 2)]
+        );
+
+        t.update(&p.parse_sentence("!AIVDM,1,1,,A,=39UOj0jFs9R,0*65").unwrap());
+        assert!(t.pending().is_empty());
+        assert_eq!(
+            t.acknowledged(),
+            vec![key(AddressedMessageKind::SafetyRelated, 2)]
+        );
+    }
+}
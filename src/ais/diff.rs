@@ -0,0 +1,187 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Fields which differ between two `VesselStaticData` records, see `VesselStaticData::diff()`.
+///
+/// A field is changed if a value appears (`None` to `Some`) or the value is different. A value
+/// disappearing (`Some` to `None`) isn't a change, because it typically means that the newer
+/// message didn't carry the field (e.g. type 24 part A or B only); it's reported with
+/// `data_lost` instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VesselStaticDataDiff {
+    /// Vessel name changed
+    pub name: bool,
+
+    /// Call sign changed
+    pub call_sign: bool,
+
+    /// Destination changed
+    pub destination: bool,
+
+    /// Estimated time of arrival changed
+    pub eta: bool,
+
+    /// Draught changed
+    pub draught10: bool,
+
+    /// Any of the dimensions to bow, stern, port or starboard changed
+    pub dimensions: bool,
+
+    /// Ship type changed (`ShipType::NotAvailable` counts as no value)
+    pub ship_type: bool,
+
+    /// Some of the compared fields had a value earlier but not anymore
+    pub data_lost: bool,
+}
+
+impl VesselStaticDataDiff {
+    /// True if any of the compared fields changed.
+    pub fn has_changes(&self) -> bool {
+        self.name
+            || self.call_sign
+            || self.destination
+            || self.eta
+            || self.draught10
+            || self.dimensions
+            || self.ship_type
+    }
+
+    /// True if the voyage related data (destination, ETA or draught) changed.
+    pub fn has_voyage_change(&self) -> bool {
+        self.destination || self.eta || self.draught10
+    }
+}
+
+/// Change of a single field: (changed, lost).
+fn field_change<T: PartialEq>(old: &Option<T>, new: &Option<T>) -> (bool, bool) {
+    match (old, new) {
+        (None, Some(_)) => (true, false),
+        (Some(a), Some(b)) => (a != b, false),
+        (Some(_), None) => (false, true),
+        (None, None) => (false, false),
+    }
+}
+
+impl VesselStaticData {
+    /// Compare this earlier record with a newer one of the same vessel, e.g. to react only when
+    /// the voyage data of the periodically repeated type 5 messages changes.
+    pub fn diff(&self, newer: &VesselStaticData) -> VesselStaticDataDiff {
+        let ship_type = |vsd: &VesselStaticData| match vsd.ship_type {
+            ShipType::NotAvailable => None,
+            t => Some(t),
+        };
+        let changes = [
+            field_change(&self.name, &newer.name),
+            field_change(&self.call_sign, &newer.call_sign),
+            field_change(&self.destination, &newer.destination),
+            field_change(&self.eta, &newer.eta),
+            field_change(&self.draught10, &newer.draught10),
+            field_change(&self.dimension_to_bow, &newer.dimension_to_bow),
+            field_change(&self.dimension_to_stern, &newer.dimension_to_stern),
+            field_change(&self.dimension_to_port, &newer.dimension_to_port),
+            field_change(&self.dimension_to_starboard, &newer.dimension_to_starboard),
+            field_change(&ship_type(self), &ship_type(newer)),
+        ];
+        VesselStaticDataDiff {
+            name: changes[0].0,
+            call_sign: changes[1].0,
+            destination: changes[2].0,
+            eta: changes[3].0,
+            draught10: changes[4].0,
+            dimensions: changes[5..9].iter().any(|(changed, _)| *changed),
+            ship_type: changes[9].0,
+            data_lost: changes.iter().any(|(_, lost)| *lost),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vessel_static_data_diff() {
+        let old = VesselStaticData::builder()
+            .mmsi(230992580)
+            .name("ALANDIA")
+            .call_sign("OJPL")
+            .ship_type(ShipType::Cargo)
+            .dimensions(100, 20, 5, 10)
+            .draught10(52)
+            .destination("MARIEHAMN")
+            .build();
+
+        // Identical records
+        let diff = old.diff(&old.clone());
+        assert_eq!(diff, VesselStaticDataDiff::default());
+        assert!(!diff.has_changes());
+        assert!(!diff.has_voyage_change());
+
+        // Different value is a change
+        let mut new = old.clone();
+        new.destination = Some(Ais6BitString::from_codes(&[
+            19, 20, 15, 3, 11, 8, 15, 12, 13,
+        ]));
+        new.draught10 = Some(60);
+        let diff = old.diff(&new);
+        assert!(diff.destination);
+        assert!(diff.draught10);
+        assert!(!diff.eta);
+        assert!(!diff.name);
+        assert!(diff.has_voyage_change());
+        assert!(!diff.data_lost);
+
+        // Padding doesn't make a change
+        let mut new = old.clone();
+        new.call_sign = Some(Ais6BitString::from_codes(&[15, 10, 16, 12, 0, 0, 0]));
+        assert!(!old.diff(&new).has_changes());
+
+        // None to Some is a change
+        let mut new = old.clone();
+        new.eta = Utc.with_ymd_and_hms(2024, 5, 14, 12, 0, 0).single();
+        let diff = old.diff(&new);
+        assert!(diff.eta);
+        assert!(diff.has_voyage_change());
+
+        // Some to None is data loss, not a change
+        let mut new = old.clone();
+        new.destination = None;
+        new.dimension_to_port = None;
+        new.ship_type = ShipType::NotAvailable;
+        let diff = old.diff(&new);
+        assert!(!diff.has_changes());
+        assert!(diff.data_lost);
+
+        // Static fields outside the voyage data
+        let mut new = old.clone();
+        new.name = Some(Ais6BitString::from_codes(&[2, 9, 18, 11, 1]));
+        new.dimension_to_bow = Some(101);
+        new.ship_type = ShipType::Tanker;
+        let diff = old.diff(&new);
+        assert!(diff.name);
+        assert!(diff.dimensions);
+        assert!(diff.ship_type);
+        assert!(!diff.call_sign);
+        assert!(diff.has_changes());
+        assert!(!diff.has_voyage_change());
+    }
+}
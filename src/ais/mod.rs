@@ -16,10 +16,14 @@ limitations under the License.
 
 //! AIS VDM/VDO data structures
 
+pub(crate) mod ack;
+pub(crate) mod diff;
+pub(crate) mod tracker;
 pub(crate) mod vdm_t1t2t3;
 pub(crate) mod vdm_t4;
 pub(crate) mod vdm_t5;
 pub(crate) mod vdm_t6;
+pub(crate) mod vdm_t7;
 pub(crate) mod vdm_t9;
 pub(crate) mod vdm_t10;
 pub(crate) mod vdm_t11;
@@ -41,6 +45,10 @@ pub(crate) mod vdm_t26;
 pub(crate) mod vdm_t27;
 
 use super::*;
+use serde::Serialize;
+pub use ack::{AckTracker, AddressedMessageKey, AddressedMessageKind};
+pub use diff::VesselStaticDataDiff;
+pub use tracker::{VesselRecord, VesselTracker};
 pub use vdm_t4::BaseStationReport;
 pub use vdm_t6::BinaryAddressedMessage;
 pub use vdm_t9::StandardSarAircraftPositionReport;
@@ -51,17 +59,20 @@ pub use vdm_t14::SafetyRelatedBroadcastMessage;
 pub use vdm_t15::{Interrogation, InterrogationCase};
 pub use vdm_t16::AssignmentModeCommand;
 pub use vdm_t17::DgnssBroadcastBinaryMessage;
+pub use vdm_t19::ExtendedClassBPositionReport;
 pub use vdm_t20::{DataLinkManagementMessage};
 pub use vdm_t21::{AidToNavigationReport, NavAidType};
 pub use vdm_t22::{ChannelManagement};
 pub use vdm_t23::{GroupAssignmentCommand};
+pub use vdm_t24::Equipment;
 pub use vdm_t25::{SingleSlotBinaryMessage};
 pub use vdm_t26::{MultipleSlotBinaryMessage};
 
 // -------------------------------------------------------------------------------------------------
 
 /// AIS station based on talker id
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Station {
     BaseStation,             // !AB
     DependentAisBaseStation, // !AD
@@ -123,6 +134,7 @@ impl core::str::FromStr for Station {
 
 /// Types 1, 2, 3 and 18: Position Report Class A, and Long Range AIS Broadcast message
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct VesselDynamicData {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -130,13 +142,22 @@ pub struct VesselDynamicData {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Class A or Class B
     pub ais_type: AisClass,
 
     /// User ID (30 bits)
     pub mmsi: u32,
 
-    // TODO: timestamp
     /// Navigation status
     pub nav_status: NavigationStatus,
 
@@ -158,15 +179,26 @@ pub struct VesselDynamicData {
     /// Longitude
     pub longitude: Option<f64>,
 
-    /// Course over ground
+    /// True if latitude and longitude are encoded with 1/10 minute resolution (type 27) instead
+    /// of the usual 1/10000 minute resolution
+    pub low_resolution_position: bool,
+
+    /// Course over ground in range `[0, 360)`
     pub cog: Option<f64>,
 
-    /// True heading (0-359)
+    /// True heading (0-359). Out of range values 360-510 are wrapped, so 360 becomes 0.
     pub heading_true: Option<f64>,
 
     /// Derived from UTC second (6 bits)
     pub timestamp_seconds: u8,
 
+    /// Full timestamp resolved from `timestamp_seconds` and the latest base station report
+    /// (type 4 or 11) or `NmeaParser::set_current_time()`. The second is placed within half a
+    /// minute of the reference time. `None` if there's no reference time, the second isn't
+    /// available (60-63) or the `time` feature is disabled.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp: Option<Timestamp>,
+
     /// Positioning system metadata (included in seconds in UTC timestamp)
     pub positioning_system_meta: Option<PositioningSystemMeta>,
 
@@ -184,6 +216,16 @@ pub struct VesselDynamicData {
     /// fixing device; false = RAIM not in use = default; true = RAIM in use
     pub raim_flag: bool,
 
+    /// Regional reserved bits, most significant bit first:
+    /// types 1-3: bits 143-146 (regional application field of ITU-R M.1371-1, which covers
+    /// the special manoeuvre indicator of later revisions; Inland AIS blue sign in bits 143-144),
+    /// type 18: bits 38-45.
+    /// `None` for type 27.
+    pub regional: Option<u8>,
+
+    /// Second regional reserved field of type 18 (bits 139-140). `None` for other types.
+    pub regional_b: Option<u8>,
+
     /// Class B unit flag: false = Class B SOTDMA unit, true = Class B "CS" unit.
     pub class_b_unit_flag: Option<bool>,
 
@@ -208,9 +250,10 @@ pub struct VesselDynamicData {
     /// true  = Frequency management via Message 22
     pub class_b_msg22_flag: Option<bool>,
 
-    /// Mode flag:
+    /// Mode flag (assigned mode flag of type 18):
     /// false = Station operating in autonomous and continuous mode = default
     /// true  = Station operating in assigned mode
+    /// `None` for types 1-3 and 27 which don't carry the flag.
     pub class_b_mode_flag: Option<bool>,
 
     /// Communication state selector flag
@@ -225,7 +268,8 @@ pub struct VesselDynamicData {
 }
 
 /// AIS class which is either Class A or Class B
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum AisClass {
     /// AIS class not known.
     Unknown,
@@ -253,6 +297,21 @@ impl core::fmt::Display for AisClass {
     }
 }
 
+impl VesselDynamicData {
+    /// Inland AIS blue sign of types 1-3 (bits 143-144 of the regional application field).
+    /// `None` if the blue sign isn't available or the message type doesn't carry it.
+    pub fn blue_sign(&self) -> Option<bool> {
+        if self.ais_type != AisClass::ClassA {
+            return None;
+        }
+        match self.regional? >> 2 {
+            1 => Some(false),
+            2 => Some(true),
+            _ => None,
+        }
+    }
+}
+
 impl LatLon for VesselDynamicData {
     fn latitude(&self) -> Option<f64> {
         self.latitude
@@ -263,8 +322,28 @@ impl LatLon for VesselDynamicData {
     }
 }
 
+impl PositionReport for VesselDynamicData {
+    fn sog_knots(&self) -> Option<f64> {
+        self.sog_knots
+    }
+
+    fn cog(&self) -> Option<f64> {
+        self.cog
+    }
+
+    fn heading(&self) -> Option<f64> {
+        self.heading_true
+    }
+
+    /// Available only when the full `timestamp` has been resolved.
+    fn fix_time_seconds(&self) -> Option<u32> {
+        self.timestamp.as_ref().map(seconds_of_day)
+    }
+}
+
 /// Navigation status for VesselDynamicData
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum NavigationStatus {
     UnderWayUsingEngine = 0,        // 0
     AtAnchor = 1,                   // 1
@@ -345,7 +424,8 @@ impl Default for NavigationStatus {
 // -------------------------------------------------------------------------------------------------
 
 /// Location metadata about positioning system
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PositioningSystemMeta {
     Operative, // When timestamp second is 0-59
     ManualInputMode,
@@ -366,8 +446,47 @@ impl core::fmt::Display for PositioningSystemMeta {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Type of an AIS emergency device identified by its MMSI
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EmergencyDeviceType {
+    /// AIS-SART, search and rescue transmitter (MMSI 970xxyyyy)
+    Sart,
+
+    /// MOB, man overboard device (MMSI 972xxyyyy)
+    Mob,
+
+    /// EPIRB-AIS, emergency position indicating radio beacon (MMSI 974xxyyyy)
+    Epirb,
+}
+
+impl core::fmt::Display for EmergencyDeviceType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EmergencyDeviceType::Sart => write!(f, "AIS-SART"),
+            EmergencyDeviceType::Mob => write!(f, "MOB"),
+            EmergencyDeviceType::Epirb => write!(f, "EPIRB-AIS"),
+        }
+    }
+}
+
+/// Classify the MMSI of an AIS emergency device (ITU-R M.585) by its 970, 972 or 974 prefix.
+/// `None` is returned for other MMSIs. Whether the device is active or in test mode is told by
+/// the navigation status (`NavigationStatus::AisSartIsActive`) and the message content.
+pub fn is_sart_mob_epirb(mmsi: u32) -> Option<EmergencyDeviceType> {
+    match mmsi / 1_000_000 {
+        970 => Some(EmergencyDeviceType::Sart),
+        972 => Some(EmergencyDeviceType::Mob),
+        974 => Some(EmergencyDeviceType::Epirb),
+        _ => None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Vessel rotation direction
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum RotDirection {
     /// Turning port (left, when seen by an observer aboard the vessel looking forward)
     Port,
@@ -397,12 +516,172 @@ impl core::fmt::Display for RotDirection {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Text field of 6-bit AIS characters, such as vessel name, call sign or destination. The raw
+/// character codes are kept so that padding and embedded '@' characters, which some encoders
+/// emit, can be detected. The text (`to_string()`) ends at the first '@' and has trailing
+/// spaces removed. Comparison and hashing use the text.
+#[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ais6BitString {
+    codes: Vec<u8>,
+}
+
+impl Ais6BitString {
+    /// Construct from raw 6-bit character codes. Only the lowest 6 bits of each code are used.
+    pub fn from_codes(codes: &[u8]) -> Ais6BitString {
+        Ais6BitString {
+            codes: codes.iter().map(|c| c & 0x3f).collect(),
+        }
+    }
+
+    /// Raw 6-bit character codes of the field including the padding.
+    pub fn codes(&self) -> &[u8] {
+        &self.codes
+    }
+
+    /// All the characters of the field including the padding, '@' characters and trailing
+    /// spaces.
+    pub fn raw_text(&self) -> String {
+        self.codes.iter().map(|c| Self::code_to_char(*c)).collect()
+    }
+
+    /// True if the field contains text after an '@' character, e.g. "EVER@DIADEM". Such text is
+    /// not included in `to_string()`.
+    pub fn had_embedded_at(&self) -> bool {
+        match self.codes.iter().position(|c| *c == 0) {
+            Some(i) => self.codes[i..].iter().any(|c| *c != 0 && *c != 32),
+            None => false,
+        }
+    }
+
+    /// True if the field consists of text optionally followed by uniform padding of either '@'
+    /// characters or spaces. Embedded '@' characters and padding mixing spaces and '@'
+    /// characters make the field unclean.
+    pub fn is_clean(&self) -> bool {
+        let padding = &self.codes[self.text_codes().len()..];
+        !self.had_embedded_at() && padding.iter().all(|c| Some(c) == padding.first())
+    }
+
+    /// Length of the text in characters.
+    pub fn len(&self) -> usize {
+        self.text_codes().len()
+    }
+
+    /// True if the text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.text_codes().is_empty()
+    }
+
+    /// Append the characters of another field, e.g. the name extension of AIS type 21.
+    pub(crate) fn append(&mut self, other: &Ais6BitString) {
+        self.codes.extend_from_slice(&other.codes);
+    }
+
+    /// Character codes of the text, i.e. up to the first '@' without trailing spaces.
+    fn text_codes(&self) -> &[u8] {
+        let end = self
+            .codes
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(self.codes.len());
+        let trimmed = self.codes[..end]
+            .iter()
+            .rposition(|c| *c != 32)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        &self.codes[..trimmed]
+    }
+
+    fn code_to_char(code: u8) -> char {
+        if code < 32 {
+            (64 + code) as char
+        } else {
+            code as char
+        }
+    }
+}
+
+impl core::fmt::Display for Ais6BitString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for c in self.text_codes() {
+            write!(f, "{}", Self::code_to_char(*c))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Ais6BitString {
+    fn eq(&self, other: &Ais6BitString) -> bool {
+        self.text_codes() == other.text_codes()
+    }
+}
+
+impl Eq for Ais6BitString {}
+
+impl core::hash::Hash for Ais6BitString {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.text_codes().hash(state);
+    }
+}
+
+impl PartialEq<str> for Ais6BitString {
+    fn eq(&self, other: &str) -> bool {
+        let mut chars = other.chars();
+        self.text_codes()
+            .iter()
+            .all(|c| chars.next() == Some(Self::code_to_char(*c)))
+            && chars.next().is_none()
+    }
+}
+
+impl PartialEq<&str> for Ais6BitString {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl From<&str> for Ais6BitString {
+    /// Encode text to 6-bit characters. Lowercase letters are converted to uppercase and
+    /// characters outside the 6-bit alphabet are replaced with '?'.
+    fn from(s: &str) -> Ais6BitString {
+        Ais6BitString {
+            codes: s
+                .chars()
+                .map(|c| match c.to_ascii_uppercase() {
+                    c @ '@'..='_' => c as u8 - 64,
+                    c @ ' '..='?' => c as u8,
+                    _ => b'?',
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Ais6BitString> for String {
+    fn from(s: Ais6BitString) -> String {
+        s.to_string()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Types 5 and 24: Ship static voyage related data, and boat static data report.
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct VesselStaticData {
     /// True if the data is about own vessel, false if about other vessel.
     pub own_vessel: bool,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Class A or Class B
     pub ais_type: AisClass,
 
@@ -416,10 +695,10 @@ pub struct VesselStaticData {
     pub imo_number: Option<u32>,
 
     /// Call sign (7 ASCII characters)
-    pub call_sign: Option<String>,
+    pub call_sign: Option<Ais6BitString>,
 
     /// Name (20 ASCII characters)
-    pub name: Option<String>,
+    pub name: Option<Ais6BitString>,
 
     /// Type of ship (first 4 of 8 bits)
     pub ship_type: ShipType,
@@ -449,13 +728,20 @@ pub struct VesselStaticData {
     pub position_fix_type: Option<PositionFixType>,
 
     /// ETA (20 bits)
-    pub eta: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub eta: Option<Timestamp>,
 
     /// Maximum present static draught in decimetres (1-255; 8 bits)
     pub draught10: Option<u8>,
 
     /// Destination (120 ASCII characters)
-    pub destination: Option<String>,
+    pub destination: Option<Ais6BitString>,
+
+    /// Data terminal ready (type 5):
+    /// true = ready,
+    /// false = not ready,
+    /// `None` = the message ends before the DTE bit
+    pub dte: Option<bool>,
 
     /// Class B mothership MMSI
     pub mothership_mmsi: Option<u32>,
@@ -464,7 +750,8 @@ pub struct VesselStaticData {
 // -------------------------------------------------------------------------------------------------
 
 /// Ship type derived from combined ship and cargo type field
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ShipType {
     NotAvailable = 0,             // 0
     Reserved1 = 10,               // 1x
@@ -588,7 +875,8 @@ impl core::fmt::Display for ShipType {
 // -------------------------------------------------------------------------------------------------
 
 /// Cargo type derived from combined ship and cargo type field
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum CargoType {
     Undefined = 10,          // x0
     HazardousCategoryA = 11, // x1
@@ -654,7 +942,8 @@ impl Default for CargoType {
 // -------------------------------------------------------------------------------------------------
 
 /// EPFD position fix types
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PositionFixType {
     Undefined = 0,                  // 0
     GPS = 1,                        // 1
@@ -665,6 +954,7 @@ pub enum PositionFixType {
     IntegratedNavigationSystem = 6, // 6
     Surveyed = 7,                   // 7
     Galileo = 8,                    // 8
+    InternalGnss = 15,              // 15
 }
 
 impl PositionFixType {
@@ -679,6 +969,7 @@ impl PositionFixType {
             6 => PositionFixType::IntegratedNavigationSystem,
             7 => PositionFixType::Surveyed,
             8 => PositionFixType::Galileo,
+            15 => PositionFixType::InternalGnss,
             _ => {
                 warn!("Unrecognized position fix type: {}", raw);
                 PositionFixType::Undefined
@@ -705,11 +996,27 @@ impl core::fmt::Display for PositionFixType {
             }
             PositionFixType::Surveyed => write!(f, "surveyed"),
             PositionFixType::Galileo => write!(f, "Galileo"),
+            PositionFixType::InternalGnss => write!(f, "internal GNSS"),
         }
     }
 }
 
 impl VesselStaticData {
+    /// Vessel name for display: the name without '@' padding and with leading, trailing and
+    /// repeated spaces removed.
+    pub fn normalized_name(&self) -> Option<String> {
+        let name = self.name.as_ref()?.to_string();
+        let normalized = name
+            .split(' ')
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>();
+        if normalized.is_empty() {
+            None
+        } else {
+            Some(normalized.join(" "))
+        }
+    }
+
     /// Decode ISO 3166 country code from MID part of MMSI.
     pub fn country(&self) -> Option<&'static str> {
         match self.mmsi / 1000000 {
@@ -1011,4 +1318,592 @@ impl VesselStaticData {
             _ => None,
         }
     }
+
+    /// Create a builder for test fixtures and synthetic data. Fields which aren't set keep their
+    /// default values.
+    pub fn builder() -> VesselStaticDataBuilder {
+        VesselStaticDataBuilder::default()
+    }
+}
+
+/// Builder of `VesselStaticData`.
+#[derive(Default, Clone, Debug)]
+pub struct VesselStaticDataBuilder {
+    vsd: VesselStaticData,
+}
+
+impl VesselStaticDataBuilder {
+    /// Set whether the data is about own vessel.
+    pub fn own_vessel(&mut self, own_vessel: bool) -> &mut Self {
+        self.vsd.own_vessel = own_vessel;
+        self
+    }
+
+    /// Set AIS class.
+    pub fn ais_type(&mut self, ais_type: AisClass) -> &mut Self {
+        self.vsd.ais_type = ais_type;
+        self
+    }
+
+    /// Set MMSI.
+    pub fn mmsi(&mut self, mmsi: u32) -> &mut Self {
+        self.vsd.mmsi = mmsi;
+        self
+    }
+
+    /// Set IMO number.
+    pub fn imo_number(&mut self, imo_number: u32) -> &mut Self {
+        self.vsd.imo_number = Some(imo_number);
+        self
+    }
+
+    /// Set call sign.
+    pub fn call_sign(&mut self, call_sign: &str) -> &mut Self {
+        self.vsd.call_sign = Some(call_sign.into());
+        self
+    }
+
+    /// Set vessel name.
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.vsd.name = Some(name.into());
+        self
+    }
+
+    /// Set ship type.
+    pub fn ship_type(&mut self, ship_type: ShipType) -> &mut Self {
+        self.vsd.ship_type = ship_type;
+        self
+    }
+
+    /// Set cargo type.
+    pub fn cargo_type(&mut self, cargo_type: CargoType) -> &mut Self {
+        self.vsd.cargo_type = cargo_type;
+        self
+    }
+
+    /// Set dimensions from the position reference to bow, stern, port and starboard in metres.
+    pub fn dimensions(&mut self, bow: u16, stern: u16, port: u16, starboard: u16) -> &mut Self {
+        self.vsd.dimension_to_bow = Some(bow);
+        self.vsd.dimension_to_stern = Some(stern);
+        self.vsd.dimension_to_port = Some(port);
+        self.vsd.dimension_to_starboard = Some(starboard);
+        self
+    }
+
+    /// Set position fix type.
+    pub fn position_fix_type(&mut self, position_fix_type: PositionFixType) -> &mut Self {
+        self.vsd.position_fix_type = Some(position_fix_type);
+        self
+    }
+
+    /// Set ETA.
+    pub fn eta(&mut self, eta: Timestamp) -> &mut Self {
+        self.vsd.eta = Some(eta);
+        self
+    }
+
+    /// Set draught in decimetres.
+    pub fn draught10(&mut self, draught10: u8) -> &mut Self {
+        self.vsd.draught10 = Some(draught10);
+        self
+    }
+
+    /// Set destination.
+    pub fn destination(&mut self, destination: &str) -> &mut Self {
+        self.vsd.destination = Some(destination.into());
+        self
+    }
+
+    /// Set mothership MMSI.
+    pub fn mothership_mmsi(&mut self, mothership_mmsi: u32) -> &mut Self {
+        self.vsd.mothership_mmsi = Some(mothership_mmsi);
+        self
+    }
+
+    /// Create the vessel static data.
+    pub fn build(&self) -> VesselStaticData {
+        self.vsd.clone()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decode AIS message bits by message type. Decoders registered with
+/// `NmeaParser::register_binary_decoder()` take precedence over the built-in ones. This is
+/// shared by `NmeaParser::parse_sentence()` and `NmeaParser::parse_ais_payload()`.
+pub(crate) fn dispatch_message(
+    bv: &BitVec,
+    fill_bits: u8,
+    station: Station,
+    radio_channel: Option<char>,
+    own_vessel: bool,
+    store: &mut ParseContext,
+) -> Result<ParsedMessage, ParseError> {
+    let sentence_type = if own_vessel { "!VDO" } else { "!VDM" };
+    let message_type = pick_u64(bv, 0, 6);
+    if let Some(decoder) = pick_dac_fid(bv).and_then(|key| store.settings.binary_decoders.get(&key))
+    {
+        return decoder(bv);
+    }
+    if let Some(min_len) = min_message_bits(message_type) {
+        if bv.len() < min_len {
+            return Err(ParseError::InvalidSentence(format!(
+                "{} message type {} too short: {} bits, at least {} required",
+                sentence_type,
+                message_type,
+                bv.len(),
+                min_len
+            )));
+        }
+    }
+    let res = match message_type {
+        // Position report with SOTDMA/ITDMA
+        1..=3 => vdm_t1t2t3::handle(bv, station, radio_channel, store, own_vessel),
+        // Base station report
+        4 => vdm_t4::handle(bv, station, radio_channel, store, own_vessel),
+        // Ship static voyage related data
+        5 => vdm_t5::handle(bv, station, radio_channel, store, own_vessel),
+        // Addressed binary message
+        6 => vdm_t6::handle(bv, station, radio_channel, own_vessel),
+        // Binary acknowledge
+        7 => vdm_t7::handle(bv, station, radio_channel, own_vessel),
+        // Binary broadcast message
+        8 => {
+            // TODO: implementation
+            Err(ParseError::UnsupportedSentenceType(format!(
+                "Unsupported {} message type: {}",
+                sentence_type, message_type
+            )))
+        }
+        // Standard SAR aircraft position report
+        9 => vdm_t9::handle(bv, station, radio_channel, own_vessel),
+        // UTC and Date inquiry
+        10 => vdm_t10::handle(bv, station, radio_channel, own_vessel),
+        // UTC and date response
+        11 => vdm_t11::handle(bv, station, radio_channel, store, own_vessel),
+        // Addressed safety related message
+        12 => vdm_t12::handle(bv, station, radio_channel, own_vessel),
+        // Safety related acknowledge
+        13 => vdm_t13::handle(bv, station, radio_channel, own_vessel),
+        // Safety related broadcast message
+        14 => vdm_t14::handle(bv, station, radio_channel, own_vessel),
+        // Interrogation
+        15 => vdm_t15::handle(bv, station, radio_channel, own_vessel),
+        // Assigned mode command
+        16 => vdm_t16::handle(bv, station, radio_channel, own_vessel),
+        // GNSS binary broadcast message
+        17 => vdm_t17::handle(bv, fill_bits, station, radio_channel, own_vessel),
+        // Standard class B CS position report
+        18 => vdm_t18::handle(bv, station, radio_channel, own_vessel),
+        // Extended class B equipment position report
+        19 => vdm_t19::handle(bv, station, radio_channel, store, own_vessel),
+        // Data link management
+        20 => vdm_t20::handle(bv, station, radio_channel, own_vessel),
+        // Aids-to-navigation report
+        21 => vdm_t21::handle(bv, station, radio_channel, store, own_vessel),
+        // Channel management
+        22 => vdm_t22::handle(bv, station, radio_channel, own_vessel),
+        // Group assignment command
+        23 => vdm_t23::handle(bv, station, radio_channel, store, own_vessel),
+        // Class B CS static data report
+        24 => vdm_t24::handle(bv, station, radio_channel, store, own_vessel),
+        // Single slot binary message
+        25 => vdm_t25::handle(bv, station, radio_channel, own_vessel),
+        // Multiple slot binary message
+        26 => vdm_t26::handle(bv, station, radio_channel, own_vessel),
+        // Long range AIS broadcast message
+        27 => vdm_t27::handle(bv, station, radio_channel, own_vessel),
+        _ => Err(ParseError::UnsupportedSentenceType(format!(
+            "Unsupported {} message type: {}",
+            sentence_type, message_type
+        ))),
+    };
+    res.map(|msg| store.resolve_ais_timestamp(msg))
+}
+
+/// Minimum number of payload bits the built-in decoder of the given message type needs. Shorter
+/// payloads would be decoded with zero-padded fields, so they are rejected instead. Variable
+/// length messages require the fixed part only.
+fn min_message_bits(message_type: u64) -> Option<usize> {
+    match message_type {
+        1..=4 | 9 | 11 | 18 | 22 => Some(168),
+        // The DTE and spare bits at the end of type 5 are often left out
+        5 => Some(420),
+        6 | 15 => Some(88),
+        7 | 10 | 12 | 13 | 20 => Some(72),
+        14 | 25 => Some(40),
+        16 | 27 => Some(96),
+        17 => Some(80),
+        19 => Some(312),
+        21 => Some(272),
+        23 | 24 => Some(160),
+        26 => Some(60),
+        _ => None,
+    }
+}
+
+/// Decode course over ground in 1/10 degrees (0-3599, 3600 = not available). Values above
+/// 3600 aren't allowed by the standard and are returned as `None` too.
+pub(crate) fn decode_cog(raw: u64) -> Option<f64> {
+    if raw < 3600 {
+        Some(raw as f64 * 0.1)
+    } else {
+        None
+    }
+}
+
+/// Decode heading or course in whole degrees (0-359, 511 = not available). Values 360-510 aren't
+/// allowed by the standard and are wrapped into range `[0, 360)`, so e.g. 360 becomes 0.
+pub(crate) fn decode_whole_degrees(raw: u64) -> Option<f64> {
+    if raw != 511 {
+        Some((raw % 360) as f64)
+    } else {
+        None
+    }
+}
+
+/// Decode ship type with `ShipType::new()` and record a warning of an unexpected value. In
+/// strict mode the unexpected value is an error instead.
+pub(crate) fn decode_ship_type(raw: u8, store: &mut ParseContext) -> Result<ShipType, ParseError> {
+    if raw > 99 {
+        if store.settings.strict {
+            return Err(ParseError::InvalidSentence(format!(
+                "Ship and cargo type out of range 0..=99: {}",
+                raw
+            )));
+        }
+        store.add_warning(ParseWarning::UnexpectedShipType(raw));
+    }
+    Ok(ShipType::new(raw))
+}
+
+/// Decode position fix type with `PositionFixType::new()` and record a warning of an
+/// unrecognized (reserved 9-14) value. In strict mode the unrecognized value is an error instead.
+pub(crate) fn decode_position_fix_type(
+    raw: u8,
+    store: &mut ParseContext,
+) -> Result<PositionFixType, ParseError> {
+    if (9..=14).contains(&raw) {
+        if store.settings.strict {
+            return Err(ParseError::InvalidSentence(format!(
+                "Reserved position fix type: {}",
+                raw
+            )));
+        }
+        store.add_warning(ParseWarning::UnrecognizedPositionFixType(raw));
+    }
+    Ok(PositionFixType::new(raw))
+}
+
+/// Pick UTC date and time of AIS types 4 and 11 with `pick_utc_date_time()`. Invalid dates are
+/// returned as `None` with a warning.
+pub(crate) fn pick_utc_date_time_with_warning(
+    bv: &BitVec,
+    index: usize,
+    store: &mut ParseContext,
+) -> Option<Timestamp> {
+    pick_utc_date_time(bv, index).unwrap_or_else(|e| {
+        warn!("{}", e);
+        store.add_warning(ParseWarning::InvalidUtcDateTime(e.to_string()));
+        None
+    })
+}
+
+/// Parse an AIS message from an armored payload which has already been extracted from VDM/VDO
+/// sentences, e.g. by another system. Multi-part payloads are given concatenated. Fill bits
+/// are removed from the end of the type 17 data, as in `NmeaParser::parse_sentence()`. A new
+/// parser is used for each call, so AIS type 24 parts aren't combined; use
+/// `NmeaParser::parse_ais_payload()` for that.
+pub fn parse_payload_message(
+    payload: &str,
+    fill_bits: u8,
+    station: Station,
+    own_vessel: bool,
+) -> Result<ParsedMessage, ParseError> {
+    NmeaParser::new().parse_ais_payload(payload, fill_bits, station, own_vessel)
+}
+
+/// Pick DAC and FID of binary message types 6, 8, 25 and 26. `None` is returned for other message
+/// types, for unstructured type 25 and 26 messages and for too short messages.
+pub(crate) fn pick_dac_fid(bv: &BitVec) -> Option<(u16, u8)> {
+    let index = match pick_u64(bv, 0, 6) {
+        6 => 72,
+        8 => 40,
+        25 | 26 => {
+            if pick_u64(bv, 39, 1) == 0 {
+                return None;
+            }
+            if pick_u64(bv, 38, 1) != 0 {
+                70
+            } else {
+                40
+            }
+        }
+        _ => {
+            return None;
+        }
+    };
+    if bv.len() < index + 16 {
+        return None;
+    }
+    Some((
+        pick_u64(bv, index, 10) as u16,
+        pick_u64(bv, index + 10, 6) as u8,
+    ))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ais_6bit_string() {
+        // "BUOY 1" padded with '@'
+        let s = Ais6BitString::from_codes(&[2, 21, 15, 25, 32, 49, 0, 0, 0]);
+        assert_eq!(s.to_string(), "BUOY 1");
+        assert_eq!(s.raw_text(), "BUOY 1@@@");
+        assert_eq!(s.len(), 6);
+        assert!(s.is_clean());
+        assert!(!s.had_embedded_at());
+        assert_eq!(s, Ais6BitString::from("BUOY 1"));
+        assert_eq!(s, "BUOY 1");
+        assert!(s != "BUOY");
+        assert!(s != "BUOY 12");
+
+        // Padded with spaces
+        let s = Ais6BitString::from("BUOY 1   ");
+        assert_eq!(s.to_string(), "BUOY 1");
+        assert!(s.is_clean());
+        assert!(!s.had_embedded_at());
+
+        // Padded with spaces and '@'
+        let s = Ais6BitString::from("BUOY 1  @@");
+        assert_eq!(s.to_string(), "BUOY 1");
+        assert!(!s.is_clean());
+        assert!(!s.had_embedded_at());
+
+        // Embedded '@'
+        let s = Ais6BitString::from("BUOY@1@@");
+        assert_eq!(s.to_string(), "BUOY");
+        assert!(s.had_embedded_at());
+        assert!(!s.is_clean());
+
+        // Characters outside the 6-bit alphabet
+        assert_eq!(Ais6BitString::from("buoy#1").to_string(), "BUOY#1");
+        assert_eq!(Ais6BitString::from("Å{").raw_text(), "??");
+
+        assert!(Ais6BitString::from("@@@").is_empty());
+        assert!(Ais6BitString::default().is_empty());
+    }
+
+    #[test]
+    fn test_normalized_name() {
+        let mut vsd = VesselStaticData::default();
+        assert_eq!(vsd.normalized_name(), None);
+
+        vsd.name = Some(Ais6BitString::from(" EVER  DIADEM   @@@@@"));
+        assert_eq!(vsd.name.as_ref().unwrap().to_string(), " EVER  DIADEM");
+        assert_eq!(vsd.normalized_name(), Some("EVER DIADEM".to_string()));
+
+        vsd.name = Some(Ais6BitString::from("    @@@@"));
+        assert_eq!(vsd.normalized_name(), None);
+    }
+
+    #[test]
+    fn test_is_sart_mob_epirb() {
+        assert_eq!(
+            is_sart_mob_epirb(970012345),
+            Some(EmergencyDeviceType::Sart)
+        );
+        assert_eq!(is_sart_mob_epirb(972012345), Some(EmergencyDeviceType::Mob));
+        assert_eq!(
+            is_sart_mob_epirb(974012345),
+            Some(EmergencyDeviceType::Epirb)
+        );
+        assert_eq!(is_sart_mob_epirb(971012345), None);
+        assert_eq!(is_sart_mob_epirb(230992580), None);
+        assert_eq!(is_sart_mob_epirb(97201234), None);
+        assert_eq!(EmergencyDeviceType::Mob.to_string(), "MOB");
+    }
+
+    #[test]
+    fn test_vessel_static_data_builder() {
+        let vsd = VesselStaticData::builder()
+            .mmsi(230992580)
+            .name("ALANDIA")
+            .ship_type(ShipType::Cargo)
+            .dimensions(100, 20, 5, 10)
+            .build();
+        assert_eq!(vsd.mmsi, 230992580);
+        assert_eq!(vsd.country(), Some("FI"));
+        assert_eq!(vsd.normalized_name(), Some("ALANDIA".into()));
+        assert_eq!(vsd.ship_type, ShipType::Cargo);
+        assert_eq!(vsd.dimension_to_stern, Some(20));
+        assert_eq!(vsd.call_sign, None);
+        assert_eq!(vsd.ais_type, AisClass::Unknown);
+    }
+
+    #[test]
+    fn test_parse_payload_message_matches_parse_sentence() {
+        let station = Station::from_str("AIVDM").unwrap();
+        let cases: [(&[&str], &str, u8, bool); 3] = [
+            (
+                &["!AIVDM,1,1,,,15RTgt0PAso;90TKcjM8h6g208CQ,0*0B"],
+                "15RTgt0PAso;90TKcjM8h6g208CQ",
+                0,
+                false,
+            ),
+            (
+                &[
+                    "!AIVDM,2,1,1,,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*5D",
+                    "!AIVDM,2,2,1,,88888888880,2*64",
+                ],
+                "55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp888888888880",
+                2,
+                false,
+            ),
+            (
+                &["!AIVDO,1,1,,,15RTgt0PAso;90TKcjM8h6g208CQ,0*09"],
+                "15RTgt0PAso;90TKcjM8h6g208CQ",
+                0,
+                true,
+            ),
+        ];
+        for (sentences, payload, fill_bits, own_vessel) in cases.iter() {
+            let mut p = NmeaParser::new();
+            let mut expected = Ok(ParsedMessage::Incomplete);
+            for sentence in sentences.iter() {
+                expected = p.parse_sentence(sentence);
+            }
+            // The sequential message ID is in the sentence, not in the payload
+            let expected = expected.map(|mut msg| {
+                msg.set_message_id(None);
+                msg
+            });
+            assert!(expected.is_ok());
+            assert_eq!(
+                parse_payload_message(payload, *fill_bits, station, *own_vessel),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_ais_payload_type24() {
+        let station = Station::from_str("AIVDM").unwrap();
+        let mut p = NmeaParser::new();
+        assert_eq!(
+            p.parse_ais_payload("H42O55i18tMET00000000000000", 2, station, false),
+            Ok(ParsedMessage::Incomplete)
+        );
+        match p.parse_ais_payload("H42O55lti4hhhilD3nink000?050", 0, station, false) {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.mmsi, 271041815);
+                assert_eq!(vsd.name, Some("PROGUY".into()));
+                assert_eq!(vsd.call_sign, Some("TC6163".into()));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Invalid input
+        assert!(p
+            .parse_ais_payload("15RTgt0PAso;90TKcjM8h6g208CQ", 6, station, false)
+            .is_err());
+        assert!(p
+            .parse_ais_payload("15RTgt0PAso;90TK cjM8h6g208CQ", 0, station, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_vessel_dynamic_data_timestamp() {
+        let mut p = NmeaParser::new();
+        let position = "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A";
+
+        // No reference time yet
+        match p.parse_sentence(position) {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.timestamp_seconds, 33);
+                assert_eq!(vdd.timestamp, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Base station report at 2007-05-14 19:57:39
+        match p.parse_sentence("!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D") {
+            Ok(ParsedMessage::BaseStationReport(_)) => {}
+            _ => {
+                assert!(false);
+            }
+        }
+        match p.parse_sentence(position) {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.timestamp_seconds, 33);
+                assert_eq!(
+                    vdd.timestamp,
+                    Utc.with_ymd_and_hms(2007, 5, 14, 19, 57, 33).single()
+                );
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Current time late in the minute, second 13 belongs to the next minute
+        p.set_current_time(
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 58)
+                .single()
+                .unwrap(),
+        );
+        match p.parse_sentence("!AIVDM,1,1,,A,16SteH0P00Jt63hHaa6SagvJ087r,0*42") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.timestamp_seconds, 13);
+                assert_eq!(
+                    vdd.timestamp,
+                    Utc.with_ymd_and_hms(2024, 3, 1, 12, 1, 13).single()
+                );
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vessel_dynamic_data_timestamp_stale_reference() {
+        let mut p = NmeaParser::new();
+        p.set_ais_reference_window(2);
+        let position = "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A";
+        let timestamp = |p: &mut NmeaParser| match p.parse_sentence(position) {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => vdd.timestamp,
+            _ => {
+                assert!(false);
+                None
+            }
+        };
+
+        // Base station report at 2007-05-14 19:57:39 is used for two parse calls
+        assert!(p
+            .parse_sentence("!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D")
+            .is_ok());
+        let expected = Utc.with_ymd_and_hms(2007, 5, 14, 19, 57, 33).single();
+        assert_eq!(timestamp(&mut p), expected);
+        assert_eq!(timestamp(&mut p), expected);
+
+        // Stale after that, also when the window is widened again
+        assert_eq!(timestamp(&mut p), None);
+        p.set_ais_reference_window(DEFAULT_AIS_REFERENCE_WINDOW);
+        assert_eq!(timestamp(&mut p), None);
+
+        // A new report renews the reference
+        assert!(p
+            .parse_sentence("!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D")
+            .is_ok());
+        assert_eq!(timestamp(&mut p), expected);
+    }
 }
@@ -0,0 +1,227 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Latest known data of a vessel collected by `VesselTracker`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VesselRecord {
+    /// User ID (30 bits)
+    pub mmsi: u32,
+
+    /// Latest dynamic data (AIS types 1-3, 18 and 27)
+    pub dynamic: Option<VesselDynamicData>,
+
+    /// Static data merged from all the static messages received (AIS types 5 and 24)
+    pub static_data: Option<VesselStaticData>,
+
+    /// Time the vessel was last heard of, in the seconds given to `VesselTracker::update()`
+    pub last_seen: u64,
+}
+
+/// Maintains a table of vessels from parsed AIS messages, e.g. for a live vessel list. The
+/// tracker is fed with the results of `NmeaParser::parse_sentence()` with `update()`. The latest
+/// dynamic data of each vessel replaces the earlier one while static data is merged, so that
+/// e.g. the name from type 5 or 24 is kept when dynamic data arrives.
+///
+/// Time is given by the caller in seconds of any monotonic clock, e.g. UNIX time, which makes
+/// the tracker usable for replaying recorded data too.
+#[derive(Clone, Debug, Default)]
+pub struct VesselTracker {
+    vessels: HashMap<u32, VesselRecord>,
+}
+
+impl VesselTracker {
+    /// Construct an empty tracker.
+    pub fn new() -> VesselTracker {
+        VesselTracker {
+            vessels: HashMap::new(),
+        }
+    }
+
+    /// Update the vessel table with the given message received at `now` seconds. Messages other
+    /// than vessel dynamic and static data are ignored. The updated record is returned.
+    pub fn update(&mut self, msg: &ParsedMessage, now: u64) -> Option<&VesselRecord> {
+        let mmsi = match msg {
+            ParsedMessage::VesselDynamicData(vdd) => vdd.mmsi,
+            ParsedMessage::VesselStaticData(vsd) => vsd.mmsi,
+            _ => return None,
+        };
+        let record = self.vessels.entry(mmsi).or_insert_with(|| VesselRecord {
+            mmsi,
+            dynamic: None,
+            static_data: None,
+            last_seen: now,
+        });
+        record.last_seen = max(record.last_seen, now);
+        match msg {
+            ParsedMessage::VesselDynamicData(vdd) => {
+                record.dynamic = Some(vdd.clone());
+            }
+            ParsedMessage::VesselStaticData(vsd) => {
+                // Replace the data if it contradicts the earlier one, e.g. after IMO change
+                record.static_data = Some(match &record.static_data {
+                    Some(old) => vsd.merge(old).unwrap_or_else(|_| vsd.clone()),
+                    None => vsd.clone(),
+                });
+            }
+            _ => {}
+        }
+        Some(record)
+    }
+
+    /// Return the record of the given vessel.
+    pub fn get(&self, mmsi: u32) -> Option<&VesselRecord> {
+        self.vessels.get(&mmsi)
+    }
+
+    /// Iterate over the vessel records in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &VesselRecord> {
+        self.vessels.values()
+    }
+
+    /// Number of vessels in the table.
+    pub fn len(&self) -> usize {
+        self.vessels.len()
+    }
+
+    /// True if the table is empty.
+    pub fn is_empty(&self) -> bool {
+        self.vessels.is_empty()
+    }
+
+    /// Remove the vessels not heard of within `max_age` seconds before `now` and return their
+    /// count.
+    pub fn evict(&mut self, now: u64, max_age: u64) -> usize {
+        let count = self.vessels.len();
+        self.vessels
+            .retain(|_, record| now.saturating_sub(record.last_seen) <= max_age);
+        count - self.vessels.len()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vessel_tracker() {
+        let mut p = NmeaParser::new();
+        let mut t = VesselTracker::new();
+        let mut feed = |t: &mut VesselTracker, sentence: &str, now: u64| {
+            let msg = p.parse_sentence(sentence).unwrap();
+            t.update(&msg, now).map(|record| record.mmsi)
+        };
+
+        // Dynamic data of a class A vessel followed by its type 5 static data
+        assert_eq!(
+            feed(
+                &mut t,
+                "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A",
+                100
+            ),
+            Some(371798000)
+        );
+        assert_eq!(
+            feed(
+                &mut t,
+                "!AIVDM,2,1,1,,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*5D",
+                110
+            ),
+            None
+        );
+        assert_eq!(
+            feed(&mut t, "!AIVDM,2,2,1,,88888888880,2*64", 110),
+            Some(351759000)
+        );
+
+        // Class B vessel: static data before dynamic data
+        assert_eq!(
+            feed(
+                &mut t,
+                "!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D",
+                120
+            ),
+            None
+        );
+        assert_eq!(
+            feed(
+                &mut t,
+                "!AIVDM,1,1,,A,H42O55lti4hhhilD3nink000?050,0*40",
+                120
+            ),
+            Some(271041815)
+        );
+        assert_eq!(
+            feed(
+                &mut t,
+                "!AIVDM,1,1,,A,B52K>;h00Fc>jpUlNV@ikwpUoP06,0*4C",
+                130
+            ),
+            Some(338087471)
+        );
+        assert_eq!(t.len(), 4);
+
+        let class_a = t.get(371798000).unwrap();
+        assert!(class_a.dynamic.is_some());
+        assert!(class_a.static_data.is_none());
+        assert_eq!(class_a.last_seen, 100);
+
+        let class_b = t.get(271041815).unwrap();
+        assert!(class_b.dynamic.is_none());
+        assert_eq!(
+            class_b
+                .static_data
+                .as_ref()
+                .and_then(|vsd| vsd.name.clone()),
+            Some("PROGUY".into())
+        );
+
+        // Static data is kept when dynamic data is updated
+        let vsd = t.get(351759000).unwrap().static_data.clone().unwrap();
+        let vdd = VesselDynamicData {
+            mmsi: 351759000,
+            sog_knots: Some(12.3),
+            ..Default::default()
+        };
+        t.update(&ParsedMessage::VesselDynamicData(vdd.clone()), 140);
+        let record = t.get(351759000).unwrap();
+        assert_eq!(record.dynamic, Some(vdd));
+        assert_eq!(record.static_data, Some(vsd));
+        assert_eq!(record.last_seen, 140);
+
+        // Other messages are ignored
+        assert!(t
+            .update(&p.parse_sentence("$HEHDT,274.07,T").unwrap(), 150)
+            .is_none());
+
+        let mut mmsis: Vec<u32> = t.iter().map(|record| record.mmsi).collect();
+        mmsis.sort();
+        assert_eq!(mmsis, vec![271041815, 338087471, 351759000, 371798000]);
+
+        // Evict the vessels not heard of within 25 seconds
+        assert_eq!(t.evict(150, 25), 2);
+        assert!(t.get(371798000).is_none());
+        assert!(t.get(271041815).is_none());
+        assert!(t.get(338087471).is_some());
+        assert!(t.get(351759000).is_some());
+        assert_eq!(t.evict(150, 25), 0);
+    }
+}
@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 10: UTC/Date Inquiry
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct UtcDateInquiry {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct UtcDateInquiry {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Source MMSI (30 bits)
     pub source_mmsi: u32,
 
@@ -40,11 +51,15 @@ pub struct UtcDateInquiry {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::UtcDateInquiry(UtcDateInquiry {
         own_vessel: { own_vessel },
         station: { station },
+        repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+        radio_channel: { radio_channel },
+        message_id: None,
         source_mmsi: { pick_u64(bv, 8, 30) as u32 },
         destination_mmsi: { pick_u64(bv, 40, 30) as u32 },
     }))
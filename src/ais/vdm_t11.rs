@@ -15,54 +15,24 @@ limitations under the License.
 */
 
 use super::*;
+use vdm_t4::pick_base_station_report;
 
 /// AIS VDM/VDO type 11: UTC/Date Response
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
-    Ok(ParsedMessage::UtcDateResponse(BaseStationReport {
-        own_vessel: { own_vessel },
-        station: { station },
-        mmsi: { pick_u64(bv, 8, 30) as u32 },
-        timestamp: {
-            Some(parse_ymdhs(
-                pick_u64(bv, 38, 14) as i32,
-                pick_u64(bv, 52, 4) as u32,
-                pick_u64(bv, 56, 5) as u32,
-                pick_u64(bv, 61, 5) as u32,
-                pick_u64(bv, 66, 6) as u32,
-                pick_u64(bv, 72, 6) as u32,
-            )?)
-        },
-        high_position_accuracy: { pick_u64(bv, 78, 1) != 0 },
-        latitude: {
-            let lat_raw = pick_i64(bv, 107, 27) as i32;
-            if lat_raw != 0x3412140 {
-                Some((lat_raw as f64) / 600000.0)
-            } else {
-                None
-            }
-        },
-        longitude: {
-            let lon_raw = pick_i64(bv, 79, 28) as i32;
-            if lon_raw != 0x6791AC0 {
-                Some((lon_raw as f64) / 600000.0)
-            } else {
-                None
-            }
-        },
-        position_fix_type: {
-            let raw = pick_u64(bv, 134, 4) as u8;
-            match raw {
-                0 => None,
-                _ => Some(PositionFixType::new(raw)),
-            }
-        },
-        raim_flag: { pick_u64(bv, 148, 1) != 0 },
-        radio_status: { pick_u64(bv, 149, 19) as u32 },
-    }))
+    Ok(ParsedMessage::UtcDateResponse(pick_base_station_report(
+        bv,
+        station,
+        radio_channel,
+        store,
+        own_vessel,
+        11,
+    )?))
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -101,4 +71,54 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_utc_date_response_to_inquiry() {
+        let mut p = NmeaParser::new();
+        let inquiry = match p.parse_sentence("!AIVDM,1,1,,B,:5MlU418PhjP,0*61") {
+            Ok(ParsedMessage::UtcDateInquiry(udi)) => udi,
+            _ => {
+                panic!("UtcDateInquiry expected");
+            }
+        };
+        let other_inquiry = match p.parse_sentence("!AIVDM,1,1,,B,:5MlU41GMK6@,0*6C") {
+            Ok(ParsedMessage::UtcDateInquiry(udi)) => udi,
+            _ => {
+                panic!("UtcDateInquiry expected");
+            }
+        };
+        match p.parse_sentence("!AIVDM,1,1,,B,;4R33:1uUK2F`q?mOt@@GoQ00000,0*5D") {
+            Ok(ParsedMessage::UtcDateResponse(bsr)) => {
+                assert_eq!(bsr.message_type, 11);
+                assert!(bsr.is_response_to(&inquiry));
+                assert!(!bsr.is_response_to(&other_inquiry));
+
+                // A base station report isn't a response even if the MMSI matches
+                let mut bsr = bsr;
+                bsr.message_type = 4;
+                assert!(!bsr.is_response_to(&inquiry));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type11_long_range() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,B,;4R33:1uUK2F`q?mOt@@GoQP0000,0*3D") {
+            Ok(ParsedMessage::UtcDateResponse(bsr)) => {
+                assert_eq!(bsr.mmsi, 304137000);
+                assert_eq!(bsr.message_type, 11);
+                assert!(bsr.is_inquiry_response());
+                assert_eq!(bsr.position_fix_type, Some(PositionFixType::GPS));
+                assert!(bsr.long_range_broadcast);
+                assert!(!bsr.raim_flag);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 12: Addressed Safety-Related Message
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct AddressedSafetyRelatedMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct AddressedSafetyRelatedMessage {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Source MMSI (30 bits)
     pub source_mmsi: u32,
 
@@ -49,12 +60,16 @@ pub struct AddressedSafetyRelatedMessage {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::AddressedSafetyRelatedMessage(
         AddressedSafetyRelatedMessage {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             source_mmsi: { pick_u64(bv, 8, 30) as u32 },
             sequence_number: { pick_u64(bv, 38, 2) as u8 },
             destination_mmsi: { pick_u64(bv, 40, 30) as u32 },
@@ -109,6 +124,7 @@ mod test {
                     // The expected result
                     ParsedMessage::AddressedSafetyRelatedMessage(asrm) => {
                         assert_eq!(asrm.source_mmsi, 237032000);
+                        assert_eq!(asrm.repeat_indicator, 1);
                         assert_eq!(asrm.sequence_number, 3);
                         assert_eq!(asrm.destination_mmsi, 2391100);
                         assert!(asrm.retransmit_flag);
@@ -18,8 +18,9 @@ use super::*;
 
 // -------------------------------------------------------------------------------------------------
 
-/// Type 13: Safety-Related Acknowledgment
-#[derive(Default, Clone, Debug, PartialEq)]
+/// Type 13: Safety-Related Acknowledgment, also used for type 7: Binary Acknowledge
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct SafetyRelatedAcknowledgement {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,9 +28,26 @@ pub struct SafetyRelatedAcknowledgement {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
+    /// AIS message type: 7 = binary acknowledge, 13 = safety-related acknowledgement
+    pub message_type: u8,
+
     /// Source MMSI (30 bits)
     pub mmsi: u32,
 
+    /// Number of acknowledged stations (1-4) based on the message length. The MMSI and sequence
+    /// fields beyond the count are zero.
+    pub acknowledgement_count: u8,
+
     /// MMSI number 1 (30 bits)
     pub mmsi1: u32,
 
@@ -55,31 +73,67 @@ pub struct SafetyRelatedAcknowledgement {
     pub mmsi4_seq: u8,
 }
 
+impl SafetyRelatedAcknowledgement {
+    /// Return the acknowledged (MMSI, sequence number) pairs present in the message.
+    pub fn acknowledgements(&self) -> Vec<(u32, u8)> {
+        [
+            (self.mmsi1, self.mmsi1_seq),
+            (self.mmsi2, self.mmsi2_seq),
+            (self.mmsi3, self.mmsi3_seq),
+            (self.mmsi4, self.mmsi4_seq),
+        ]
+        .iter()
+        .take(self.acknowledgement_count as usize)
+        .copied()
+        .collect()
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// AIS VDM/VDO type 13: Safety-Related Acknowledgment
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::SafetyRelatedAcknowledgement(
-        SafetyRelatedAcknowledgement {
-            own_vessel: { own_vessel },
-            station: { station },
-            mmsi: { pick_u64(bv, 8, 30) as u32 },
-            mmsi1: { pick_u64(bv, 40, 30) as u32 },
-            mmsi1_seq: { pick_u64(bv, 70, 2) as u8 },
-            mmsi2: { pick_u64(bv, 72, 30) as u32 },
-            mmsi2_seq: { pick_u64(bv, 102, 2) as u8 },
-            mmsi3: { pick_u64(bv, 104, 30) as u32 },
-            mmsi3_seq: { pick_u64(bv, 134, 2) as u8 },
-            mmsi4: { pick_u64(bv, 136, 30) as u32 },
-            mmsi4_seq: { pick_u64(bv, 166, 2) as u8 },
-        },
+        pick_acknowledgement(bv, station, radio_channel, own_vessel, 13),
     ))
 }
 
+/// Decode the acknowledgement of type 7 or 13, which share the same layout.
+pub(crate) fn pick_acknowledgement(
+    bv: &BitVec,
+    station: Station,
+    radio_channel: Option<char>,
+    own_vessel: bool,
+    message_type: u8,
+) -> SafetyRelatedAcknowledgement {
+    SafetyRelatedAcknowledgement {
+        own_vessel: { own_vessel },
+        station: { station },
+        repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+        radio_channel: { radio_channel },
+        message_id: None,
+        message_type,
+        mmsi: { pick_u64(bv, 8, 30) as u32 },
+        acknowledgement_count: {
+            // 40 header bits followed by 32-bit MMSI and sequence number pairs
+            (bv.len().saturating_sub(40) / 32).clamp(1, 4) as u8
+        },
+        mmsi1: { pick_u64(bv, 40, 30) as u32 },
+        mmsi1_seq: { pick_u64(bv, 70, 2) as u8 },
+        mmsi2: { pick_u64(bv, 72, 30) as u32 },
+        mmsi2_seq: { pick_u64(bv, 102, 2) as u8 },
+        mmsi3: { pick_u64(bv, 104, 30) as u32 },
+        mmsi3_seq: { pick_u64(bv, 134, 2) as u8 },
+        mmsi4: { pick_u64(bv, 136, 30) as u32 },
+        mmsi4_seq: { pick_u64(bv, 166, 2) as u8 },
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -95,6 +149,7 @@ mod test {
                     // The expected result
                     ParsedMessage::SafetyRelatedAcknowledgement(sra) => {
                         assert_eq!(sra.mmsi, 211378120);
+                        assert_eq!(sra.message_type, 13);
                         assert_eq!(sra.mmsi1, 211217560);
                         assert_eq!(sra.mmsi1_seq, 2);
                         assert_eq!(sra.mmsi2, 0);
@@ -103,6 +158,8 @@ mod test {
                         assert_eq!(sra.mmsi3_seq, 0);
                         assert_eq!(sra.mmsi4, 0);
                         assert_eq!(sra.mmsi4_seq, 0);
+                        assert_eq!(sra.acknowledgement_count, 1);
+                        assert_eq!(sra.acknowledgements(), vec![(211217560, 2)]);
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
@@ -117,4 +174,20 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type13_two_stations() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,=39UOj0jFs9R=i:c4@,2*1E") {
+            Ok(ParsedMessage::SafetyRelatedAcknowledgement(sra)) => {
+                assert_eq!(sra.mmsi, 211378120);
+                assert_eq!(sra.acknowledgement_count, 2);
+                assert_eq!(sra.acknowledgements(), vec![(211217560, 2), (230992580, 1)]);
+                assert_eq!(sra.mmsi3, 0);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
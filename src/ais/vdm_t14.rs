@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 14: Safety-Related Broadcast Message
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct SafetyRelatedBroadcastMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct SafetyRelatedBroadcastMessage {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Source MMSI (30 bits)
     pub mmsi: u32,
 
@@ -40,14 +51,22 @@ pub struct SafetyRelatedBroadcastMessage {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::SafetyRelatedBroadcastMessage(
         SafetyRelatedBroadcastMessage {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
-            text: { pick_string(bv, 40, 161) },
+            text: {
+                // Text length is derived from the message length, which leaves out fill bits
+                let char_count = core::cmp::min(bv.len().saturating_sub(40) / 6, 161);
+                pick_string(bv, 40, char_count)
+            },
         },
     ))
 }
@@ -128,4 +147,44 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type14_two_fragments() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "!AIVDM,2,1,3,B,>5?Per1<D=E8U@F1<D=E8U@F0p5HTL5@Ttp4j1L58pTpN09DuV37:0tHJ1=@,0*08",
+        ) {
+            Ok(ParsedMessage::Incomplete) => {}
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+        match p.parse_sentence("!AIVDM,2,2,3,B,5@Ttr0pD5:10u9B0DqA84p<G,2*74") {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::SafetyRelatedBroadcastMessage(srbm) => {
+                        assert_eq!(srbm.mmsi, 351809000);
+                        assert_eq!(
+                            srbm.text,
+                            "SECURITE SECURITE NAVIGATIONAL WARNING BUOY 12 OFF STATION NEAR \
+                             PORT ENTRANCE"
+                        );
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
 }
@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 15: Interrogation
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Interrogation {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct Interrogation {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Interrogation case based on data length
     pub case: InterrogationCase,
 
@@ -59,7 +70,8 @@ pub struct Interrogation {
 }
 
 /// The four cases of interrogation, depending on data length mostly.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum InterrogationCase {
     /// One station is interrogated for one message type.
     Case1,
@@ -74,6 +86,12 @@ pub enum InterrogationCase {
     Case4,
 }
 
+impl Default for InterrogationCase {
+    fn default() -> InterrogationCase {
+        InterrogationCase::Case1
+    }
+}
+
 impl InterrogationCase {
     pub fn new(bv: &BitVec) -> InterrogationCase {
         let len = bv.len();
@@ -101,12 +119,16 @@ impl InterrogationCase {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     let case = InterrogationCase::new(bv);
     Ok(ParsedMessage::Interrogation(Interrogation {
         own_vessel,
         station,
+        repeat_indicator: pick_u64(bv, 6, 2) as u8,
+        radio_channel,
+        message_id: None,
         case,
         mmsi: { pick_u64(bv, 8, 30) as u32 },
         mmsi1: { pick_u64(bv, 40, 30) as u32 },
@@ -154,6 +176,7 @@ mod test {
                     // The expected result
                     ParsedMessage::Interrogation(i) => {
                         assert_eq!(i.mmsi, 3669720);
+                        assert_eq!(i.repeat_indicator, 3);
                         assert_eq!(i.mmsi1, 367014320);
                         assert_eq!(i.type1_1, 3);
                         assert_eq!(i.offset1_1, 516);
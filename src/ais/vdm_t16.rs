@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 16: Assignment Mode Command
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct AssignmentModeCommand {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct AssignmentModeCommand {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     // When the message is 96 bits long it is interpreted as an assignment for a single station,
     // When the message is 144 bits long it is interpreted as a channel assignled for two stations.
     pub assigned_for_single_station: bool,
@@ -59,6 +70,7 @@ pub struct AssignmentModeCommand {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     let single = bv.len() < 144;
@@ -66,6 +78,9 @@ pub(crate) fn handle(
         AssignmentModeCommand {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             assigned_for_single_station: { single },
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             mmsi1: { pick_u64(bv, 40, 30) as u32 },
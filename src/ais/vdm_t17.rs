@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 17: DGNSS Broadcast Binary Message.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DgnssBroadcastBinaryMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct DgnssBroadcastBinaryMessage {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Source MMSI (30 bits)
     pub mmsi: u32,
 
@@ -36,22 +47,46 @@ pub struct DgnssBroadcastBinaryMessage {
     /// Longitude (18 bits)
     pub longitude: Option<f64>,
 
-    /// Payload (80-815 bits). Note that it appears to be tied to the now obsolete RTCM2 protocol.
+    /// Payload (0-736 bits) starting from bit 80 with the fill bits removed. Note that it appears
+    /// to be tied to the now obsolete RTCM2 protocol.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
     pub payload: BitVec,
 }
 
+impl LatLon for DgnssBroadcastBinaryMessage {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// AIS VDM/VDO type 17: DGNSS Broadcast Binary Message
 pub(crate) fn handle(
     bv: &BitVec,
+    fill_bits: u8,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
+    if bv.len() < 80 {
+        return Err(ParseError::InvalidSentence(format!(
+            "AIS type 17 message too short: {} bits",
+            bv.len()
+        )));
+    }
+    let data_end = max(80, bv.len() - fill_bits as usize);
     Ok(ParsedMessage::DgnssBroadcastBinaryMessage(
         DgnssBroadcastBinaryMessage {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             latitude: {
                 let lat_raw = pick_i64(bv, 58, 17) as i32;
@@ -69,7 +104,7 @@ pub(crate) fn handle(
                     None
                 }
             },
-            payload: bv.iter().skip(80).collect(),
+            payload: bv[80..data_end].to_bitvec(),
         },
     ))
 }
@@ -121,4 +156,23 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type17_fill_bits() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,A02VqLPA4I6C07h5Ed1h,2*37") {
+            Ok(ParsedMessage::DgnssBroadcastBinaryMessage(i)) => {
+                assert_eq!(i.mmsi, 2734450);
+                assert::close(i.latitude().unwrap_or(0.0), 59.987, 0.001);
+                assert::close(i.longitude().unwrap_or(0.0), 29.130, 0.001);
+                assert_eq!(i.payload.len(), 38);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Shorter than the 80-bit header
+        assert!(p.parse_sentence("!AIVDM,1,1,,A,A02VqLPA4I,0*62").is_err());
+    }
 }
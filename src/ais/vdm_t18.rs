@@ -19,11 +19,15 @@ use super::*;
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::VesselDynamicData(VesselDynamicData {
         own_vessel: { own_vessel },
         station: { station },
+        repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+        radio_channel: { radio_channel },
+        message_id: None,
         ais_type: { AisClass::ClassB },
         mmsi: { pick_u64(bv, 8, 30) as u32 },
         sog_knots: {
@@ -51,30 +55,20 @@ pub(crate) fn handle(
                 None
             }
         },
-        cog: {
-            let cog_raw = pick_u64(bv, 112, 12);
-            if cog_raw != 0xE10 {
-                Some(cog_raw as f64 * 0.1)
-            } else {
-                None
-            }
-        },
-        heading_true: {
-            let th_raw = pick_u64(bv, 124, 9);
-            if th_raw != 511 {
-                Some(th_raw as f64)
-            } else {
-                None
-            }
-        },
+        low_resolution_position: false,
+        cog: decode_cog(pick_u64(bv, 112, 12)),
+        heading_true: decode_whole_degrees(pick_u64(bv, 124, 9)),
         timestamp_seconds: pick_u64(bv, 133, 6) as u8,
+        timestamp: None,
         class_b_unit_flag: { None },
         class_b_display: Some(pick_u64(bv, 141, 1) != 0),
         class_b_dsc: Some(pick_u64(bv, 142, 1) != 0),
         class_b_band_flag: Some(pick_u64(bv, 143, 1) != 0),
         class_b_msg22_flag: Some(pick_u64(bv, 144, 1) != 0),
-        class_b_mode_flag: Some(pick_u64(bv, 145, 1) != 0),
+        class_b_mode_flag: Some(pick_u64(bv, 146, 1) != 0),
         raim_flag: pick_u64(bv, 141, 1) != 0,
+        regional: Some(pick_u64(bv, 38, 8) as u8),
+        regional_b: Some(pick_u64(bv, 139, 2) as u8),
         class_b_css_flag: { None },
         radio_status: Some(pick_u64(bv, 149, 19) as u32),
         nav_status: NavigationStatus::NotDefined,
@@ -111,6 +105,10 @@ mod test {
                         assert_eq!(vdd.timestamp_seconds, 49);
                         assert_eq!(vdd.positioning_system_meta, None);
                         assert_eq!(vdd.special_manoeuvre, None);
+                        assert_eq!(vdd.class_b_mode_flag, Some(false));
+                        assert_eq!(vdd.regional, Some(0));
+                        assert_eq!(vdd.regional_b, Some(0));
+                        assert_eq!(vdd.blue_sign(), None);
                         assert!(vdd.raim_flag);
                     }
                     ParsedMessage::Incomplete => {
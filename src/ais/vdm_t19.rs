@@ -15,24 +15,250 @@ limitations under the License.
 */
 use super::*;
 
+// -------------------------------------------------------------------------------------------------
+
+/// Type 19: Extended Class B Equipment Position Report
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ExtendedClassBPositionReport {
+    /// True if the data is about own vessel, false if about other.
+    pub own_vessel: bool,
+
+    /// AIS station type.
+    pub station: Station,
+
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
+    /// User ID (30 bits)
+    pub mmsi: u32,
+
+    /// Regional, reserved (8 bits after MMSI)
+    pub regional: u8,
+
+    /// Speed over ground in knots
+    pub sog_knots: Option<f64>,
+
+    /// Position accuracy: true = high (<= 10 m), false = low (> 10 m)
+    pub high_position_accuracy: bool,
+
+    /// Latitude
+    pub latitude: Option<f64>,
+
+    /// Longitude
+    pub longitude: Option<f64>,
+
+    /// Course over ground
+    pub cog: Option<f64>,
+
+    /// True heading (0-359)
+    pub heading_true: Option<f64>,
+
+    /// Derived from UTC second (6 bits)
+    pub timestamp_seconds: u8,
+
+    /// Regional, reserved (4 bits after UTC second). Inland AIS uses the bits for the blue sign.
+    pub regional_b: u8,
+
+    /// Name (20 ASCII characters)
+    pub name: Option<Ais6BitString>,
+
+    /// Type of ship (first 4 of 8 bits)
+    pub ship_type: ShipType,
+
+    /// Type of ship and cargo (last 4 of 8 bits)
+    pub cargo_type: CargoType,
+
+    /// Overall dimension / reference for position A (9 bits)
+    pub dimension_to_bow: Option<u16>,
+
+    /// Overall dimension / reference for position B (9 bits)
+    pub dimension_to_stern: Option<u16>,
+
+    /// Overall dimension / reference for position C (6 bits)
+    pub dimension_to_port: Option<u16>,
+
+    /// Overall dimension / reference for position D (6 bits)
+    pub dimension_to_starboard: Option<u16>,
+
+    /// Position fix type (EPFD)
+    pub position_fix_type: Option<PositionFixType>,
+
+    /// RAIM (Receiver autonomous integrity monitoring) flag of electronic position
+    /// fixing device; false = RAIM not in use = default; true = RAIM in use
+    pub raim_flag: bool,
+
+    /// Data terminal ready:
+    /// true = ready (raw bit 0),
+    /// false = not ready = default (raw bit 1)
+    pub dte: bool,
+
+    /// Assigned mode flag:
+    /// false = Station operating in autonomous and continuous mode = default
+    /// true  = Station operating in assigned mode
+    pub assigned: bool,
+}
+
+impl LatLon for ExtendedClassBPositionReport {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
+impl PositionReport for ExtendedClassBPositionReport {
+    fn sog_knots(&self) -> Option<f64> {
+        self.sog_knots
+    }
+
+    fn cog(&self) -> Option<f64> {
+        self.cog
+    }
+
+    fn heading(&self) -> Option<f64> {
+        self.heading_true
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// AIS VDM/VDO type 19: Extended Class B Equipment Position Report
 pub(crate) fn handle(
-    _bv: &BitVec,
-    _station: Station,
-    _own_vessel: bool,
+    bv: &BitVec,
+    station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
+    own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
-    // TODO: implementation (Class B)
-    Err(ParseError::UnsupportedSentenceType(
-        "Unsupported AIVDM message type: 19".into(),
+    Ok(ParsedMessage::ExtendedClassBPositionReport(
+        ExtendedClassBPositionReport {
+            own_vessel: { own_vessel },
+            station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
+            mmsi: { pick_u64(bv, 8, 30) as u32 },
+            regional: { pick_u64(bv, 38, 8) as u8 },
+            sog_knots: {
+                let raw = pick_u64(bv, 46, 10);
+                if raw < 1023 {
+                    Some((raw as f64) * 0.1)
+                } else {
+                    None
+                }
+            },
+            high_position_accuracy: { pick_u64(bv, 56, 1) != 0 },
+            longitude: {
+                let lon_raw = pick_i64(bv, 57, 28) as i32;
+                if lon_raw != 0x6791AC0 {
+                    Some((lon_raw as f64) / 600000.0)
+                } else {
+                    None
+                }
+            },
+            latitude: {
+                let lat_raw = pick_i64(bv, 85, 27) as i32;
+                if lat_raw != 0x3412140 {
+                    Some((lat_raw as f64) / 600000.0)
+                } else {
+                    None
+                }
+            },
+            cog: decode_cog(pick_u64(bv, 112, 12)),
+            heading_true: decode_whole_degrees(pick_u64(bv, 124, 9)),
+            timestamp_seconds: pick_u64(bv, 133, 6) as u8,
+            regional_b: { pick_u64(bv, 139, 4) as u8 },
+            name: {
+                let raw = pick_6bit_string(bv, 143, 20);
+                if raw.is_empty() {
+                    None
+                } else {
+                    Some(raw)
+                }
+            },
+            ship_type: { decode_ship_type(pick_u64(bv, 263, 8) as u8, store)? },
+            cargo_type: { CargoType::new(pick_u64(bv, 263, 8) as u8) },
+            dimension_to_bow: { Some(pick_u64(bv, 271, 9) as u16) },
+            dimension_to_stern: { Some(pick_u64(bv, 280, 9) as u16) },
+            dimension_to_port: { Some(pick_u64(bv, 289, 6) as u16) },
+            dimension_to_starboard: { Some(pick_u64(bv, 295, 6) as u16) },
+            position_fix_type: {
+                let raw = pick_u64(bv, 301, 4) as u8;
+                match raw {
+                    0 => None,
+                    _ => Some(decode_position_fix_type(raw, store)?),
+                }
+            },
+            raim_flag: { pick_u64(bv, 305, 1) != 0 },
+            // Inverted polarity: 0 = available, 1 = not available
+            dte: { pick_u64(bv, 306, 1) == 0 },
+            assigned: { pick_u64(bv, 307, 1) != 0 },
+        },
     ))
 }
 
+// -------------------------------------------------------------------------------------------------
+
 #[cfg(test)]
 mod test {
-    //    use super::*;
+    use super::*;
+
+    #[test]
+    fn test_parse_vdm_type19() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "!AIVDM,1,1,,B,C5N3SRgPEnJGEBT>NhWAwwo862PaLELTBJ:V00000000S0D:R220,0*0B",
+        ) {
+            Ok(ParsedMessage::ExtendedClassBPositionReport(ecbpr)) => {
+                assert_eq!(ecbpr.mmsi, 367059850);
+                assert_eq!(ecbpr.radio_channel, Some('B'));
+                assert_eq!(ecbpr.regional, 248);
+                assert::close(ecbpr.sog_knots.unwrap_or(0.0), 8.7, 0.01);
+                assert!(!ecbpr.high_position_accuracy);
+                assert::close(ecbpr.latitude.unwrap_or(0.0), 29.543695, 0.000001);
+                assert::close(ecbpr.longitude.unwrap_or(0.0), -88.810392, 0.000001);
+                assert::close(ecbpr.cog.unwrap_or(0.0), 335.9, 0.01);
+                assert_eq!(ecbpr.heading_true, None);
+                assert_eq!(ecbpr.timestamp_seconds, 46);
+                assert_eq!(ecbpr.regional_b, 4);
+                assert_eq!(ecbpr.name, Some("CAPT.J.RIMES".into()));
+                assert_eq!(ecbpr.ship_type, ShipType::Cargo);
+                assert_eq!(ecbpr.dimension_to_bow, Some(5));
+                assert_eq!(ecbpr.dimension_to_stern, Some(21));
+                assert_eq!(ecbpr.dimension_to_port, Some(4));
+                assert_eq!(ecbpr.dimension_to_starboard, Some(4));
+                assert_eq!(ecbpr.position_fix_type, Some(PositionFixType::GPS));
+                assert!(!ecbpr.raim_flag);
+                assert!(ecbpr.dte);
+                assert!(!ecbpr.assigned);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
 
-    //    #[test]
-    //    fn test_parse_vdm_type19() {
-    //        assert!(false);
-    //    }
+        // DTE not ready, RAIM in use and assigned mode
+        match p.parse_sentence(
+            "!AIVDM,1,1,,B,C5N3SRgPEnJGEBT>NhWAwwo862PaLELTBJ:V00000000S0D:R23h,0*52",
+        ) {
+            Ok(ParsedMessage::ExtendedClassBPositionReport(ecbpr)) => {
+                assert!(ecbpr.raim_flag);
+                assert!(!ecbpr.dte);
+                assert!(ecbpr.assigned);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
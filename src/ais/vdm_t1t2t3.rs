@@ -20,11 +20,16 @@ use super::*;
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::VesselDynamicData(VesselDynamicData {
         own_vessel: { own_vessel },
         station: { station },
+        repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+        radio_channel: { radio_channel },
+        message_id: None,
         ais_type: { AisClass::ClassA },
         mmsi: { pick_u64(bv, 8, 30) as u32 },
         nav_status: { NavigationStatus::new(pick_u64(bv, 38, 4) as u8) },
@@ -77,23 +82,11 @@ pub(crate) fn handle(
                 None
             }
         },
-        cog: {
-            let cog_raw = pick_u64(bv, 116, 12);
-            if cog_raw != 0xE10 {
-                Some(cog_raw as f64 * 0.1)
-            } else {
-                None
-            }
-        },
-        heading_true: {
-            let th_raw = pick_u64(bv, 128, 9);
-            if th_raw != 511 {
-                Some(th_raw as f64)
-            } else {
-                None
-            }
-        },
+        low_resolution_position: false,
+        cog: decode_cog(pick_u64(bv, 116, 12)),
+        heading_true: decode_whole_degrees(pick_u64(bv, 128, 9)),
         timestamp_seconds: pick_u64(bv, 137, 6) as u8,
+        timestamp: None,
         positioning_system_meta: {
             // second of UTC timestamp has some hidden information
             let sec_raw = pick_u64(bv, 137, 6) as u16;
@@ -110,15 +103,18 @@ pub(crate) fn handle(
             let raw = pick_u64(bv, 143, 2);
             match raw {
                 0 => None,
-                1 => Some(true),
+                1 => Some(false),
                 2 => Some(true),
                 _ => {
                     warn!("Unrecognized Maneuver Indicator value: {}", raw);
+                    store.add_warning(ParseWarning::UnrecognizedManeuverIndicator(raw as u8));
                     None
                 }
             }
         },
         raim_flag: pick_u64(bv, 148, 1) != 0,
+        regional: Some(pick_u64(bv, 143, 4) as u8),
+        regional_b: None,
         class_b_unit_flag: None,
         class_b_display: None,
         class_b_dsc: None,
@@ -145,6 +141,8 @@ mod test {
                     // The expected result
                     ParsedMessage::VesselDynamicData(vdd) => {
                         assert_eq!(vdd.mmsi, 371798000);
+                        assert_eq!(vdd.repeat_indicator, 0);
+                        assert_eq!(vdd.radio_channel, Some('A'));
                         assert_eq!(vdd.nav_status, NavigationStatus::UnderWayUsingEngine);
                         assert_eq!(vdd.rot, None);
                         assert_eq!(vdd.rot_direction, Some(RotDirection::Port));
@@ -232,6 +230,7 @@ mod test {
                         assert!(vdd.high_position_accuracy);
                         assert::close(vdd.latitude.unwrap_or(0.0), 36.91, 0.01);
                         assert::close(vdd.longitude.unwrap_or(0.0), -76.33, 0.01);
+                        assert!(!vdd.low_resolution_position);
                         assert_eq!(vdd.cog, Some(252.0));
                         assert_eq!(vdd.heading_true, Some(352.0));
                         assert_eq!(vdd.timestamp_seconds, 35);
@@ -255,4 +254,137 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type1_special_manoeuvre() {
+        // Special manoeuvre indicator value 1: not engaged in special manoeuvre
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g2P8CQ,0*2A") {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::VesselDynamicData(vdd) => {
+                        assert_eq!(vdd.mmsi, 371798000);
+                        assert_eq!(vdd.special_manoeuvre, Some(false));
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type1_no_radio_channel() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,,15RTgt0PAso;90TKcjM8h6g208CQ,0*0B") {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::VesselDynamicData(vdd) => {
+                        assert_eq!(vdd.mmsi, 371798000);
+                        assert_eq!(vdd.radio_channel, None);
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type1_maneuver_warning() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g3P8CQ,0*2B") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.mmsi, 371798000);
+                assert_eq!(vdd.special_manoeuvre, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(
+            p.take_warnings(),
+            vec![ParseWarning::UnrecognizedManeuverIndicator(3)]
+        );
+        assert!(p.take_warnings().is_empty());
+
+        // Warnings are cleared on the next parse
+        p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g3P8CQ,0*2B")
+            .ok();
+        p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .ok();
+        assert!(p.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_parse_vdm_type1_blue_sign() {
+        let mut p = NmeaParser::new();
+
+        // Inland AIS blue sign set
+        match p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g308CQ,0*4B") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.regional, Some(0b1000));
+                assert_eq!(vdd.regional_b, None);
+                assert_eq!(vdd.blue_sign(), Some(true));
+                assert_eq!(vdd.special_manoeuvre, Some(true));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Blue sign not available
+        match p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.regional, Some(0));
+                assert_eq!(vdd.blue_sign(), None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type1_cog_heading_edge_values() {
+        let mut p = NmeaParser::new();
+
+        // COG 3600 is "not available", heading 360 wraps to 0
+        match p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM>4;A208CQ,0*3B") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.cog, None);
+                assert_eq!(vdd.heading_true, Some(0.0));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Largest COG, heading not available
+        match p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM>3ww208CQ,0*46") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert::close(vdd.cog.unwrap_or(0.0), 359.9, 0.001);
+                assert_eq!(vdd.heading_true, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
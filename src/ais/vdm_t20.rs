@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 20: Data Link Management Message
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DataLinkManagementMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct DataLinkManagementMessage {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// Interrogation case based on data length
     pub case: InterrogationCase,
 
@@ -88,6 +99,7 @@ pub struct DataLinkManagementMessage {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     let case = InterrogationCase::new(bv);
@@ -95,6 +107,9 @@ pub(crate) fn handle(
         DataLinkManagementMessage {
             own_vessel,
             station,
+            repeat_indicator: pick_u64(bv, 6, 2) as u8,
+            radio_channel,
+            message_id: None,
             case,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             offset1: { pick_u64(bv, 40, 12) as u16 },
@@ -132,6 +147,7 @@ mod test {
                     // The expected result
                     ParsedMessage::DataLinkManagementMessage(dlmm) => {
                         assert_eq!(dlmm.mmsi, 3669705);
+                        assert_eq!(dlmm.repeat_indicator, 3);
                         assert_eq!(dlmm.offset1, 2182);
                         assert_eq!(dlmm.number1, 5);
                         assert_eq!(dlmm.timeout1, 7);
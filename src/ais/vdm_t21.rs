@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 21: Aid-to-Navigation Report
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct AidToNavigationReport {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct AidToNavigationReport {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
@@ -34,10 +45,10 @@ pub struct AidToNavigationReport {
     pub aid_type: NavAidType,
 
     /// Name (120 bits)
-    pub name: String,
+    pub name: Ais6BitString,
 
     /// Position accuracy.
-    high_position_accuracy: bool,
+    pub high_position_accuracy: bool,
 
     /// Latitude
     pub latitude: Option<f64>,
@@ -92,7 +103,8 @@ impl LatLon for AidToNavigationReport {
 }
 
 /// Type of navigation aid
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum NavAidType {
     /// Default, type not specified
     NotSpecified, // 0
@@ -286,12 +298,17 @@ impl core::fmt::Display for NavAidType {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::AidToNavigationReport(
         AidToNavigationReport {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             aid_type: {
                 NavAidType::new(pick_u64(bv, 38, 5) as u8)
@@ -299,8 +316,13 @@ pub(crate) fn handle(
                     .unwrap_or(NavAidType::NotSpecified)
             },
             name: {
-                let mut s = pick_string(bv, 43, 20);
-                s.push_str(&pick_string(bv, 272, 14));
+                let mut s = pick_6bit_string(bv, 43, 20);
+                // Name extension exists only when the message is longer than 272 bits. Integer
+                // division leaves out possible fill bits.
+                if bv.len() > 272 {
+                    let ext_char_count = core::cmp::min((bv.len() - 272) / 6, 14);
+                    s.append(&pick_6bit_string(bv, 272, ext_char_count));
+                }
                 s
             },
             high_position_accuracy: { pick_u64(bv, 163, 1) != 0 },
@@ -324,7 +346,9 @@ pub(crate) fn handle(
             dimension_to_stern: { Some(pick_u64(bv, 228, 9) as u16) },
             dimension_to_port: { Some(pick_u64(bv, 237, 6) as u16) },
             dimension_to_starboard: { Some(pick_u64(bv, 243, 6) as u16) },
-            position_fix_type: { Some(PositionFixType::new(pick_u64(bv, 249, 4) as u8)) },
+            position_fix_type: {
+                Some(decode_position_fix_type(pick_u64(bv, 249, 4) as u8, store)?)
+            },
             timestamp_seconds: { pick_u64(bv, 253, 6) as u8 },
             off_position_indicator: { pick_u64(bv, 243, 1) != 0 },
             regional: { pick_u64(bv, 260, 8) as u8 },
@@ -394,4 +418,32 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type21_short_name() {
+        // 272-bit message with non-zero fill bits
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,B,E1mg=5J1:WdhHP0000000000000;W:Te=eLvH50```q:D?,4*7D")
+        {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::AidToNavigationReport(atnr) => {
+                        assert_eq!(atnr.mmsi, 123456789);
+                        assert_eq!(atnr.name, "BUOY 1");
+                        assert_eq!(atnr.timestamp_seconds, 50);
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
 }
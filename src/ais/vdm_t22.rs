@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 22: Channel Management
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ChannelManagement {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct ChannelManagement {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
@@ -85,18 +96,65 @@ pub struct ChannelManagement {
     pub zonesize: u8,
 }
 
+impl ChannelManagement {
+    /// Return the region (northeast latitude, northeast longitude, southwest latitude, southwest
+    /// longitude) of a broadcast command. `None` is returned for addressed commands.
+    pub fn region(&self) -> Option<(f64, f64, f64, f64)> {
+        if self.addressed {
+            return None;
+        }
+        Some((self.ne_lat?, self.ne_lon?, self.sw_lat?, self.sw_lon?))
+    }
+
+    /// Return the destination MMSIs of an addressed command. `None` is returned for broadcast
+    /// commands. The second destination is `None` if it's not given (zero).
+    pub fn destinations(&self) -> Option<(u32, Option<u32>)> {
+        if !self.addressed {
+            return None;
+        }
+        Some((self.dest1_mmsi?, self.dest2_mmsi.filter(|mmsi| *mmsi != 0)))
+    }
+
+    /// Return human readable label of channel A if it's a well-known AIS channel.
+    pub fn channel_a_name(&self) -> Option<&'static str> {
+        channel_name(self.channel_a)
+    }
+
+    /// Return human readable label of channel B if it's a well-known AIS channel.
+    pub fn channel_b_name(&self) -> Option<&'static str> {
+        channel_name(self.channel_b)
+    }
+}
+
+/// Map ITU-R M.1084 channel number to a label of a well-known AIS channel.
+fn channel_name(channel: u16) -> Option<&'static str> {
+    match channel {
+        2087 => Some("AIS 1 (87B)"),
+        2088 => Some("AIS 2 (88B)"),
+        2027 => Some("ASM 1 (27B)"),
+        2028 => Some("ASM 2 (28B)"),
+        75 => Some("Long range AIS (75)"),
+        76 => Some("Long range AIS (76)"),
+        _ => None,
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// AIS VDM/VDO type 22: Channel Management
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     let addressed = pick_u64(bv, 139, 1) != 0;
     Ok(ParsedMessage::ChannelManagement(ChannelManagement {
         own_vessel: { own_vessel },
         station: { station },
+        repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+        radio_channel: { radio_channel },
+        message_id: None,
         mmsi: { pick_u64(bv, 8, 30) as u32 },
         channel_a: { pick_u64(bv, 40, 12) as u16 },
         channel_b: { pick_u64(bv, 52, 12) as u16 },
@@ -168,6 +226,8 @@ mod test {
                         assert_eq!(cm.mmsi, 3160048);
                         assert_eq!(cm.channel_a, 2087);
                         assert_eq!(cm.channel_b, 2088);
+                        assert_eq!(cm.channel_a_name(), Some("AIS 1 (87B)"));
+                        assert_eq!(cm.channel_b_name(), Some("AIS 2 (88B)"));
                         assert_eq!(cm.txrx, 0);
                         assert!(!cm.power);
                         assert::close(cm.ne_lat.unwrap_or(0.0), 45.55, 0.01);
@@ -178,6 +238,41 @@ mod test {
                         assert!(!cm.channel_a_band);
                         assert!(!cm.channel_b_band);
                         assert_eq!(cm.zonesize, 4);
+                        let (ne_lat, ne_lon, sw_lat, sw_lon) = cm.region().unwrap();
+                        assert::close(ne_lat, 45.55, 0.01);
+                        assert::close(ne_lon, -73.50, 0.01);
+                        assert::close(sw_lat, 42.33, 0.01);
+                        assert::close(sw_lon, -80.17, 0.01);
+                        assert_eq!(cm.destinations(), None);
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type22_addressed() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,F030ot22N2P0roVR`>eq`d@B0000,0*01") {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::ChannelManagement(cm) => {
+                        assert_eq!(cm.mmsi, 3160048);
+                        assert!(cm.addressed);
+                        assert_eq!(cm.dest1_mmsi, Some(123456789));
+                        assert_eq!(cm.dest2_mmsi, Some(987654321));
+                        assert_eq!(cm.destinations(), Some((123456789, Some(987654321))));
+                        assert_eq!(cm.region(), None);
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
@@ -192,4 +287,12 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_channel_name() {
+        assert_eq!(channel_name(2087), Some("AIS 1 (87B)"));
+        assert_eq!(channel_name(2088), Some("AIS 2 (88B)"));
+        assert_eq!(channel_name(75), Some("Long range AIS (75)"));
+        assert_eq!(channel_name(1016), None);
+    }
 }
@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 23: Group Assignment Command
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct GroupAssignmentCommand {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct GroupAssignmentCommand {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
@@ -68,7 +79,8 @@ pub struct GroupAssignmentCommand {
 }
 
 /// Station Type (for message type 23).
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum StationType {
     /// All types of mobiles (default)
     AllTypes,
@@ -150,7 +162,8 @@ impl StationType {
 }
 
 /// Station interval (for message type 23)
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum StationInterval {
     /// As given by the autonomous mode
     Autonomous,
@@ -237,19 +250,24 @@ impl Default for StationInterval {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::GroupAssignmentCommand(
         GroupAssignmentCommand {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             ne_lat: { Some(pick_i64(bv, 58, 17) as f64 / 600.0) },
             ne_lon: { Some(pick_i64(bv, 40, 18) as f64 / 600.0) },
             sw_lat: { Some(pick_i64(bv, 93, 17) as f64 / 600.0) },
             sw_lon: { Some(pick_i64(bv, 75, 18) as f64 / 600.0) },
             station_type: StationType::new(pick_u64(bv, 110, 4) as u8)?,
-            ship_type: ShipType::new(pick_u64(bv, 114, 8) as u8),
+            ship_type: decode_ship_type(pick_u64(bv, 114, 8) as u8, store)?,
             cargo_type: CargoType::new(pick_u64(bv, 114, 8) as u8),
             txrx: {
                 let val = pick_u64(bv, 144, 2) as u8;
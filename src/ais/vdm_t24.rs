@@ -19,7 +19,8 @@ use super::*;
 pub(crate) fn handle(
     bv: &BitVec,
     _station: Station,
-    store: &mut NmeaParser,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     // Check whether the message bit layout follows part A or part B format
@@ -39,16 +40,20 @@ pub(crate) fn handle(
     // Pick the fields
     let vsd = VesselStaticData {
         own_vessel,
+        repeat_indicator: pick_u64(bv, 6, 2) as u8,
+        radio_channel,
+        message_id: None,
         ais_type: AisClass::ClassB,
         mmsi: pick_u64(bv, 8, 30) as u32,
         ais_version_indicator: 0,
         imo_number: None,
         call_sign: {
             if part_b {
-                let raw = pick_string(bv, 90, 7);
-                match raw.as_str() {
-                    "" => None,
-                    _ => Some(raw),
+                let raw = pick_6bit_string(bv, 90, 7);
+                if raw.is_empty() {
+                    None
+                } else {
+                    Some(raw)
                 }
             } else {
                 None
@@ -56,10 +61,11 @@ pub(crate) fn handle(
         },
         name: {
             if part_a {
-                let raw = pick_string(bv, 40, 120);
-                match raw.as_str() {
-                    "" => None,
-                    _ => Some(raw),
+                let raw = pick_6bit_string(bv, 40, 20);
+                if raw.is_empty() {
+                    None
+                } else {
+                    Some(raw)
                 }
             } else {
                 None
@@ -67,7 +73,7 @@ pub(crate) fn handle(
         },
         ship_type: {
             if part_b {
-                ShipType::new(pick_u64(bv, 40, 8) as u8)
+                decode_ship_type(pick_u64(bv, 40, 8) as u8, store)?
             } else {
                 ShipType::NotAvailable
             }
@@ -132,6 +138,7 @@ pub(crate) fn handle(
         eta: None,
         draught10: None,
         destination: None,
+        dte: None,
         mothership_mmsi: {
             if part_b {
                 Some(pick_u64(bv, 132, 30) as u32)
@@ -151,78 +158,151 @@ pub(crate) fn handle(
 }
 
 impl VesselStaticData {
-    /// Merge two data structures together. This is used to combine part A and B
-    /// of class B AIVDM type 24 messages.
-    fn merge(&self, other: &VesselStaticData) -> Result<VesselStaticData, String> {
-        if self.ais_type != other.ais_type {
-            Err(format!(
-                "Mismatching AIS types: {} != {}",
-                self.ais_type, other.ais_type
-            ))
-        } else if self.mmsi != other.mmsi {
-            Err(format!(
-                "Mismatching MMSI numbers: {} != {}",
-                self.mmsi, other.mmsi
-            ))
-        } else if self.imo_number != other.imo_number {
+    /// Merge two data structures together. This is used to combine part A and B of class B
+    /// AIVDM type 24 messages, but it can also combine e.g. type 5 and type 24 records of the
+    /// same vessel. Fields available in only one of the records are taken from it. If the AIS
+    /// types differ, the Class A data is preferred. An error is returned if the records are
+    /// about different vessels.
+    pub fn merge(&self, other: &VesselStaticData) -> Result<VesselStaticData, ParseError> {
+        // Prefer Class A data when merging records of different AIS types
+        let (primary, secondary) =
+            if other.ais_type == AisClass::ClassA && self.ais_type != AisClass::ClassA {
+                (other, self)
+            } else {
+                (self, other)
+            };
+        primary.merge_preferring(secondary)
+    }
+
+    /// Merge two data structures together preferring the values of `self`.
+    fn merge_preferring(&self, other: &VesselStaticData) -> Result<VesselStaticData, ParseError> {
+        if self.mmsi != other.mmsi {
+            Err(format!("Mismatching MMSI numbers: {} != {}", self.mmsi, other.mmsi).into())
+        } else if matches!((self.imo_number, other.imo_number), (Some(a), Some(b)) if a != b) {
             Err(format!(
                 "Mismatching IMO numbers: {} != {}",
-                self.mmsi, other.mmsi
-            ))
-        } else if self.ais_version_indicator != other.ais_version_indicator {
+                self.imo_number.unwrap_or(0),
+                other.imo_number.unwrap_or(0)
+            )
+            .into())
+        } else if self.ais_type == other.ais_type
+            && self.ais_version_indicator != other.ais_version_indicator
+        {
             Err(format!(
                 "Mismatching AIS version indicators: {} != {}",
                 self.ais_version_indicator, other.ais_version_indicator
-            ))
+            )
+            .into())
         } else {
-            Ok(VesselStaticData {
-                own_vessel: self.own_vessel,
-                ais_type: self.ais_type,
-                mmsi: self.mmsi,
-                ais_version_indicator: self.ais_version_indicator,
-                imo_number: choose_some(self.imo_number, other.imo_number),
-                call_sign: choose_some_string(&self.call_sign, &other.call_sign),
-                name: choose_some_string(&self.name, &other.name),
-                ship_type: {
-                    if self.ship_type != ShipType::NotAvailable {
-                        self.ship_type
-                    } else {
-                        other.ship_type
-                    }
-                },
-                cargo_type: {
-                    if self.cargo_type != CargoType::Undefined {
-                        self.cargo_type
-                    } else {
-                        other.cargo_type
-                    }
-                },
-                equipment_vendor_id: choose_some_string(
-                    &self.equipment_vendor_id,
-                    &other.equipment_vendor_id,
-                ),
-                equipment_model: choose_some(self.equipment_model, other.equipment_model),
-                equipment_serial_number: choose_some(
-                    self.equipment_serial_number,
-                    other.equipment_serial_number,
-                ),
-                dimension_to_bow: choose_some(self.dimension_to_bow, other.dimension_to_bow),
-                dimension_to_stern: choose_some(self.dimension_to_stern, other.dimension_to_stern),
-                dimension_to_port: choose_some(self.dimension_to_port, other.dimension_to_port),
-                dimension_to_starboard: choose_some(
-                    self.dimension_to_starboard,
-                    other.dimension_to_starboard,
-                ),
-                position_fix_type: choose_some(self.position_fix_type, other.position_fix_type),
-                eta: choose_some(self.eta, other.eta),
-                draught10: choose_some(self.draught10, other.draught10),
-                destination: choose_some_string(&self.destination, &other.destination),
-                mothership_mmsi: choose_some(self.mothership_mmsi, other.mothership_mmsi),
-            })
+            Ok(self.merge_fields(other))
+        }
+    }
+
+    /// Combine the fields of two data structures preferring the values of `self`.
+    fn merge_fields(&self, other: &VesselStaticData) -> VesselStaticData {
+        VesselStaticData {
+            own_vessel: self.own_vessel,
+            repeat_indicator: self.repeat_indicator,
+            radio_channel: self.radio_channel.or(other.radio_channel),
+            message_id: self.message_id.or(other.message_id),
+            ais_type: self.ais_type,
+            mmsi: self.mmsi,
+            ais_version_indicator: self.ais_version_indicator,
+            imo_number: choose_some(self.imo_number, other.imo_number),
+            call_sign: choose_some_clone(&self.call_sign, &other.call_sign),
+            name: choose_some_clone(&self.name, &other.name),
+            ship_type: {
+                if self.ship_type != ShipType::NotAvailable {
+                    self.ship_type
+                } else {
+                    other.ship_type
+                }
+            },
+            cargo_type: {
+                if self.cargo_type != CargoType::Undefined {
+                    self.cargo_type
+                } else {
+                    other.cargo_type
+                }
+            },
+            equipment_vendor_id: choose_some_clone(
+                &self.equipment_vendor_id,
+                &other.equipment_vendor_id,
+            ),
+            equipment_model: choose_some(self.equipment_model, other.equipment_model),
+            equipment_serial_number: choose_some(
+                self.equipment_serial_number,
+                other.equipment_serial_number,
+            ),
+            dimension_to_bow: choose_some(self.dimension_to_bow, other.dimension_to_bow),
+            dimension_to_stern: choose_some(self.dimension_to_stern, other.dimension_to_stern),
+            dimension_to_port: choose_some(self.dimension_to_port, other.dimension_to_port),
+            dimension_to_starboard: choose_some(
+                self.dimension_to_starboard,
+                other.dimension_to_starboard,
+            ),
+            position_fix_type: choose_some(self.position_fix_type, other.position_fix_type),
+            eta: choose_some(self.eta, other.eta),
+            draught10: choose_some(self.draught10, other.draught10),
+            destination: choose_some_clone(&self.destination, &other.destination),
+            dte: choose_some(self.dte, other.dte),
+            mothership_mmsi: choose_some(self.mothership_mmsi, other.mothership_mmsi),
         }
     }
 }
 
+/// Class B transceiver identification of AIS type 24 part B, see `VesselStaticData::equipment()`.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Equipment {
+    /// Manufacturer ID (3 characters), e.g. "SRT"
+    pub vendor_id: String,
+
+    /// Manufacturer name of a registered manufacturer ID
+    pub manufacturer: Option<&'static str>,
+
+    /// Unit model code (4 bits)
+    pub model: Option<u8>,
+
+    /// Serial number (20 bits)
+    pub serial_number: Option<u32>,
+}
+
+/// Decode NMEA registered manufacturer mnemonic code of common AIS transceiver vendors.
+pub(crate) fn manufacturer_name(vendor_id: &str) -> Option<&'static str> {
+    match vendor_id {
+        "ACR" => Some("ACR Electronics"),
+        "FEC" => Some("Furuno Electric"),
+        "GRM" => Some("Garmin"),
+        "JRC" => Some("Japan Radio"),
+        "KOD" => Some("Koden Electronics"),
+        "LWR" => Some("Lowrance"),
+        "RAY" => Some("Raymarine"),
+        "SRT" => Some("Software Radio Technology"),
+        "TRM" => Some("Trimble"),
+        "VSP" => Some("Vesper Marine"),
+        _ => None,
+    }
+}
+
+impl VesselStaticData {
+    /// Class B vendor ID, unit model and serial number bundled together with the manufacturer
+    /// name decoded. `None` is returned if the vendor ID isn't available, e.g. for type 5 and
+    /// type 24 part A data.
+    pub fn equipment(&self) -> Option<Equipment> {
+        let vendor_id = self.equipment_vendor_id.as_ref()?;
+        if vendor_id.is_empty() {
+            return None;
+        }
+        Some(Equipment {
+            vendor_id: vendor_id.clone(),
+            manufacturer: manufacturer_name(vendor_id),
+            model: self.equipment_model,
+            serial_number: self.equipment_serial_number,
+        })
+    }
+}
+
 /// Choose the argument which is Some. If both are Some, choose the first one.
 fn choose_some<T>(a: Option<T>, b: Option<T>) -> Option<T> {
     if a.is_some() {
@@ -233,7 +313,7 @@ fn choose_some<T>(a: Option<T>, b: Option<T>) -> Option<T> {
 }
 
 /// Choose the argument which is Some. If both are Some, choose the first one.
-fn choose_some_string(a: &Option<String>, b: &Option<String>) -> Option<String> {
+fn choose_some_clone<T: Clone>(a: &Option<T>, b: &Option<T>) -> Option<T> {
     if a.is_some() {
         a.clone()
     } else {
@@ -317,4 +397,152 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_flush_partial_vsds() {
+        let mut p = NmeaParser::new();
+        assert!(p.flush_partial_vsds().is_empty());
+
+        // Part A only
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D"),
+            Ok(ParsedMessage::Incomplete)
+        );
+        let vsds = p.flush_partial_vsds();
+        assert_eq!(vsds.len(), 1);
+        assert_eq!(vsds[0].mmsi, 271041815);
+        assert_eq!(vsds[0].name, Some("PROGUY".into()));
+        assert_eq!(vsds[0].call_sign, None);
+        assert_eq!(p.vsds_count(), 0);
+
+        // Part B arriving after the flush waits for a new part A
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,H42O55lti4hhhilD3nink000?050,0*40"),
+            Ok(ParsedMessage::Incomplete)
+        );
+        assert_eq!(p.flush_partial_vsds().len(), 1);
+    }
+
+    #[test]
+    fn test_vsd_pairing_window() {
+        let part_a = "!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D";
+        let part_b = "!AIVDM,1,1,,A,H42O55lti4hhhilD3nink000?050,0*40";
+        let unrelated = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+
+        // Parts within the window are combined
+        let mut p = NmeaParser::new();
+        p.set_vsd_pairing_window(100);
+        assert_eq!(p.parse_sentence(part_a), Ok(ParsedMessage::Incomplete));
+        for _ in 0..99 {
+            assert!(p.parse_sentence(unrelated).is_ok());
+        }
+        match p.parse_sentence(part_b) {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.name, Some("PROGUY".into()));
+                assert_eq!(vsd.call_sign, Some("TC6163".into()));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Stale part A is discarded and part B is cached as a new first part
+        assert_eq!(p.parse_sentence(part_a), Ok(ParsedMessage::Incomplete));
+        for _ in 0..100 {
+            assert!(p.parse_sentence(unrelated).is_ok());
+        }
+        assert_eq!(p.parse_sentence(part_b), Ok(ParsedMessage::Incomplete));
+        let vsds = p.flush_partial_vsds();
+        assert_eq!(vsds.len(), 1);
+        assert_eq!(vsds[0].name, None);
+        assert_eq!(vsds[0].call_sign, Some("TC6163".into()));
+    }
+
+    #[test]
+    fn test_merge_type5_and_type24() {
+        let t5 = VesselStaticData {
+            ais_type: AisClass::ClassA,
+            mmsi: 271041815,
+            ais_version_indicator: 1,
+            imo_number: Some(9134270),
+            name: Some("PROGUY".into()),
+            destination: Some("ISTANBUL".into()),
+            ..Default::default()
+        };
+
+        let t24 = VesselStaticData {
+            ais_type: AisClass::ClassB,
+            mmsi: 271041815,
+            name: Some("PROGUY 2".into()),
+            call_sign: Some("TC6163".into()),
+            equipment_vendor_id: Some("1D0".into()),
+            ..Default::default()
+        };
+
+        // Class A data is preferred regardless of the order
+        for merged in [t5.merge(&t24).unwrap(), t24.merge(&t5).unwrap()].iter() {
+            assert_eq!(merged.ais_type, AisClass::ClassA);
+            assert_eq!(merged.ais_version_indicator, 1);
+            assert_eq!(merged.imo_number, Some(9134270));
+            assert_eq!(merged.name, Some("PROGUY".into()));
+            assert_eq!(merged.call_sign, Some("TC6163".into()));
+            assert_eq!(merged.destination, Some("ISTANBUL".into()));
+            assert_eq!(merged.equipment_vendor_id, Some("1D0".into()));
+        }
+
+        // Different vessels
+        let mut other = t24.clone();
+        other.mmsi = 230000000;
+        assert_eq!(
+            t5.merge(&other),
+            Err(ParseError::InvalidSentence(
+                "Mismatching MMSI numbers: 271041815 != 230000000".to_string()
+            ))
+        );
+        let mut other = t5.clone();
+        other.imo_number = Some(1234567);
+        assert_eq!(
+            t5.merge(&other),
+            Err(ParseError::InvalidSentence(
+                "Mismatching IMO numbers: 9134270 != 1234567".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_equipment() {
+        let part_a = "!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D";
+        let mut p = NmeaParser::new();
+        assert_eq!(p.parse_sentence(part_a), Ok(ParsedMessage::Incomplete));
+        match p.parse_sentence("!AIVDM,1,1,,A,H42O55ltCBD<N90D3nink000?050,0*4E") {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                let equipment = vsd.equipment().unwrap();
+                assert_eq!(equipment.vendor_id, "SRT");
+                assert_eq!(equipment.manufacturer, Some("Software Radio Technology"));
+                assert_eq!(equipment.model, Some(3));
+                assert_eq!(equipment.serial_number, Some(123456));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Unregistered vendor ID
+        assert_eq!(p.parse_sentence(part_a), Ok(ParsedMessage::Incomplete));
+        match p.parse_sentence("!AIVDM,1,1,,A,H42O55lti4hhhilD3nink000?050,0*40") {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                let equipment = vsd.equipment().unwrap();
+                assert_eq!(equipment.vendor_id, "1D0");
+                assert_eq!(equipment.manufacturer, None);
+                assert_eq!(equipment.model, Some(12));
+                assert_eq!(equipment.serial_number, Some(199796));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Type 5 and part A don't carry the vendor ID
+        assert_eq!(VesselStaticData::default().equipment(), None);
+    }
 }
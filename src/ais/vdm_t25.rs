@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 25: Single Slot Binary Message
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct SingleSlotBinaryMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct SingleSlotBinaryMessage {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
@@ -38,6 +49,7 @@ pub struct SingleSlotBinaryMessage {
     pub app_id: Option<u16>,
 
     /// Data field of length 0-128 bits.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
     pub data: BitVec,
 }
 
@@ -48,6 +60,7 @@ pub struct SingleSlotBinaryMessage {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     let addressed = pick_u64(bv, 38, 1) != 0;
@@ -57,6 +70,9 @@ pub(crate) fn handle(
         SingleSlotBinaryMessage {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             dest_mmsi: {
                 if addressed {
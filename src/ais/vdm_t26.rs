@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 26: Multiple Slot Binary Message
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct MultipleSlotBinaryMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,6 +28,16 @@ pub struct MultipleSlotBinaryMessage {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
@@ -38,6 +49,7 @@ pub struct MultipleSlotBinaryMessage {
     pub app_id: Option<u16>,
 
     /// Data field of length 0-1004 bits.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
     pub data: BitVec,
 
     /// Radio status
@@ -51,6 +63,7 @@ pub struct MultipleSlotBinaryMessage {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     let addressed = pick_u64(bv, 38, 1) != 0;
@@ -60,6 +73,9 @@ pub(crate) fn handle(
         MultipleSlotBinaryMessage {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             dest_mmsi: {
                 if addressed {
@@ -108,6 +124,7 @@ mod test {
                     // The expected result
                     ParsedMessage::MultipleSlotBinaryMessage(msbm) => {
                         assert_eq!(msbm.mmsi, 137920605);
+                        assert_eq!(msbm.repeat_indicator, 1);
                         assert_eq!(msbm.dest_mmsi, Some(838351848));
                         assert_eq!(msbm.app_id, None);
                     }
@@ -131,6 +148,7 @@ mod test {
                     // The expected result
                     ParsedMessage::MultipleSlotBinaryMessage(msbm) => {
                         assert_eq!(msbm.mmsi, 285913259);
+                        assert_eq!(msbm.repeat_indicator, 3);
                         assert_eq!(msbm.dest_mmsi, None);
                         assert_eq!(msbm.app_id, Some(16254));
                     }
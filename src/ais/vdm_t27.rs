@@ -20,11 +20,15 @@ use super::*;
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::VesselDynamicData(VesselDynamicData {
         own_vessel: { own_vessel },
         station: { station },
+        repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+        radio_channel: { radio_channel },
+        message_id: None,
         ais_type: { AisClass::ClassA },
         mmsi: { pick_u64(bv, 8, 30) as u32 },
         nav_status: { NavigationStatus::new(pick_u64(bv, 40, 4) as u8) },
@@ -55,20 +59,17 @@ pub(crate) fn handle(
                 None
             }
         },
-        cog: {
-            let cog_raw = pick_u64(bv, 62, 17);
-            if cog_raw != 91000 {
-                Some(cog_raw as f64 * 0.1)
-            } else {
-                None
-            }
-        },
+        low_resolution_position: true,
+        cog: decode_whole_degrees(pick_u64(bv, 85, 9)),
         heading_true: None,
         timestamp_seconds: 0,
+        timestamp: None,
         positioning_system_meta: None,
         current_gnss_position: Some(pick_u64(bv, 62, 1) == 0),
         special_manoeuvre: None,
         raim_flag: pick_u64(bv, 39, 1) != 0,
+        regional: None,
+        regional_b: None,
         class_b_unit_flag: None,
         class_b_display: None,
         class_b_dsc: None,
@@ -95,6 +96,7 @@ mod test {
                     // The expected result
                     ParsedMessage::VesselDynamicData(vdd) => {
                         assert_eq!(vdd.mmsi, 206914217);
+                        assert_eq!(vdd.repeat_indicator, 1);
                         assert_eq!(vdd.nav_status, NavigationStatus::NotUnderCommand);
                         assert_eq!(vdd.rot, None);
                         assert_eq!(vdd.rot_direction, None);
@@ -102,7 +104,8 @@ mod test {
                         assert!(!vdd.high_position_accuracy);
                         assert::close(vdd.latitude.unwrap_or(0.0), 4.8, 0.1);
                         assert::close(vdd.longitude.unwrap_or(0.0), 137.0, 0.1);
-                        assert::close(vdd.cog.unwrap_or(0.0), 290.0, 1.0);
+                        assert!(vdd.low_resolution_position);
+                        assert_eq!(vdd.cog, Some(167.0));
                         assert_eq!(vdd.timestamp_seconds, 0);
                         assert_eq!(vdd.current_gnss_position, Some(true));
                         assert!(!vdd.raim_flag);
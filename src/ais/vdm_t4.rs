@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 4: Base Station Report
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct BaseStationReport {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,11 +28,25 @@ pub struct BaseStationReport {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
+    /// AIS message type: 4 = base station report, 11 = UTC/date response
+    pub message_type: u8,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
     /// Timestamp
-    pub timestamp: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp: Option<Timestamp>,
 
     /// Position accuracy: true = high (<= 10 m), false = low (> 10 m)
     pub high_position_accuracy: bool,
@@ -42,9 +57,13 @@ pub struct BaseStationReport {
     /// Longitude
     pub longitude: Option<f64>,
 
-    // Type of electronic position fixing device.
+    /// Type of electronic position fixing device.
     pub position_fix_type: Option<PositionFixType>,
 
+    /// Transmission control for long-range broadcast message (type 27):
+    /// false = default, true = request transmission of long-range broadcast messages
+    pub long_range_broadcast: bool,
+
     /// Riverine And Inland Navigation systems blue sign:
     /// RAIM (Receiver autonomous integrity monitoring) flag of electronic position
     /// fixing device; false = RAIM not in use = default; true = RAIM in use
@@ -56,6 +75,20 @@ pub struct BaseStationReport {
     pub radio_status: u32,
 }
 
+impl BaseStationReport {
+    /// True if this is a UTC/date response (type 11) to an inquiry, false if this is an
+    /// unsolicited base station report (type 4).
+    pub fn is_inquiry_response(&self) -> bool {
+        self.message_type == 11
+    }
+
+    /// True if this is a UTC/date response (type 11) from the station the inquiry (type 10)
+    /// was addressed to.
+    pub fn is_response_to(&self, inquiry: &UtcDateInquiry) -> bool {
+        self.message_type == 11 && self.mmsi == inquiry.destination_mmsi
+    }
+}
+
 impl LatLon for BaseStationReport {
     fn latitude(&self) -> Option<f64> {
         self.latitude
@@ -72,22 +105,38 @@ impl LatLon for BaseStationReport {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
-    Ok(ParsedMessage::BaseStationReport(BaseStationReport {
+    Ok(ParsedMessage::BaseStationReport(pick_base_station_report(
+        bv,
+        station,
+        radio_channel,
+        store,
+        own_vessel,
+        4,
+    )?))
+}
+
+/// Decode the layout shared by types 4 and 11.
+pub(crate) fn pick_base_station_report(
+    bv: &BitVec,
+    station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
+    own_vessel: bool,
+    message_type: u8,
+) -> Result<BaseStationReport, ParseError> {
+    Ok(BaseStationReport {
         own_vessel: { own_vessel },
         station: { station },
+        repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+        radio_channel: { radio_channel },
+        message_id: None,
+        message_type,
         mmsi: { pick_u64(bv, 8, 30) as u32 },
-        timestamp: {
-            Some(parse_ymdhs(
-                pick_u64(bv, 38, 14) as i32,
-                pick_u64(bv, 52, 4) as u32,
-                pick_u64(bv, 56, 5) as u32,
-                pick_u64(bv, 61, 5) as u32,
-                pick_u64(bv, 66, 6) as u32,
-                pick_u64(bv, 72, 6) as u32,
-            )?)
-        },
+        timestamp: { pick_utc_date_time_with_warning(bv, 38, store) },
         high_position_accuracy: { pick_u64(bv, 78, 1) != 0 },
         latitude: {
             let lat_raw = pick_i64(bv, 107, 27) as i32;
@@ -109,12 +158,13 @@ pub(crate) fn handle(
             let raw = pick_u64(bv, 134, 4) as u8;
             match raw {
                 0 => None,
-                _ => Some(PositionFixType::new(raw)),
+                _ => Some(decode_position_fix_type(raw, store)?),
             }
         },
+        long_range_broadcast: { pick_u64(bv, 138, 1) != 0 },
         raim_flag: { pick_u64(bv, 148, 1) != 0 },
         radio_status: { pick_u64(bv, 149, 19) as u32 },
-    }))
+    })
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -132,6 +182,8 @@ mod test {
                     // The expected result
                     ParsedMessage::BaseStationReport(bsr) => {
                         assert_eq!(bsr.mmsi, 3669702);
+                        assert_eq!(bsr.message_type, 4);
+                        assert_eq!(bsr.repeat_indicator, 0);
                         assert_eq!(
                             bsr.timestamp,
                             Utc.with_ymd_and_hms(2007, 5, 14, 19, 57, 39).single()
@@ -140,6 +192,8 @@ mod test {
                         assert::close(bsr.latitude.unwrap_or(0.0), 36.884, 0.001);
                         assert::close(bsr.longitude.unwrap_or(0.0), -76.352, 0.001);
                         assert_eq!(bsr.position_fix_type, Some(PositionFixType::Surveyed));
+                        assert!(!bsr.long_range_broadcast);
+                        assert!(!bsr.is_inquiry_response());
                         assert!(!bsr.raim_flag);
                         assert_eq!(bsr.radio_status, 67039);
                     }
@@ -156,4 +210,113 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type4_utc_not_available() {
+        // Hour 24 means that UTC is not available
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,403OviQuMGHqWrRO9>E6fE700@GO,0*46") {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::BaseStationReport(bsr) => {
+                        assert_eq!(bsr.mmsi, 3669702);
+                        assert_eq!(bsr.timestamp, None);
+                        assert::close(bsr.latitude.unwrap_or(0.0), 36.884, 0.001);
+                        assert::close(bsr.longitude.unwrap_or(0.0), -76.352, 0.001);
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type4_date_not_available() {
+        // Year, month and day 0 mean that the date is not available; the time of day is valid
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,403OviP000CqWrRO9>E6fE700@GO,0*03") {
+            Ok(ps) => match ps {
+                ParsedMessage::BaseStationReport(bsr) => {
+                    assert_eq!(bsr.mmsi, 3669702);
+                    assert_eq!(bsr.timestamp, None);
+                    assert::close(bsr.latitude.unwrap_or(0.0), 36.884, 0.001);
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+
+        // Month 13 is invalid
+        match p.parse_sentence("!AIVDM,1,1,,A,403OviQuOGCqWrRO9>E6fE700@GO,0*4F") {
+            Ok(ps) => match ps {
+                ParsedMessage::BaseStationReport(bsr) => {
+                    assert_eq!(bsr.timestamp, None);
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type4_position_fix_type() {
+        let internal_gnss = "!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE?00@GO,0*45";
+        let reserved = "!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE<00@GO,0*46";
+
+        // Internal GNSS (15) is valid also in strict mode
+        let mut p = NmeaParser::new();
+        p.set_strict(true);
+        match p.parse_sentence(internal_gnss) {
+            Ok(ParsedMessage::BaseStationReport(bsr)) => {
+                assert_eq!(bsr.position_fix_type, Some(PositionFixType::InternalGnss));
+                assert_eq!(PositionFixType::InternalGnss.to_value(), 15);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert!(p.take_warnings().is_empty());
+
+        // Reserved values are rejected in strict mode
+        match p.parse_sentence(reserved) {
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert!(e.to_string().contains("Reserved position fix type: 12"));
+            }
+        }
+
+        // ... and fall back to undefined with a warning by default
+        p.set_strict(false);
+        match p.parse_sentence(reserved) {
+            Ok(ParsedMessage::BaseStationReport(bsr)) => {
+                assert_eq!(bsr.position_fix_type, Some(PositionFixType::Undefined));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(
+            p.take_warnings(),
+            vec![ParseWarning::UnrecognizedPositionFixType(12)]
+        );
+    }
 }
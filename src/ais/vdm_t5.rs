@@ -19,10 +19,15 @@ use super::*;
 pub(crate) fn handle(
     bv: &BitVec,
     _station: Station,
+    radio_channel: Option<char>,
+    store: &mut ParseContext,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::VesselStaticData(VesselStaticData {
         own_vessel,
+        repeat_indicator: pick_u64(bv, 6, 2) as u8,
+        radio_channel,
+        message_id: None,
         ais_type: AisClass::ClassB,
         mmsi: pick_u64(bv, 8, 30) as u32,
         ais_version_indicator: pick_u64(bv, 38, 2) as u8,
@@ -34,20 +39,22 @@ pub(crate) fn handle(
             }
         },
         call_sign: {
-            let raw = pick_string(bv, 70, 7);
-            match raw.as_str() {
-                "" => None,
-                _ => Some(raw),
+            let raw = pick_6bit_string(bv, 70, 7);
+            if raw.is_empty() {
+                None
+            } else {
+                Some(raw)
             }
         },
         name: {
-            let raw = pick_string(bv, 112, 20);
-            match raw.as_str() {
-                "" => None,
-                _ => Some(raw),
+            let raw = pick_6bit_string(bv, 112, 20);
+            if raw.is_empty() {
+                None
+            } else {
+                Some(raw)
             }
         },
-        ship_type: { ShipType::new(pick_u64(bv, 232, 8) as u8) },
+        ship_type: { decode_ship_type(pick_u64(bv, 232, 8) as u8, store)? },
         cargo_type: { CargoType::new(pick_u64(bv, 232, 8) as u8) },
         equipment_vendor_id: {
             None // part of AIS class B
@@ -66,16 +73,27 @@ pub(crate) fn handle(
             let raw = pick_u64(bv, 270, 4) as u8;
             match raw {
                 0 => None,
-                _ => Some(PositionFixType::new(raw)),
+                _ => Some(decode_position_fix_type(raw, store)?),
             }
         },
+        #[cfg(feature = "time")]
+        eta: pick_eta_with_now(bv, 274, store.current_time())?,
+        #[cfg(not(feature = "time"))]
         eta: pick_eta(bv, 274)?,
         draught10: Some(pick_u64(bv, 294, 8) as u8),
         destination: {
-            let raw = pick_string(bv, 302, 20);
-            match raw.as_str() {
-                "" => None,
-                _ => Some(raw),
+            let raw = pick_6bit_string(bv, 302, 20);
+            if raw.is_empty() {
+                None
+            } else {
+                Some(raw)
+            }
+        },
+        dte: {
+            if bv.len() > 422 {
+                Some(pick_u64(bv, 422, 1) == 0)
+            } else {
+                None
             }
         },
         mothership_mmsi: { None },
@@ -91,6 +109,7 @@ mod test {
     #[test]
     fn test_parse_vdm_type5() {
         let mut p = NmeaParser::new();
+        p.set_current_time(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).single().unwrap());
 
         let s1 = "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C";
         let s2 = "!AIVDM,2,2,1,A,88888888880,2*25";
@@ -129,15 +148,13 @@ mod test {
                         assert_eq!(vsd.dimension_to_port, Some(1));
                         assert_eq!(vsd.dimension_to_starboard, Some(31));
                         assert_eq!(vsd.position_fix_type, Some(PositionFixType::GPS));
-                        assert_eq!(vsd.eta, {
-                            vsd.eta.map(|dt| {
-                                Utc.with_ymd_and_hms(dt.year(), 5, 15, 14, 0, 30)
-                                    .single()
-                                    .unwrap()
-                            })
-                        });
+                        assert_eq!(
+                            vsd.eta,
+                            Utc.with_ymd_and_hms(2024, 5, 15, 14, 0, 30).single()
+                        );
                         assert_eq!(vsd.draught10, Some(122));
                         assert_eq!(vsd.destination, Some("NEW YORK".into()));
+                        assert_eq!(vsd.dte, Some(true));
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
@@ -213,4 +230,152 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type5_eta_next_year() {
+        // ETA more than 180 days in the past is assumed to be about the next year
+        let mut p = NmeaParser::new();
+        p.set_current_time(Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).single().unwrap());
+        p.parse_sentence(
+            "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+        )
+        .ok();
+        match p.parse_sentence("!AIVDM,2,2,1,A,88888888880,2*25") {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::VesselStaticData(vsd) => {
+                        assert_eq!(
+                            vsd.eta,
+                            Utc.with_ymd_and_hms(2025, 5, 15, 14, 0, 30).single()
+                        );
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type5_embedded_at() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "!AIVDM,1,1,,A,55?MbV02;H;s<HtKR20EHE80@T4@Dn2222222216L961O5Gf0NSQEp6ClRp888888888880,2*1E",
+        ) {
+            Ok(ps) => match ps {
+                ParsedMessage::VesselStaticData(vsd) => {
+                    let name = vsd.name.unwrap();
+                    assert_eq!(name.to_string(), "EVER");
+                    assert_eq!(name.raw_text(), "EVER@DIADEM         ");
+                    assert!(name.had_embedded_at());
+                    assert!(!name.is_clean());
+
+                    let call_sign = vsd.call_sign.unwrap();
+                    assert_eq!(call_sign, "3FOF8");
+                    assert!(!call_sign.had_embedded_at());
+                    assert!(call_sign.is_clean());
+                    assert_eq!(String::from(call_sign), "3FOF8".to_string());
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type5_truncated() {
+        // The first fragment of a two-fragment type 5 message (360 bits) as a single sentence
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "!AIVDM,1,1,,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*2E",
+        ) {
+            Err(e) => match e.inner_error() {
+                ParseError::InvalidSentence(msg) => {
+                    assert_eq!(
+                        msg,
+                        "!VDM message type 5 too short: 360 bits, at least 420 required"
+                    );
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Ok(_) => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type5_420_bits() {
+        // 420 bits: the DTE bit and the spare bit at the end are left out
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "!AIVDM,1,1,,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp88888888888,0*2E",
+        ) {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.mmsi, 351759000);
+                assert_eq!(vsd.name, Some("EVER DIADEM".into()));
+                assert_eq!(vsd.draught10, Some(122));
+                assert_eq!(vsd.destination, Some("NEW YORK".into()));
+                assert_eq!(vsd.dte, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type5_ship_type_out_of_range() {
+        // Ship and cargo type 150
+        let s1 = "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn222222222FL961O5Gf0NSQEp6ClRp8,0*6F";
+        let s2 = "!AIVDM,2,2,1,A,88888888880,2*25";
+
+        // Warning by default
+        let mut p = NmeaParser::new();
+        assert_eq!(p.parse_sentence(s1).ok(), Some(ParsedMessage::Incomplete));
+        match p.parse_sentence(s2) {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.ship_type, ShipType::NotAvailable);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(
+            p.take_warnings(),
+            vec![ParseWarning::UnexpectedShipType(150)]
+        );
+
+        // Error in strict mode
+        p.set_strict(true);
+        assert_eq!(p.parse_sentence(s1).ok(), Some(ParsedMessage::Incomplete));
+        match p.parse_sentence(s2) {
+            Err(e) => match e.inner_error() {
+                ParseError::InvalidSentence(msg) => {
+                    assert_eq!(msg, "Ship and cargo type out of range 0..=99: 150");
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Ok(_) => {
+                assert!(false);
+            }
+        }
+        assert!(p.take_warnings().is_empty());
+    }
 }
@@ -19,7 +19,8 @@ use super::*;
 // -------------------------------------------------------------------------------------------------
 
 /// Type 6: Binary Addressed Message
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct BinaryAddressedMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,13 +28,23 @@ pub struct BinaryAddressedMessage {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
-    /// User ID (2 bits)
+    /// Sequence number (0-3) used for acknowledgement with type 7
     pub sequence_number: u8,
 
-    /// User ID (30 bits)
+    /// Destination user ID (30 bits)
     pub destination_mmsi: u32,
 
     /// Retransmit flag
@@ -64,12 +75,16 @@ impl LatLon for BinaryAddressedMessage {
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::BinaryAddressedMessage(
         BinaryAddressedMessage {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             sequence_number: { pick_u64(bv, 38, 2) as u8 },
             destination_mmsi: { pick_u64(bv, 40, 30) as u32 },
@@ -95,6 +110,7 @@ mod test {
                     // The expected result
                     ParsedMessage::BinaryAddressedMessage(bam) => {
                         assert_eq!(bam.mmsi, 150834090);
+                        assert_eq!(bam.repeat_indicator, 1);
                         assert_eq!(bam.sequence_number, 3);
                         assert_eq!(bam.destination_mmsi, 313240222);
                         assert!(!bam.retransmit_flag);
@@ -1,5 +1,5 @@
 /*
-Copyright 2020 Timo Saarinen
+Copyright 2024 Timo Saarinen
 
 Licensed under the Apache License, Version 2.0 (the "License");
 you may not use this file except in compliance with the License.
@@ -15,7 +15,53 @@ limitations under the License.
 */
 
 use super::*;
+use vdm_t13::pick_acknowledgement;
 
-// Message type 13 is a receipt acknowledgement to senders of previous messages of type 12. 
-// The message layout is identical to a type 7 Binary Acknowledge.
+// -------------------------------------------------------------------------------------------------
 
+/// AIS VDM/VDO type 7: Binary Acknowledge
+pub(crate) fn handle(
+    bv: &BitVec,
+    station: Station,
+    radio_channel: Option<char>,
+    own_vessel: bool,
+) -> Result<ParsedMessage, ParseError> {
+    Ok(ParsedMessage::BinaryAcknowledge(pick_acknowledgement(
+        bv,
+        station,
+        radio_channel,
+        own_vessel,
+        7,
+    )))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vdm_type7() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,739UOj0jFs9R,0*6F") {
+            Ok(ParsedMessage::BinaryAcknowledge(ack)) => {
+                assert_eq!(ack.message_type, 7);
+                assert_eq!(ack.mmsi, 211378120);
+                assert_eq!(ack.acknowledgements(), vec![(211217560, 2)]);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        match p.parse_sentence("!AIVDM,1,1,,B,739UOj0jFs9R=i:c4@,4*11") {
+            Ok(ParsedMessage::BinaryAcknowledge(ack)) => {
+                assert_eq!(ack.radio_channel, Some('B'));
+                assert_eq!(ack.acknowledgements(), vec![(211217560, 2), (230992580, 1)]);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+}
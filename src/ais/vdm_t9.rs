@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 9: Standard SAR Aircraft Position Report
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct StandardSarAircraftPositionReport {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -27,12 +28,32 @@ pub struct StandardSarAircraftPositionReport {
     /// AIS station type.
     pub station: Station,
 
+    /// Repeat indicator (2 bits)
+    pub repeat_indicator: u8,
+
+    /// Radio channel code of the sentence ('A' or 'B', sometimes '1' or '2')
+    pub radio_channel: Option<char>,
+
+    /// Sequential message ID of the VDM/VDO sentence (0-9) if given. Receivers typically fill it
+    /// in for multi-fragment messages only.
+    pub message_id: Option<u8>,
+
     /// User ID (30 bits)
     pub mmsi: u32,
 
-    /// Altitude
+    /// Altitude in meters (GNSS or barometric, see `barometric_altitude`). Value 4094 means
+    /// 4094 meters or higher, see `altitude_is_max_range`.
     pub altitude: Option<u16>,
 
+    /// True if the altitude is at the upper limit of the field (raw value 4094), i.e. the actual
+    /// altitude is 4094 meters or higher.
+    pub altitude_is_max_range: bool,
+
+    /// Altitude sensor: true = barometric, false = GNSS = default. The flag is the first
+    /// bit of the regional reserved field as defined in ITU-R M.1371-5; earlier editions leave
+    /// it regional, so the value may be unreliable from older stations.
+    pub barometric_altitude: bool,
+
     /// Speed over ground in knots. Value 1022 means 1022 knots or more.
     pub sog_knots: Option<u16>,
 
@@ -51,15 +72,18 @@ pub struct StandardSarAircraftPositionReport {
     /// Derived from UTC second (6 bits)
     pub timestamp_seconds: u8,
 
-    /// Regional, reserved.
+    /// Regional, reserved (8 bits). ITU-R M.1371-5 uses the first bit for the altitude sensor
+    /// (`barometric_altitude`) and leaves the rest spare.
     pub regional: u8,
 
     /// Data terminal ready:
-    /// true = ready,
-    /// false = not ready
+    /// true = ready (raw bit 0),
+    /// false = not ready = default (raw bit 1)
     pub dte: bool,
 
-    /// Assigned flag.
+    /// Assigned mode flag:
+    /// false = Station operating in autonomous and continuous mode = default
+    /// true  = Station operating in assigned mode
     pub assigned: bool,
 
     /// Riverine And Inland Navigation systems blue sign:
@@ -81,18 +105,32 @@ impl LatLon for StandardSarAircraftPositionReport {
     }
 }
 
+impl PositionReport for StandardSarAircraftPositionReport {
+    fn sog_knots(&self) -> Option<f64> {
+        self.sog_knots.map(f64::from)
+    }
+
+    fn cog(&self) -> Option<f64> {
+        self.cog
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// AIS VDM/VDO type 9: Standard SAR Aircraft Position Report
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    radio_channel: Option<char>,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::StandardSarAircraftPositionReport(
         StandardSarAircraftPositionReport {
             own_vessel: { own_vessel },
             station: { station },
+            repeat_indicator: { pick_u64(bv, 6, 2) as u8 },
+            radio_channel: { radio_channel },
+            message_id: None,
             mmsi: { pick_u64(bv, 8, 30) as u32 },
             altitude: {
                 let raw = pick_u64(bv, 38, 12) as u16;
@@ -102,6 +140,8 @@ pub(crate) fn handle(
                     None
                 }
             },
+            altitude_is_max_range: { pick_u64(bv, 38, 12) == 4094 },
+            barometric_altitude: { pick_u64(bv, 134, 1) != 0 },
             sog_knots: {
                 let raw = pick_u64(bv, 50, 10) as u16;
                 if raw != 1023 {
@@ -127,16 +167,10 @@ pub(crate) fn handle(
                     None
                 }
             },
-            cog: {
-                let cog_raw = pick_u64(bv, 116, 12);
-                if cog_raw != 0xE10 {
-                    Some(cog_raw as f64 * 0.1)
-                } else {
-                    None
-                }
-            },
+            cog: decode_cog(pick_u64(bv, 116, 12)),
             timestamp_seconds: pick_u64(bv, 128, 6) as u8,
             regional: { pick_u64(bv, 134, 8) as u8 },
+            // Inverted polarity: 0 = available, 1 = not available
             dte: { pick_u64(bv, 142, 1) == 0 },
             assigned: { pick_u64(bv, 146, 1) != 0 },
             raim_flag: { pick_u64(bv, 147, 1) != 0 },
@@ -159,8 +193,11 @@ mod test {
                 match ps {
                     // The expected result
                     ParsedMessage::StandardSarAircraftPositionReport(sapr) => {
+                        assert_eq!(sapr.radio_channel, Some('B'));
                         assert_eq!(sapr.mmsi, 111232511);
                         assert_eq!(sapr.altitude, Some(303));
+                        assert!(!sapr.altitude_is_max_range);
+                        assert!(!sapr.barometric_altitude);
                         assert_eq!(sapr.sog_knots, Some(42));
                         assert!(!sapr.high_position_accuracy);
                         assert::close(sapr.longitude.unwrap_or(0.0), -6.27884, 0.00001);
@@ -186,4 +223,39 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type9_assigned() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,B,91b55wi;hbOS@OdQAC062Ch2889h,0*38") {
+            Ok(ParsedMessage::StandardSarAircraftPositionReport(sapr)) => {
+                assert_eq!(sapr.mmsi, 111232511);
+                assert!(sapr.assigned);
+                assert!(!sapr.raim_flag);
+                assert_eq!(sapr.radio_status, 33392);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_type9_max_altitude() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,B,91b55wwwPbOS@OdQAC062Cp2089h,0*42") {
+            Ok(ParsedMessage::StandardSarAircraftPositionReport(sapr)) => {
+                assert_eq!(sapr.mmsi, 111232511);
+                assert_eq!(sapr.altitude, Some(4094));
+                assert!(sapr.altitude_is_max_range);
+                assert!(sapr.barometric_altitude);
+                assert_eq!(sapr.regional, 0x80);
+                assert_eq!(sapr.sog_knots, Some(42));
+                assert!(!sapr.dte);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
@@ -16,20 +16,156 @@ limitations under the License.
 
 use core::fmt;
 use core::num::{ParseIntError, ParseFloatError};
-use alloc::string::String;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
 
 /// Parse error returned by `NmeaParser::parse_sentence()`. `String` data type is used instead of
 /// `static &str` because the error messages are expected to contain context-specific details.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum ParseError {
     /// Unsupported (or unimplemented) sentence type
     UnsupportedSentenceType(String),
 
-    /// NMEA checksum doesn't match
+    /// AIS payload contains invalid characters or a field has a corrupted value
     CorruptedSentence(String),
 
+    /// NMEA checksum doesn't match, is missing or is truncated. This error is constructed
+    /// without heap allocation, which keeps counting and skipping bad sentences of a noisy feed
+    /// cheap.
+    Checksum(ChecksumError),
+
+    /// The sentence isn't structurally valid, see `validate_sentence()`. Like `Checksum`, this
+    /// error is constructed without heap allocation.
+    Structure(StructureError),
+
     /// The sentence format isn't what expected
     InvalidSentence(String),
+
+    /// The sentence can't be parsed without the multi-sentence state of the parser, e.g. by
+    /// `NmeaParser::parse_sentence_stateless()`
+    RequiresState(String),
+
+    /// Error which occurred while handling a sentence of the given type. The original sentence
+    /// is included to make it easier to locate the problem.
+    WithContext {
+        /// Sentence type without the start delimiter (e.g. "GGA" or "VDM")
+        sentence_type: &'static str,
+
+        /// The sentence which caused the error
+        sentence: String,
+
+        /// The actual error
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        error: Box<ParseError>,
+    },
+}
+
+impl ParseError {
+    /// Wrap the error with the type and content of the sentence it occurred in.
+    pub(crate) fn with_context(self, sentence_type: &'static str, sentence: &str) -> ParseError {
+        match self {
+            ParseError::WithContext { .. } => self,
+            _ => ParseError::WithContext {
+                sentence_type,
+                sentence: sentence.to_string(),
+                error: Box::new(self),
+            },
+        }
+    }
+
+    /// Return the type of the sentence which caused the error if it's known.
+    pub fn sentence_type(&self) -> Option<&str> {
+        match self {
+            ParseError::WithContext { sentence_type, .. } => Some(sentence_type),
+            _ => None,
+        }
+    }
+
+    /// Return the sentence which caused the error if it's known.
+    pub fn sentence(&self) -> Option<&str> {
+        match self {
+            ParseError::WithContext { sentence, .. } => Some(sentence.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the actual error without the sentence context.
+    pub fn inner_error(&self) -> &ParseError {
+        match self {
+            ParseError::WithContext { error, .. } => error.inner_error(),
+            _ => self,
+        }
+    }
+}
+
+/// Checksum problem of `ParseError::Checksum`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ChecksumError {
+    /// The sentence has no checksum but checksums are required
+    Missing,
+
+    /// The checksum has less than two digits
+    Truncated,
+
+    /// The given checksum doesn't match the calculated one
+    Mismatch {
+        /// Checksum calculated from the sentence
+        calculated: u8,
+
+        /// The two checksum characters given in the sentence
+        given: [u8; 2],
+    },
+}
+
+impl fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChecksumError::Missing => write!(f, "checksum missing"),
+            ChecksumError::Truncated => write!(f, "checksum truncated"),
+            ChecksumError::Mismatch { calculated, given } => write!(
+                f,
+                "checksum \"{:02X}\" != \"{}\"",
+                calculated,
+                core::str::from_utf8(given).unwrap_or("??")
+            ),
+        }
+    }
+}
+
+/// Structural problem of `ParseError::Structure`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum StructureError {
+    /// The sentence doesn't start with '$' or '!'
+    NoStartDelimiter,
+
+    /// The sentence is longer than `MAX_NMEA_SENTENCE_LENGTH` (length including CR LF)
+    TooLong(usize),
+
+    /// The sentence has a control or non-ASCII character (byte position)
+    InvalidCharacter(usize),
+}
+
+impl fmt::Display for StructureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructureError::NoStartDelimiter => write!(f, "start delimiter missing"),
+            StructureError::TooLong(len) => write!(
+                f,
+                "sentence length {} exceeds the maximum of {}",
+                len,
+                crate::MAX_NMEA_SENTENCE_LENGTH
+            ),
+            StructureError::InvalidCharacter(i) => {
+                write!(f, "invalid character at position {}", i)
+            }
+        }
+    }
 }
 
 impl From<String> for ParseError {
@@ -57,7 +193,15 @@ impl fmt::Display for ParseError {
                 write!(f, "Unsupported NMEA sentence type: {}", s)
             }
             ParseError::CorruptedSentence(s) => write!(f, "Corrupted NMEA sentence: {}", s),
+            ParseError::Checksum(e) => write!(f, "Corrupted NMEA sentence: {}", e),
+            ParseError::Structure(e) => write!(f, "Invalid NMEA sentence: {}", e),
             ParseError::InvalidSentence(s) => write!(f, "Invalid NMEA sentence: {}", s),
+            ParseError::RequiresState(s) => write!(f, "NMEA sentence requires parser state: {}", s),
+            ParseError::WithContext {
+                sentence_type,
+                sentence,
+                error,
+            } => write!(f, "{} (in {} sentence: {})", error, sentence_type, sentence),
         }
     }
 }
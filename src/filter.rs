@@ -0,0 +1,144 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::*;
+
+/// Selects the sentences `NmeaParser` decodes. Sentences rejected by the filter are skipped
+/// right after checksum verification and `ParsedMessage::Ignored` is returned for them without
+/// decoding the fields, storing fragments or building the AIS payload bits. By default
+/// everything is accepted. Set the filter with `NmeaParser::set_message_filter()`.
+///
+/// Sentence types are given without the talker ID, e.g. "GGA" or "VDM". AIS message types apply
+/// to VDM and VDO sentences.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageFilter {
+    /// Accepted sentence types or `None` for all
+    included_types: Option<HashSet<String>>,
+
+    /// Rejected sentence types
+    excluded_types: HashSet<String>,
+
+    /// Bit mask of accepted AIS message types or `None` for all
+    included_ais_types: Option<u64>,
+
+    /// Bit mask of rejected AIS message types
+    excluded_ais_types: u64,
+}
+
+impl MessageFilter {
+    /// Construct a filter which accepts everything.
+    pub fn new() -> MessageFilter {
+        MessageFilter::default()
+    }
+
+    /// Accept only the given sentence types. Calling this again adds to the types.
+    pub fn include_sentence_types(&mut self, types: &[&str]) -> &mut Self {
+        self.included_types
+            .get_or_insert_with(HashSet::new)
+            .extend(types.iter().map(|t| t.to_string()));
+        self
+    }
+
+    /// Reject the given sentence types.
+    pub fn exclude_sentence_types(&mut self, types: &[&str]) -> &mut Self {
+        self.excluded_types
+            .extend(types.iter().map(|t| t.to_string()));
+        self
+    }
+
+    /// Accept only the given AIS message types (1-63). Calling this again adds to the types.
+    pub fn include_ais_types(&mut self, types: &[u8]) -> &mut Self {
+        let mask = self.included_ais_types.get_or_insert(0);
+        *mask |= Self::ais_type_mask(types);
+        self
+    }
+
+    /// Reject the given AIS message types (1-63).
+    pub fn exclude_ais_types(&mut self, types: &[u8]) -> &mut Self {
+        self.excluded_ais_types |= Self::ais_type_mask(types);
+        self
+    }
+
+    /// True if sentences of the given type (e.g. "GGA") are decoded.
+    pub fn accepts_sentence_type(&self, sentence_type: &str) -> bool {
+        let included = match &self.included_types {
+            Some(types) => types.contains(sentence_type),
+            None => true,
+        };
+        included && !self.excluded_types.contains(sentence_type)
+    }
+
+    /// True if AIS messages of the given type are decoded.
+    pub fn accepts_ais_type(&self, message_type: u8) -> bool {
+        let bit = Self::ais_type_mask(&[message_type]);
+        let included = match self.included_ais_types {
+            Some(mask) => mask & bit != 0,
+            None => true,
+        };
+        included && self.excluded_ais_types & bit == 0
+    }
+
+    /// True if all AIS message types are accepted, which allows skipping the type peek.
+    pub(crate) fn accepts_all_ais_types(&self) -> bool {
+        self.included_ais_types.is_none() && self.excluded_ais_types == 0
+    }
+
+    fn ais_type_mask(types: &[u8]) -> u64 {
+        types
+            .iter()
+            .filter(|t| **t < 64)
+            .fold(0, |mask, t| mask | (1 << t))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_message_filter() {
+        let all = MessageFilter::new();
+        assert!(all.accepts_sentence_type("GSV"));
+        assert!(all.accepts_ais_type(8));
+        assert!(all.accepts_all_ais_types());
+
+        let mut filter = MessageFilter::new();
+        filter
+            .include_sentence_types(&["GGA", "VDM"])
+            .include_sentence_types(&["RMC"])
+            .exclude_ais_types(&[6, 8])
+            .exclude_ais_types(&[12, 14]);
+        assert!(filter.accepts_sentence_type("GGA"));
+        assert!(filter.accepts_sentence_type("RMC"));
+        assert!(!filter.accepts_sentence_type("GSV"));
+        assert!(filter.accepts_ais_type(1));
+        assert!(!filter.accepts_ais_type(6));
+        assert!(!filter.accepts_ais_type(14));
+        assert!(!filter.accepts_all_ais_types());
+
+        let mut filter = MessageFilter::new();
+        filter
+            .exclude_sentence_types(&["GSV"])
+            .include_ais_types(&[1, 2, 3, 5, 18, 24]);
+        assert!(filter.accepts_sentence_type("GGA"));
+        assert!(!filter.accepts_sentence_type("GSV"));
+        assert!(filter.accepts_ais_type(24));
+        assert!(!filter.accepts_ais_type(4));
+        assert!(!filter.accepts_ais_type(63));
+    }
+}
@@ -19,7 +19,8 @@ use serde::Serialize;
 use super::*;
 
 /// ALM - GPS Almanac Data
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct AlmData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -64,19 +65,56 @@ pub struct AlmData {
     pub af1: Option<u16>,
 }
 
+impl AlmData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> AlmData {
+        AlmData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
-/// xxALM: Global Positioning System Fix Data
+/// xxALM: GPS Almanac Data. The fields are GPS specific, so almanacs of other navigation systems
+/// are rejected instead of being misinterpreted.
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &ParseContext,
 ) -> Result<ParsedMessage, ParseError> {
+    if nav_system != NavigationSystem::Gps && nav_system != NavigationSystem::Combination {
+        return Err(ParseError::UnsupportedSentenceType(format!(
+            "Unsupported almanac navigation system: {}",
+            nav_system
+        )));
+    }
+
     let split: Vec<&str> = sentence.split(',').collect();
 
+    // PRN and week number are decimal, the other fields hexadecimal
+    let prn = pick_number_field::<u8>(&split, 3)?;
+    let week_number = pick_number_field::<u16>(&split, 4)?;
+    if store.settings.strict {
+        if let Some(prn) = prn.filter(|prn| *prn > 32) {
+            return Err(ParseError::InvalidSentence(format!(
+                "ALM PRN out of range 1..=32: {}",
+                prn
+            )));
+        }
+        if let Some(week_number) = week_number.filter(|wk| *wk > 1023) {
+            return Err(ParseError::InvalidSentence(format!(
+                "ALM week number out of range 0..=1023: {}",
+                week_number
+            )));
+        }
+    }
+
     Ok(ParsedMessage::Alm(AlmData {
         source: nav_system,
-        prn: pick_hex_field(&split, 3)?,
-        week_number: { pick_hex_field::<u16>(&split, 4)?.map(|wk| wk & 0x3ff) },
+        prn,
+        week_number: week_number.map(|wk| wk % 1024),
         health_bits: pick_hex_field(&split, 5)?,
         eccentricity: pick_hex_field(&split, 6)?,
         reference_time: pick_hex_field(&split, 7)?,
@@ -106,7 +144,7 @@ mod test {
                 ParsedMessage::Alm(alm) => {
                     assert_eq!(alm.source, NavigationSystem::Gps);
                     assert_eq!(alm.prn, Some(2));
-                    assert_eq!(alm.week_number, Some(535));
+                    assert_eq!(alm.week_number, Some(593));
                     assert_eq!(alm.health_bits, Some(0));
                     assert_eq!(alm.eccentricity, Some(0x50F6));
                     assert_eq!(alm.reference_time, Some(0x0f));
@@ -131,4 +169,74 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_glalm() {
+        // GLONASS almanac fields differ from GPS
+        match NmeaParser::new().parse_sentence(
+            "$GLALM,31,1,02,1617,00,50F6,0F,FD98,FD39,A10CF3,81389B,423632,BD913C,148,001*",
+        ) {
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert!(matches!(
+                    e.inner_error(),
+                    ParseError::UnsupportedSentenceType(_)
+                ));
+                assert!(e
+                    .to_string()
+                    .contains("Unsupported almanac navigation system: GLONASS"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_alm_out_of_range() {
+        let prn_32 = "$GPALM,31,1,32,617,00,50F6,0F,FD98,FD39,A10CF3,81389B,423632,BD913C,148,001*";
+        let prn_33 = "$GPALM,31,1,33,617,00,50F6,0F,FD98,FD39,A10CF3,81389B,423632,BD913C,148,001*";
+        let week_1617 =
+            "$GPALM,31,1,02,1617,00,50F6,0F,FD98,FD39,A10CF3,81389B,423632,BD913C,148,001*";
+
+        // Accepted by default
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(prn_33) {
+            Ok(ParsedMessage::Alm(alm)) => {
+                assert_eq!(alm.prn, Some(33));
+                assert_eq!(alm.week_number, Some(617));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Rejected in strict mode, but PRNs up to 32 are valid
+        p.set_strict(true);
+        match p.parse_sentence(prn_32) {
+            Ok(ParsedMessage::Alm(alm)) => {
+                assert_eq!(alm.prn, Some(32));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        match p.parse_sentence(prn_33) {
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert!(e.to_string().contains("ALM PRN out of range 1..=32: 33"));
+            }
+        }
+        match p.parse_sentence(week_1617) {
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert!(e
+                    .to_string()
+                    .contains("ALM week number out of range 0..=1023: 1617"));
+            }
+        }
+    }
 }
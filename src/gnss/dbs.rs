@@ -17,8 +17,12 @@ limitations under the License.
 use super::*;
 
 /// DBS - Depth Below Surface
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DbsData {
+    /// Navigation system
+    pub source: NavigationSystem,
+
     /// Water depth below surface, meters
     pub depth_meters: Option<f64>,
 
@@ -29,13 +33,32 @@ pub struct DbsData {
     pub depth_fathoms: Option<f64>,
 }
 
+impl DbsData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> DbsData {
+        DbsData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Water depth below surface as `Meters`.
+    pub fn typed_depth_meters(&self) -> Option<Meters> {
+        self.depth_meters.map(Meters)
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxDBS: Depth Below Surface
-pub(crate) fn handle(sentence: &str) -> Result<ParsedMessage, ParseError> {
+pub(crate) fn handle(
+    sentence: &str,
+    nav_system: NavigationSystem,
+) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Dbs(DbsData {
+        source: nav_system,
         depth_meters: pick_number_field(&split, 3)?,
         depth_feet: pick_number_field(&split, 1)?,
         depth_fathoms: pick_number_field(&split, 5)?,
@@ -56,7 +79,8 @@ mod test {
                 ParsedMessage::Dbs(dbs) => {
                     assert_eq!(dbs.depth_meters, Some(5.2));
                     assert_eq!(dbs.depth_feet, Some(16.9));
-                    assert_eq!(dbs.depth_fathoms, Some(2.8))
+                    assert_eq!(dbs.depth_fathoms, Some(2.8));
+                    assert_eq!(dbs.typed_depth_meters(), Some(Meters(5.2)));
                 }
                 ParsedMessage::Incomplete => {
                     assert!(false);
@@ -17,24 +17,71 @@ limitations under the License.
 use super::*;
 
 /// DPT - Depth of Water
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DptData {
+    /// Navigation system
+    pub source: NavigationSystem,
+
     /// Water depth relative to transducer, meters
     pub depth_relative_to_transducer: Option<f64>,
 
     /// Offset from transducer, meters positive means distance from transducer to water line negative means distance from transducer to keel
     pub transducer_offset: Option<f64>,
+
+    /// Maximum range scale in use, meters
+    pub max_range: Option<f64>,
+}
+
+impl DptData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> DptData {
+        DptData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Water depth relative to transducer as `Meters`.
+    pub fn typed_depth_relative_to_transducer(&self) -> Option<Meters> {
+        self.depth_relative_to_transducer.map(Meters)
+    }
+
+    /// Water depth below surface, i.e. depth relative to transducer plus a positive transducer
+    /// offset. `None` is returned if the offset is negative (distance to keel) or missing.
+    pub fn depth_below_surface(&self) -> Option<f64> {
+        let offset = self.transducer_offset.filter(|offset| *offset >= 0.0)?;
+        Some(self.depth_relative_to_transducer? + offset)
+    }
+
+    /// Water depth below keel, i.e. depth relative to transducer minus the distance from the
+    /// transducer to keel given as a negative offset. `None` is returned if the offset is
+    /// positive (distance to water line) or missing.
+    pub fn depth_below_keel(&self) -> Option<f64> {
+        let offset = self.transducer_offset.filter(|offset| *offset <= 0.0)?;
+        Some(self.depth_relative_to_transducer? + offset)
+    }
+
+    /// Offset from transducer as `Meters`.
+    pub fn typed_transducer_offset(&self) -> Option<Meters> {
+        self.transducer_offset.map(Meters)
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
 
 /// xxDPT: Depth of Water
-pub(crate) fn handle(sentence: &str) -> Result<ParsedMessage, ParseError> {
+pub(crate) fn handle(
+    sentence: &str,
+    nav_system: NavigationSystem,
+) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Dpt(DptData {
+        source: nav_system,
         depth_relative_to_transducer: pick_number_field(&split, 1)?,
         transducer_offset: pick_number_field(&split, 2)?,
+        max_range: pick_number_field(&split, 3)?,
     }))
 }
 
@@ -50,8 +97,11 @@ mod test {
         match NmeaParser::new().parse_sentence("$SDDPT,17.5,0.3*67") {
             Ok(ps) => match ps {
                 ParsedMessage::Dpt(dpt) => {
+                    assert_eq!(dpt.source, NavigationSystem::Other);
                     assert_eq!(dpt.depth_relative_to_transducer, Some(17.5));
                     assert_eq!(dpt.transducer_offset, Some(0.3));
+                    assert_eq!(dpt.typed_depth_relative_to_transducer(), Some(Meters(17.5)));
+                    assert_eq!(dpt.typed_transducer_offset(), Some(Meters(0.3)));
                 }
                 ParsedMessage::Incomplete => {
                     assert!(false);
@@ -65,4 +115,57 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_dpt_source() {
+        let mut p = NmeaParser::new();
+        let sd = p.parse_sentence("$SDDPT,17.5,0.3").unwrap();
+        let gp = p.parse_sentence("$GPDPT,17.5,0.3").unwrap();
+        let gn = p.parse_sentence("$GNDPT,17.5,0.3").unwrap();
+        match (sd, gp, gn) {
+            (ParsedMessage::Dpt(sd), ParsedMessage::Dpt(gp), ParsedMessage::Dpt(gn)) => {
+                assert_eq!(sd.source, NavigationSystem::Other);
+                assert_eq!(gp.source, NavigationSystem::Gps);
+                assert_eq!(gn.source, NavigationSystem::Combination);
+                assert_ne!(sd.source, gp.source);
+                assert_eq!(
+                    sd.depth_relative_to_transducer,
+                    gp.depth_relative_to_transducer
+                );
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dpt_offsets() {
+        let mut p = NmeaParser::new();
+
+        // Transducer 1.2 m above keel, with max range
+        match p.parse_sentence("$SDDPT,17.5,-1.2,100.0") {
+            Ok(ParsedMessage::Dpt(dpt)) => {
+                assert_eq!(dpt.transducer_offset, Some(-1.2));
+                assert_eq!(dpt.max_range, Some(100.0));
+                assert_eq!(dpt.depth_below_surface(), None);
+                assert::close(dpt.depth_below_keel().unwrap_or(0.0), 16.3, 0.001);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Transducer 0.3 m below water line, without max range
+        match p.parse_sentence("$SDDPT,17.5,0.3*67") {
+            Ok(ParsedMessage::Dpt(dpt)) => {
+                assert_eq!(dpt.max_range, None);
+                assert::close(dpt.depth_below_surface().unwrap_or(0.0), 17.8, 0.001);
+                assert_eq!(dpt.depth_below_keel(), None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
@@ -17,7 +17,8 @@ limitations under the License.
 use super::*;
 
 /// DTM - Datum being used
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DtmData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -41,6 +42,25 @@ pub struct DtmData {
     pub ref_datum_id: Option<String>,
 }
 
+impl DtmData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> DtmData {
+        DtmData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Apply the latitude and longitude offsets to the given position (in degrees). Missing
+    /// offsets are treated as zero. Returns the shifted position as (latitude, longitude) tuple.
+    pub fn apply_to(&self, lat: f64, lon: f64) -> (f64, f64) {
+        (
+            lat + self.lat_offset.unwrap_or(0.0),
+            lon + self.lon_offset.unwrap_or(0.0),
+        )
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxDTM: Datum being used
@@ -92,4 +112,21 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_dtm_apply_to() {
+        match NmeaParser::new().parse_sentence("$GPDTM,999,,0.6,S,1.2,E,005.8,W84") {
+            Ok(ParsedMessage::Dtm(dtm)) => {
+                let (lat, lon) = dtm.apply_to(60.0, 25.0);
+                assert::close(lat, 59.99, 0.000001);
+                assert::close(lon, 25.02, 0.000001);
+            }
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
 }
@@ -17,14 +17,21 @@ limitations under the License.
 use super::*;
 
 /// GGA - time, position, and fix related data
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct GgaData {
     /// Navigation system
     pub source: NavigationSystem,
 
+    /// AIS station type if the talker is an AIS station (e.g. `$ABGGA` of a base station),
+    /// otherwise `None`
+    #[cfg(feature = "ais")]
+    pub station: Option<ais::Station>,
+
     /// UTC of position fix
-    #[serde(with = "json_date_time_utc")]
-    pub timestamp: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "time", serde(with = "json_date_time_utc"))]
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp: Option<Timestamp>,
 
     /// Latitude in degrees
     pub latitude: Option<f64>,
@@ -41,10 +48,12 @@ pub struct GgaData {
     /// Horizontal dilution of position
     pub hdop: Option<f64>,
 
-    /// Altitude above mean sea level (metres)
+    /// Altitude above mean sea level (metres). Values given in feet (unit `F`) are converted to
+    /// metres.
     pub altitude: Option<f64>,
 
-    /// Height of geoid (mean sea level) above WGS84 ellipsoid
+    /// Height of geoid (mean sea level) above WGS84 ellipsoid (metres). Values given in feet
+    /// (unit `F`) are converted to metres.
     pub geoid_separation: Option<f64>,
 
     /// Age of differential GPS data record, Type 1 or Type 9.
@@ -64,8 +73,72 @@ impl LatLon for GgaData {
     }
 }
 
+/// GGA doesn't carry speed or course; pair it with VTG data for them.
+impl PositionReport for GgaData {
+    fn fix_time_seconds(&self) -> Option<u32> {
+        self.time_of_day_secs()
+    }
+}
+
+impl LatLon for (GgaData, VtgData) {
+    fn latitude(&self) -> Option<f64> {
+        self.0.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.0.longitude
+    }
+}
+
+/// Position and fix time of GGA combined with speed and true course of VTG.
+impl PositionReport for (GgaData, VtgData) {
+    fn sog_knots(&self) -> Option<f64> {
+        self.1.sog_knots
+    }
+
+    fn cog(&self) -> Option<f64> {
+        self.1.cog_true
+    }
+
+    fn fix_time_seconds(&self) -> Option<u32> {
+        self.0.time_of_day_secs()
+    }
+}
+
+impl GgaData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> GgaData {
+        GgaData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Fix quality in the form common with `GnsData::fix_quality()`.
+    pub fn fix_quality(&self) -> FixQuality {
+        self.quality.into()
+    }
+
+    /// Test whether the DGPS correction is older than `max_age_secs` seconds. `None` is returned
+    /// if the age isn't available, e.g. because DGPS isn't used.
+    pub fn dgps_is_stale(&self, max_age_secs: f64) -> Option<bool> {
+        self.age_of_dgps.map(|age| age > max_age_secs)
+    }
+
+    /// Horizontal dilution of precision as `Dop`.
+    pub fn typed_hdop(&self) -> Option<Dop> {
+        self.hdop.map(Dop)
+    }
+
+    /// Seconds since midnight of the fix timestamp.
+    pub fn time_of_day_secs(&self) -> Option<u32> {
+        self.timestamp.as_ref().map(seconds_of_day)
+    }
+}
+
 /// GGA GPS quality indicator
-#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum GgaQualityIndicator {
     Invalid,                // 0
     GpsFix,                 // 1
@@ -78,6 +151,12 @@ pub enum GgaQualityIndicator {
     SimulationMode,         // 8
 }
 
+impl Default for GgaQualityIndicator {
+    fn default() -> GgaQualityIndicator {
+        GgaQualityIndicator::Invalid
+    }
+}
+
 impl GgaQualityIndicator {
     pub fn new(a: u8) -> GgaQualityIndicator {
         match a {
@@ -93,6 +172,21 @@ impl GgaQualityIndicator {
             _ => GgaQualityIndicator::Invalid,
         }
     }
+
+    /// True if there's a position fix of any kind, i.e. the indicator isn't `Invalid`.
+    pub fn has_fix(&self) -> bool {
+        *self != GgaQualityIndicator::Invalid
+    }
+
+    /// True if the fix uses differential corrections (DGPS, RTK or float RTK).
+    pub fn is_differential(&self) -> bool {
+        matches!(
+            self,
+            GgaQualityIndicator::DGpsFix
+                | GgaQualityIndicator::RealTimeKinematic
+                | GgaQualityIndicator::RealTimeKinematicFloat
+        )
+    }
 }
 
 impl core::fmt::Display for GgaQualityIndicator {
@@ -115,17 +209,45 @@ impl core::fmt::Display for GgaQualityIndicator {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Pick a distance field followed by its unit field and convert it to metres. A missing unit is
+/// assumed to be metres. Unknown units are errors, or warnings with `None` value in lenient mode.
+fn pick_metres(
+    split: &[&str],
+    num: usize,
+    store: &mut ParseContext,
+) -> Result<Option<f64>, ParseError> {
+    let value: Option<f64> = pick_number_field(split, num)?;
+    match split.get(num + 1).copied().unwrap_or("") {
+        "M" | "" => Ok(value),
+        "F" => Ok(value.map(|feet| feet * 0.3048)),
+        unit => {
+            if store.settings.lenient {
+                warn!("Unknown unit of GGA field {}: {}", num, unit);
+                store.add_warning(ParseWarning::UnknownUnit(unit.into()));
+                Ok(None)
+            } else {
+                Err(ParseError::InvalidSentence(format!(
+                    "Unknown unit of GGA field {}: {}",
+                    num, unit
+                )))
+            }
+        }
+    }
+}
+
 /// xxGGA: Global Positioning System Fix Data
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut ParseContext,
 ) -> Result<ParsedMessage, ParseError> {
-    let now: DateTime<Utc> = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).single().unwrap();
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Gga(GgaData {
         source: nav_system,
-        timestamp: parse_hhmmss(split.get(1).unwrap_or(&""), now).ok(),
+        #[cfg(feature = "ais")]
+        station: None,
+        timestamp: parse_time_of_day(split.get(1).unwrap_or(&"")).ok(),
         latitude: parse_latitude_ddmm_mmm(
             split.get(2).unwrap_or(&""),
             split.get(3).unwrap_or(&""),
@@ -137,8 +259,8 @@ pub(crate) fn handle(
         quality: GgaQualityIndicator::new(pick_number_field(&split, 6)?.unwrap_or(0)),
         satellite_count: pick_number_field(&split, 7)?,
         hdop: pick_number_field(&split, 8)?,
-        altitude: pick_number_field(&split, 9)?,
-        geoid_separation: pick_number_field(&split, 11)?,
+        altitude: pick_metres(&split, 9, store)?,
+        geoid_separation: pick_metres(&split, 11, store)?,
         age_of_dgps: pick_number_field(&split, 13)?,
         ref_station_id: pick_number_field(&split, 14)?,
     }))
@@ -166,8 +288,14 @@ mod test {
                         assert::close(gga.latitude.unwrap_or(0.0), 48.117, 0.001);
                         assert::close(gga.longitude.unwrap_or(0.0), 11.517, 0.001);
                         assert_eq!(gga.quality, GgaQualityIndicator::GpsFix);
+                        assert_eq!(gga.fix_quality(), FixQuality::Autonomous);
+                        assert_eq!(gga.time_of_day_secs(), Some(45319));
                         assert_eq!(gga.satellite_count.unwrap_or(0), 8);
                         assert::close(gga.hdop.unwrap_or(0.0), 0.9, 0.1);
+                        assert_eq!(
+                            gga.typed_hdop().map(|d| d.classification()),
+                            Some(DopClass::Ideal)
+                        );
                         assert::close(gga.altitude.unwrap_or(0.0), 545.4, 0.1);
                         assert::close(gga.geoid_separation.unwrap_or(0.0), 46.9, 0.1);
                         assert_eq!(gga.age_of_dgps, None);
@@ -248,4 +376,102 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_gga_quality_indicator_helpers() {
+        assert!(!GgaQualityIndicator::Invalid.has_fix());
+        assert!(!GgaQualityIndicator::Invalid.is_differential());
+        assert!(GgaQualityIndicator::GpsFix.has_fix());
+        assert!(!GgaQualityIndicator::GpsFix.is_differential());
+        assert!(GgaQualityIndicator::RealTimeKinematic.has_fix());
+        assert!(GgaQualityIndicator::RealTimeKinematic.is_differential());
+        assert!(GgaQualityIndicator::DGpsFix.is_differential());
+        assert!(!GgaQualityIndicator::DeadReckoning.is_differential());
+    }
+
+    #[test]
+    fn test_gga_dgps_is_stale() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "$GPGGA,123519,4807.038,N,01131.000,E,2,08,0.9,545.4,M,46.9,M,45.0,0120",
+        ) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.age_of_dgps, Some(45.0));
+                assert_eq!(gga.dgps_is_stale(30.0), Some(true));
+                assert_eq!(gga.dgps_is_stale(60.0), Some(false));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.dgps_is_stale(30.0), None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_gga_altitude_units() {
+        let mut p = NmeaParser::new();
+
+        // Negative altitude and geoid separation
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,-12.3,M,-46.9,M,,*77")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.altitude, Some(-12.3));
+                assert_eq!(gga.geoid_separation, Some(-46.9));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Altitude in feet
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,100.0,F,46.9,M,,*4D")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert::close(gga.altitude.unwrap_or(0.0), 30.48, 0.001);
+                assert_eq!(gga.geoid_separation, Some(46.9));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Missing unit fields
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,,46.9,,,*47") {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.altitude, Some(545.4));
+                assert_eq!(gga.geoid_separation, Some(46.9));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Unknown unit
+        assert!(p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,X,46.9,M,,*52")
+            .is_err());
+        p.set_lenient(true);
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,X,46.9,M,,*52")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.altitude, None);
+                assert_eq!(gga.geoid_separation, Some(46.9));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(
+            p.take_warnings(),
+            vec![ParseWarning::UnknownUnit("X".into())]
+        );
+    }
 }
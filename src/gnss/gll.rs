@@ -16,11 +16,17 @@ limitations under the License.
 use super::*;
 
 /// GLL - geographic Position - Latitude/Longitude
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct GllData {
     /// Navigation system
     pub source: NavigationSystem,
 
+    /// AIS station type if the talker is an AIS station (e.g. `$ABGGA` of a base station),
+    /// otherwise `None`
+    #[cfg(feature = "ais")]
+    pub station: Option<ais::Station>,
+
     /// Latitude in degrees.
     pub latitude: Option<f64>,
 
@@ -28,8 +34,9 @@ pub struct GllData {
     pub longitude: Option<f64>,
 
     /// UTC of position fix
-    #[serde(with = "json_date_time_utc")]
-    pub timestamp: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "time", serde(with = "json_date_time_utc"))]
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp: Option<Timestamp>,
 
     /// True = data valid, false = data invalid.
     pub data_valid: Option<bool>,
@@ -38,6 +45,16 @@ pub struct GllData {
     pub faa_mode: Option<FaaMode>,
 }
 
+impl GllData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> GllData {
+        GllData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 impl LatLon for GllData {
     fn latitude(&self) -> Option<f64> {
         self.latitude
@@ -48,6 +65,12 @@ impl LatLon for GllData {
     }
 }
 
+impl PositionReport for GllData {
+    fn fix_time_seconds(&self) -> Option<u32> {
+        self.timestamp.as_ref().map(seconds_of_day)
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxGLL: Geographic Position, Latitude / Longitude and time.
@@ -55,11 +78,12 @@ pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
 ) -> Result<ParsedMessage, ParseError> {
-    let now: DateTime<Utc> = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).single().unwrap();
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Gll(GllData {
         source: nav_system,
+        #[cfg(feature = "ais")]
+        station: None,
         latitude: parse_latitude_ddmm_mmm(
             split.get(1).unwrap_or(&""),
             split.get(2).unwrap_or(&""),
@@ -68,7 +92,7 @@ pub(crate) fn handle(
             split.get(3).unwrap_or(&""),
             split.get(4).unwrap_or(&""),
         )?,
-        timestamp: parse_hhmmss(split.get(5).unwrap_or(&""), now).ok(),
+        timestamp: parse_time_of_day(split.get(5).unwrap_or(&"")).ok(),
         data_valid: {
             match *split.get(6).unwrap_or(&"") {
                 "A" => Some(true),
@@ -17,13 +17,20 @@ limitations under the License.
 use super::*;
 
 /// GNS - GNSS fix data
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct GnsData {
     /// Navigation system
     pub source: NavigationSystem,
 
+    /// AIS station type if the talker is an AIS station (e.g. `$ABGGA` of a base station),
+    /// otherwise `None`
+    #[cfg(feature = "ais")]
+    pub station: Option<ais::Station>,
+
     /// UTC of position fix
-    pub timestamp: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp: Option<Timestamp>,
 
     /// Latitude in degrees
     pub latitude: Option<f64>,
@@ -49,7 +56,7 @@ pub struct GnsData {
     /// Altitude above mean sea level (metres)
     pub altitude: Option<f64>,
 
-    /// Height of geoid (mean sea level) above WGS84 ellipsoid
+    /// Height of geoid (mean sea level) above WGS84 ellipsoid (metres)
     pub geoid_separation: Option<f64>,
 
     /// Age of differential GPS data record, Type 1 or Type 9.
@@ -69,8 +76,40 @@ impl LatLon for GnsData {
     }
 }
 
+impl GnsData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> GnsData {
+        GnsData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Horizontal dilution of precision as `Dop`.
+    pub fn typed_hdop(&self) -> Option<Dop> {
+        self.hdop.map(Dop)
+    }
+
+    /// Best fix quality across all the navigation systems.
+    pub fn fix_quality(&self) -> FixQuality {
+        core::iter::once(&self.gps_mode)
+            .chain(core::iter::once(&self.glonass_mode))
+            .chain(self.other_modes.iter())
+            .map(|m| FixQuality::from(*m))
+            .max()
+            .unwrap_or(FixQuality::NoFix)
+    }
+
+    /// Test whether the DGPS correction is older than `max_age_secs` seconds. `None` is returned
+    /// if the age isn't available, e.g. because DGPS isn't used.
+    pub fn dgps_is_stale(&self, max_age_secs: f64) -> Option<bool> {
+        self.age_of_dgps.map(|age| age > max_age_secs)
+    }
+}
+
 /// GNS mode indicator
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum GnsModeIndicator {
     /// Satellite system not used in position fix, or fix not valid
     Invalid,
@@ -96,6 +135,12 @@ pub enum GnsModeIndicator {
     SimulationMode,
 }
 
+impl Default for GnsModeIndicator {
+    fn default() -> GnsModeIndicator {
+        GnsModeIndicator::Invalid
+    }
+}
+
 impl GnsModeIndicator {
     pub fn new(a: char) -> GnsModeIndicator {
         match a {
@@ -138,13 +183,14 @@ pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
 ) -> Result<ParsedMessage, ParseError> {
-    let now: DateTime<Utc> = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).single().unwrap();
     let split: Vec<&str> = sentence.split(',').collect();
     let modes: Vec<char> = split.get(6).unwrap_or(&"").chars().collect();
 
     Ok(ParsedMessage::Gns(GnsData {
         source: nav_system,
-        timestamp: parse_hhmmss(split.get(1).unwrap_or(&""), now).ok(),
+        #[cfg(feature = "ais")]
+        station: None,
+        timestamp: parse_time_of_day(split.get(1).unwrap_or(&"")).ok(),
         latitude: parse_latitude_ddmm_mmm(
             split.get(2).unwrap_or(&""),
             split.get(3).unwrap_or(&""),
@@ -194,6 +240,7 @@ mod test {
                         assert_eq!(gns.gps_mode, GnsModeIndicator::Autonomous);
                         assert_eq!(gns.glonass_mode, GnsModeIndicator::Autonomous);
                         assert_eq!(gns.other_modes[0], GnsModeIndicator::Invalid);
+                        assert_eq!(gns.fix_quality(), FixQuality::Autonomous);
                         assert_eq!(gns.satellite_count.unwrap_or(0), 10);
                         assert::close(gns.hdop.unwrap_or(0.0), 0.9, 0.1);
                         assert::close(gns.altitude.unwrap_or(0.0), 532.4, 0.1);
@@ -229,6 +276,7 @@ mod test {
                         assert_eq!(gns.gps_mode, GnsModeIndicator::Invalid);
                         assert_eq!(gns.glonass_mode, GnsModeIndicator::Invalid);
                         assert!(gns.other_modes.is_empty());
+                        assert_eq!(gns.fix_quality(), FixQuality::NoFix);
                         assert_eq!(gns.satellite_count, None);
                         assert_eq!(gns.hdop, None);
                         assert_eq!(gns.altitude, None);
@@ -249,4 +297,42 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_gns_fix_quality_best_of_modes() {
+        let mut gns = GnsData::default();
+        assert_eq!(gns.fix_quality(), FixQuality::NoFix);
+
+        gns.gps_mode = GnsModeIndicator::Autonomous;
+        gns.glonass_mode = GnsModeIndicator::Differential;
+        assert_eq!(gns.fix_quality(), FixQuality::Differential);
+
+        gns.other_modes = vec![
+            GnsModeIndicator::Invalid,
+            GnsModeIndicator::RealTimeKinematic,
+        ];
+        assert_eq!(gns.fix_quality(), FixQuality::Rtk);
+
+        gns.gps_mode = GnsModeIndicator::SimulationMode;
+        gns.glonass_mode = GnsModeIndicator::Invalid;
+        gns.other_modes = vec![GnsModeIndicator::DeadReckoning];
+        assert_eq!(gns.fix_quality(), FixQuality::DeadReckoning);
+    }
+
+    #[test]
+    fn test_gns_dgps_is_stale() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "$GNGNS,014035.00,4332.69262,S,17235.48549,E,DD,13,0.9,25.63,11.24,45,0120",
+        ) {
+            Ok(ParsedMessage::Gns(gns)) => {
+                assert_eq!(gns.age_of_dgps, Some(45.0));
+                assert_eq!(gns.dgps_is_stale(30.0), Some(true));
+                assert_eq!(gns.dgps_is_stale(45.0), Some(false));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
@@ -15,7 +15,8 @@ limitations under the License.
 */
 use super::*;
 /// GSA - GNSS dilution of position (DOP) and active satellites
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct GsaData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -37,10 +38,63 @@ pub struct GsaData {
 
     /// Vertical dilution of precision
     pub vdop: Option<f64>,
+
+    /// GNSS system ID (NMEA 4.1): 1 = GPS, 2 = GLONASS, 3 = Galileo, 4 = BeiDou, 5 = QZSS,
+    /// 6 = NavIC. Combined receivers emit one `$GNGSA` per system, and this field tells which
+    /// system the PRN numbers belong to.
+    pub system_id: Option<u8>,
+}
+
+impl GsaData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> GsaData {
+        GsaData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Position (3D) dilution of precision as `Dop`.
+    pub fn typed_pdop(&self) -> Option<Dop> {
+        self.pdop.map(Dop)
+    }
+
+    /// Horizontal dilution of precision as `Dop`.
+    pub fn typed_hdop(&self) -> Option<Dop> {
+        self.hdop.map(Dop)
+    }
+
+    /// Vertical dilution of precision as `Dop`.
+    pub fn typed_vdop(&self) -> Option<Dop> {
+        self.vdop.map(Dop)
+    }
+
+    /// Navigation system of the PRN numbers: the system identified by `system_id` if it's
+    /// given, otherwise `source`.
+    pub fn system(&self) -> NavigationSystem {
+        self.system_id
+            .and_then(NavigationSystem::from_system_id)
+            .unwrap_or(self.source)
+    }
+}
+
+/// Group GSA data by `system_id`, e.g. the consecutive `$GNGSA` sentences of a combined receiver.
+/// The groups are in the order their first sentence appears, and data without the system ID
+/// forms a group of its own.
+pub fn group_gsa_by_system_id(data: &[GsaData]) -> Vec<(Option<u8>, Vec<&GsaData>)> {
+    let mut groups: Vec<(Option<u8>, Vec<&GsaData>)> = Vec::new();
+    for gsa in data {
+        match groups.iter_mut().find(|(id, _)| *id == gsa.system_id) {
+            Some((_, group)) => group.push(gsa),
+            None => groups.push((gsa.system_id, vec![gsa])),
+        }
+    }
+    groups
 }
 
 /// GSA position fix type
-#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum GsaFixMode {
     /// No fix.
     NotAvailable,
@@ -110,6 +164,7 @@ pub(crate) fn handle(
         pdop: pick_number_field(&split, 15)?,
         hdop: pick_number_field(&split, 16)?,
         vdop: pick_number_field(&split, 17)?,
+        system_id: pick_number_field(&split, 18)?,
     }))
 }
 
@@ -133,6 +188,12 @@ mod test {
                         assert_eq!(gsa.pdop, Some(1.7));
                         assert_eq!(gsa.hdop, Some(1.0));
                         assert_eq!(gsa.vdop, Some(1.3));
+                        assert_eq!(gsa.typed_pdop(), Some(Dop(1.7)));
+                        assert_eq!(
+                            gsa.typed_hdop().map(|d| d.classification()),
+                            Some(DopClass::Excellent)
+                        );
+                        assert_eq!(gsa.typed_vdop().map(|d| d.to_string()), Some("1.3".into()));
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
@@ -147,4 +208,87 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_gngsa_system_id() {
+        let mut p = NmeaParser::new();
+        let mut data = Vec::new();
+        for sentence in [
+            "$GNGSA,A,3,05,07,13,15,,,,,,,,,1.4,0.8,1.1,1*38",
+            "$GNGSA,A,3,70,71,80,,,,,,,,,,1.4,0.8,1.1,2*36",
+            "$GNGSA,A,3,08,09,,,,,,,,,,,1.4,0.8,1.1,1*3D",
+        ]
+        .iter()
+        {
+            match p.parse_sentence(sentence) {
+                Ok(ParsedMessage::Gsa(gsa)) => data.push(gsa),
+                Ok(_) => assert!(false),
+                Err(e) => assert_eq!(e.to_string(), "OK"),
+            }
+        }
+
+        assert_eq!(data[0].source, NavigationSystem::Combination);
+        assert_eq!(data[0].system_id, Some(1));
+        assert_eq!(data[0].system(), NavigationSystem::Gps);
+        assert_eq!(data[1].system_id, Some(2));
+        assert_eq!(data[1].system(), NavigationSystem::Glonass);
+        assert_eq!(data[1].prn_numbers, vec![70, 71, 80]);
+
+        let groups = group_gsa_by_system_id(&data);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, Some(1));
+        assert_eq!(
+            groups[0]
+                .1
+                .iter()
+                .flat_map(|gsa| gsa.prn_numbers.iter().copied())
+                .collect::<Vec<u8>>(),
+            vec![5, 7, 13, 15, 8, 9]
+        );
+        assert_eq!(groups[1].0, Some(2));
+        assert_eq!(groups[1].1.len(), 1);
+
+        // Older sentences without the system ID
+        match p.parse_sentence("$GPGSA,A,3,19,28,14,18,27,22,31,39,,,,,1.7,1.0,1.3*34") {
+            Ok(ParsedMessage::Gsa(gsa)) => {
+                assert_eq!(gsa.system_id, None);
+                assert_eq!(gsa.system(), NavigationSystem::Gps);
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_gngsa_satellite_summary() {
+        // GPS, Galileo and BeiDou share PRN numbers 5, 7 and 13
+        let mut p = NmeaParser::new();
+        let mut builder = SatelliteSummary::builder();
+        for sentence in [
+            "$GNGSA,A,3,05,07,13,,,,,,,,,,1.2,0.7,1.0,1*34",
+            "$GNGSA,A,3,05,07,26,30,,,,,,,,,1.2,0.7,1.0,3*33",
+            "$GNGSA,A,3,07,13,,,,,,,,,,,1.2,0.7,1.0,4*34",
+        ]
+        .iter()
+        {
+            match p.parse_sentence(sentence) {
+                Ok(ParsedMessage::Gsa(gsa)) => {
+                    builder.add_gsa(&gsa);
+                }
+                _ => assert!(false),
+            }
+        }
+        let summary = builder.build();
+
+        let count = |source| {
+            summary
+                .satellites
+                .iter()
+                .filter(|s| s.source == source && s.used)
+                .count()
+        };
+        assert_eq!(summary.used_count, 9);
+        assert_eq!(count(NavigationSystem::Gps), 3);
+        assert_eq!(count(NavigationSystem::Galileo), 4);
+        assert_eq!(count(NavigationSystem::Beidou), 2);
+    }
 }
@@ -16,7 +16,8 @@ limitations under the License.
 use super::*;
 
 /// GSV - satellite information
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct GsvData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -32,6 +33,21 @@ pub struct GsvData {
 
     /// SNR, 0-99 dB, None when not tracking
     pub snr: Option<f32>,
+
+    /// GNSS signal ID (NMEA 4.10 and later), e.g. 1 = L1 C/A for GPS or B1I for BeiDou. The
+    /// same satellite is reported once per signal, so a PRN may appear more than once with
+    /// different signal IDs.
+    pub signal_id: Option<u8>,
+}
+
+impl GsvData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> GsvData {
+        GsvData {
+            source,
+            ..Default::default()
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -40,18 +56,23 @@ pub struct GsvData {
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
-    store: &mut NmeaParser,
+    store: &mut ParseContext,
 ) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
+    // Groups of different signals are sent concurrently, so the signal ID is part of the key
     let msg_type = split.first().unwrap_or(&"");
     let msg_count = pick_number_field(&split, 1)?.unwrap_or(0);
     let msg_num = pick_number_field(&split, 2)?.unwrap_or(0);
-    store.push_string(make_gsv_key(msg_type, msg_count, msg_num), sentence.into());
+    let signal_id = pick_signal_id(&split)?;
+    store.push_string(
+        make_gsv_key(msg_type, signal_id, msg_count, msg_num),
+        sentence.into(),
+    );
 
     let mut found_count = 0;
     for i in 1..(msg_count + 1) {
-        if store.contains_key(make_gsv_key(msg_type, msg_count, i)) {
+        if store.contains_key(make_gsv_key(msg_type, signal_id, msg_count, i)) {
             found_count += 1;
         }
     }
@@ -59,26 +80,23 @@ pub(crate) fn handle(
     if found_count == msg_count {
         let mut v = Vec::new();
         for i in 1..(msg_count + 1) {
-            if let Some(sentence) = store.pull_string(make_gsv_key(msg_type, msg_count, i)) {
+            if let Some(sentence) =
+                store.pull_string(make_gsv_key(msg_type, signal_id, msg_count, i))
+            {
                 let split: Vec<&str> = sentence.split(',').collect();
+                let blocks_end = if signal_id.is_some() {
+                    split.len() - 1
+                } else {
+                    split.len()
+                };
                 for j in 0..4 {
-                    if let Some(prn) = pick_number_field(&split, 4 + 4 * j as usize)
-                        .ok()
-                        .unwrap_or(None)
-                    {
-                        v.push(GsvData {
-                            source: nav_system,
-                            prn_number: prn,
-                            elevation: pick_number_field(&split, 4 + 4 * j as usize + 1)
-                                .ok()
-                                .unwrap_or(None),
-                            azimuth: pick_number_field(&split, 4 + 4 * j as usize + 2)
-                                .ok()
-                                .unwrap_or(None),
-                            snr: pick_number_field(&split, 4 + 4 * j as usize + 3)
-                                .ok()
-                                .unwrap_or(None),
-                        });
+                    let base = 4 + 4 * j;
+                    if base + 4 > blocks_end {
+                        break;
+                    }
+                    if let Some(mut gsv) = parse_satellite_block(&split, base, nav_system) {
+                        gsv.signal_id = signal_id;
+                        v.push(gsv);
                     }
                 }
             }
@@ -90,9 +108,48 @@ pub(crate) fn handle(
     }
 }
 
+/// Parse the four-field satellite block starting at field index `base`. `None` is returned if
+/// the PRN number field is empty or invalid.
+pub(crate) fn parse_satellite_block(
+    split: &[&str],
+    base: usize,
+    source: NavigationSystem,
+) -> Option<GsvData> {
+    let prn_number = pick_number_field(split, base).ok().flatten()?;
+    Some(GsvData {
+        source,
+        prn_number,
+        elevation: pick_number_field(split, base + 1).ok().flatten(),
+        azimuth: pick_number_field(split, base + 2).ok().flatten(),
+        snr: pick_number_field(split, base + 3).ok().flatten(),
+        signal_id: None,
+    })
+}
+
+/// Pick the signal ID field of NMEA 4.10, which follows the satellite blocks. Sentences without
+/// it consist of the header and whole four-field blocks.
+fn pick_signal_id(split: &[&str]) -> Result<Option<u8>, ParseError> {
+    if split.len() > 4 && (split.len() - 4) % 4 == 1 {
+        Ok(pick_hex_field(split, split.len() - 1)?)
+    } else {
+        Ok(None)
+    }
+}
+
 /// Make key for store
-fn make_gsv_key(sentence_type: &str, msg_count: u32, msg_num: u32) -> String {
-    format!("{},{},{}", sentence_type, msg_count, msg_num)
+fn make_gsv_key(
+    sentence_type: &str,
+    signal_id: Option<u8>,
+    msg_count: u32,
+    msg_num: u32,
+) -> String {
+    match signal_id {
+        Some(signal_id) => format!(
+            "{},{:X},{},{}",
+            sentence_type, signal_id, msg_count, msg_num
+        ),
+        None => format!("{},{},{}", sentence_type, msg_count, msg_num),
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -163,6 +220,7 @@ mod test {
                         assert_eq!(s11.elevation, Some(5.0));
                         assert_eq!(s11.azimuth, Some(244.0));
                         assert_eq!(s11.snr, Some(0.0));
+                        assert_eq!(s11.signal_id, None);
                     }
                     _ => {
                         assert_eq!(p.strings_count(), 3);
@@ -176,4 +234,95 @@ mod test {
         }
         assert_eq!(p.strings_count(), 0);
     }
+
+    #[test]
+    fn test_parse_satellite_block() {
+        let split: Vec<&str> = "$GPGSV,3,3,11,22,42,067,42,24,14,311,43,27,05,244,,,,,"
+            .split(',')
+            .collect();
+
+        // Full block
+        let s = parse_satellite_block(&split, 4, NavigationSystem::Gps).unwrap();
+        assert_eq!(s.source, NavigationSystem::Gps);
+        assert_eq!(s.prn_number, 22);
+        assert_eq!(s.elevation, Some(42.0));
+        assert_eq!(s.azimuth, Some(67.0));
+        assert_eq!(s.snr, Some(42.0));
+
+        // Last full block
+        let s = parse_satellite_block(&split, 8, NavigationSystem::Gps).unwrap();
+        assert_eq!(s.prn_number, 24);
+        assert_eq!(s.snr, Some(43.0));
+
+        // Block with empty SNR (not tracking)
+        let s = parse_satellite_block(&split, 12, NavigationSystem::Gps).unwrap();
+        assert_eq!(s.prn_number, 27);
+        assert_eq!(s.elevation, Some(5.0));
+        assert_eq!(s.azimuth, Some(244.0));
+        assert_eq!(s.snr, None);
+
+        // Empty block and block beyond the end of the sentence
+        assert_eq!(
+            parse_satellite_block(&split, 16, NavigationSystem::Gps),
+            None
+        );
+        assert_eq!(
+            parse_satellite_block(&split, 20, NavigationSystem::Gps),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_gsv_signal_ids() {
+        let mut p = NmeaParser::new();
+
+        // Interleaved groups of BeiDou signals B1I (1) and B2a (B) sharing the satellites
+        let sentences = [
+            "$BDGSV,2,1,05,01,45,120,40,02,30,200,38,03,60,080,42,04,20,300,35,1*74",
+            "$BDGSV,2,1,05,01,45,120,44,02,30,200,41,03,60,080,45,04,20,300,39,B*06",
+            "$BDGSV,2,2,05,05,10,050,30,1*42",
+            "$BDGSV,2,2,05,05,10,050,33,B*32",
+        ];
+        assert_eq!(
+            p.parse_sentence(sentences[0]),
+            Ok(ParsedMessage::Incomplete)
+        );
+        assert_eq!(
+            p.parse_sentence(sentences[1]),
+            Ok(ParsedMessage::Incomplete)
+        );
+        assert_eq!(p.strings_count(), 2);
+
+        let b1i = match p.parse_sentence(sentences[2]) {
+            Ok(ParsedMessage::Gsv(v)) => v,
+            _ => panic!("GSV expected"),
+        };
+        let b2a = match p.parse_sentence(sentences[3]) {
+            Ok(ParsedMessage::Gsv(v)) => v,
+            _ => panic!("GSV expected"),
+        };
+        assert_eq!(p.strings_count(), 0);
+
+        assert_eq!(b1i.len(), 5);
+        assert_eq!(b2a.len(), 5);
+        assert!(b1i.iter().all(|s| s.signal_id == Some(1)));
+        assert!(b2a.iter().all(|s| s.signal_id == Some(0xB)));
+        assert!(b1i.iter().all(|s| s.source == NavigationSystem::Beidou));
+        assert_eq!(
+            b1i.iter().map(|s| s.prn_number).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            b2a.iter().map(|s| s.prn_number).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(b1i[0].snr, Some(40.0));
+        assert_eq!(b2a[0].snr, Some(44.0));
+        assert_eq!(b1i[4].snr, Some(30.0));
+        assert_eq!(b2a[4].snr, Some(33.0));
+
+        // Both signals of the same satellite are kept when concatenated
+        let all: Vec<GsvData> = b1i.iter().chain(b2a.iter()).cloned().collect();
+        assert_eq!(all.iter().filter(|s| s.prn_number == 1).count(), 2);
+    }
 }
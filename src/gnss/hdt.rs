@@ -17,19 +17,37 @@ limitations under the License.
 use super::*;
 
 /// HDT - Heading, true
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct HdtData {
+    /// Navigation system
+    pub source: NavigationSystem,
+
     /// Heading - true
     pub heading_true: Option<f64>,
 }
 
+impl HdtData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> HdtData {
+        HdtData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxHDT: Heading, true
-pub(crate) fn handle(sentence: &str) -> Result<ParsedMessage, ParseError> {
+pub(crate) fn handle(
+    sentence: &str,
+    nav_system: NavigationSystem,
+) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Hdt(HdtData {
+        source: nav_system,
         heading_true: pick_number_field(&split, 1)?,
     }))
 }
@@ -0,0 +1,449 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Own position fix stored in `FixHistory`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixRecord {
+    /// Time of the fix
+    pub timestamp: DateTime<Utc>,
+
+    /// Latitude in degrees
+    pub latitude: f64,
+
+    /// Longitude in degrees
+    pub longitude: f64,
+
+    /// Speed over ground in knots (RMC only)
+    pub sog_knots: Option<f64>,
+
+    /// Course over ground in degrees (RMC only)
+    pub cog: Option<f64>,
+}
+
+/// Position estimated by `FixHistory::at()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterpolatedFix {
+    /// The requested time
+    pub timestamp: DateTime<Utc>,
+
+    /// Latitude in degrees
+    pub latitude: f64,
+
+    /// Longitude in degrees
+    pub longitude: f64,
+
+    /// Speed over ground in knots if known at both ends of the interval
+    pub sog_knots: Option<f64>,
+
+    /// Course over ground in degrees if known at both ends of the interval
+    pub cog: Option<f64>,
+
+    /// True if the time is after the latest fix and the position was extrapolated
+    pub extrapolated: bool,
+}
+
+/// Fixed-capacity history of recent own position fixes (requires `time` feature), e.g. for
+/// filling gaps by dead reckoning. GGA, RMC, GLL and GNS messages are accepted; the ones
+/// without a valid position are ignored. The fixes are kept in time order even if they arrive
+/// out of order, and the oldest ones are dropped when the capacity is reached. Fixes of the same
+/// time, e.g. GGA and RMC of the same epoch, are combined.
+///
+/// GGA, GLL and GNS carry the time of day only. Their date is taken from the fix closest in
+/// time, so that a fix at 00:00:01 following one at 23:59:59 is placed on the next day. Until
+/// the first RMC provides a date, such fixes keep the parser's placeholder date and they are
+/// moved to the RMC's date when it arrives.
+#[derive(Clone, Debug)]
+pub struct FixHistory {
+    fixes: VecDeque<FixRecord>,
+    capacity: usize,
+    max_gap_ms: i64,
+    dated: bool,
+}
+
+impl FixHistory {
+    /// Construct an empty history of at most `capacity` fixes. Positions aren't interpolated
+    /// between fixes more than `max_gap_secs` seconds apart or extrapolated further than that
+    /// from the latest fix.
+    pub fn new(capacity: usize, max_gap_secs: f64) -> FixHistory {
+        FixHistory {
+            fixes: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            max_gap_ms: (max_gap_secs * 1000.0) as i64,
+            dated: false,
+        }
+    }
+
+    /// Add the fix of the given message. Return true if the fix was stored, false if the message
+    /// isn't a valid position fix or is older than the whole full history.
+    pub fn push(&mut self, msg: &ParsedMessage) -> bool {
+        let (timestamp, latitude, longitude, sog_knots, cog, dated) = match msg {
+            ParsedMessage::Rmc(rmc) if rmc.status_active != Some(false) => (
+                rmc.timestamp,
+                rmc.latitude,
+                rmc.longitude,
+                rmc.sog_knots,
+                rmc.bearing,
+                true,
+            ),
+            ParsedMessage::Gga(gga) if gga.fix_quality() != FixQuality::NoFix => (
+                gga.timestamp,
+                gga.latitude,
+                gga.longitude,
+                None,
+                None,
+                false,
+            ),
+            ParsedMessage::Gll(gll) if gll.data_valid != Some(false) => (
+                gll.timestamp,
+                gll.latitude,
+                gll.longitude,
+                None,
+                None,
+                false,
+            ),
+            ParsedMessage::Gns(gns) if gns.fix_quality() != FixQuality::NoFix => (
+                gns.timestamp,
+                gns.latitude,
+                gns.longitude,
+                None,
+                None,
+                false,
+            ),
+            _ => return false,
+        };
+        let (timestamp, latitude, longitude) = match (timestamp, latitude, longitude) {
+            (Some(t), Some(lat), Some(lon)) => (t, lat, lon),
+            _ => return false,
+        };
+
+        let timestamp = if dated {
+            if !self.dated {
+                // Move the fixes received before the first date next to it
+                for fix in self.fixes.iter_mut() {
+                    fix.timestamp = closest_day(fix.timestamp, timestamp);
+                }
+                self.fixes
+                    .make_contiguous()
+                    .sort_by_key(|fix| fix.timestamp);
+                self.dated = true;
+            }
+            timestamp
+        } else {
+            match self.fixes.back() {
+                Some(latest) => closest_day(timestamp, latest.timestamp),
+                None => timestamp,
+            }
+        };
+
+        let pos = self.fixes.partition_point(|fix| fix.timestamp < timestamp);
+        if let Some(fix) = self.fixes.get_mut(pos) {
+            if fix.timestamp == timestamp {
+                fix.latitude = latitude;
+                fix.longitude = longitude;
+                fix.sog_knots = sog_knots.or(fix.sog_knots);
+                fix.cog = cog.or(fix.cog);
+                return true;
+            }
+        }
+        if self.fixes.len() == self.capacity {
+            if pos == 0 {
+                return false;
+            }
+            self.fixes.pop_front();
+            self.fixes.insert(
+                pos - 1,
+                FixRecord {
+                    timestamp,
+                    latitude,
+                    longitude,
+                    sog_knots,
+                    cog,
+                },
+            );
+        } else {
+            self.fixes.insert(
+                pos,
+                FixRecord {
+                    timestamp,
+                    latitude,
+                    longitude,
+                    sog_knots,
+                    cog,
+                },
+            );
+        }
+        true
+    }
+
+    /// Return the latest fix.
+    pub fn latest(&self) -> Option<&FixRecord> {
+        self.fixes.back()
+    }
+
+    /// Iterate over the fixes from the oldest to the latest.
+    pub fn iter(&self) -> impl Iterator<Item = &FixRecord> {
+        self.fixes.iter()
+    }
+
+    /// Number of fixes in the history.
+    pub fn len(&self) -> usize {
+        self.fixes.len()
+    }
+
+    /// True if the history is empty.
+    pub fn is_empty(&self) -> bool {
+        self.fixes.is_empty()
+    }
+
+    /// Estimate the position at the given time. Between two fixes the position is interpolated
+    /// linearly. After the latest fix it's extrapolated from the movement between the two latest
+    /// fixes. `None` is returned for times before the oldest fix and when the fixes around the
+    /// time, or the latest fix and the time, are more than the maximum gap apart.
+    pub fn at(&self, timestamp: DateTime<Utc>) -> Option<InterpolatedFix> {
+        let pos = self.fixes.partition_point(|fix| fix.timestamp < timestamp);
+        if let Some(fix) = self.fixes.get(pos) {
+            if fix.timestamp == timestamp {
+                return Some(InterpolatedFix {
+                    timestamp,
+                    latitude: fix.latitude,
+                    longitude: fix.longitude,
+                    sog_knots: fix.sog_knots,
+                    cog: fix.cog,
+                    extrapolated: false,
+                });
+            }
+            if pos == 0 {
+                return None;
+            }
+            let a = &self.fixes[pos - 1];
+            if millis_between(a, fix) > self.max_gap_ms {
+                return None;
+            }
+            Some(interpolate(a, fix, timestamp, false))
+        } else {
+            let latest = self.fixes.back()?;
+            if timestamp
+                .signed_duration_since(latest.timestamp)
+                .num_milliseconds()
+                > self.max_gap_ms
+            {
+                return None;
+            }
+            let previous = self.fixes.get(self.fixes.len().checked_sub(2)?)?;
+            if millis_between(previous, latest) > self.max_gap_ms {
+                return None;
+            }
+            let mut fix = interpolate(previous, latest, timestamp, true);
+            fix.sog_knots = latest.sog_knots;
+            fix.cog = latest.cog;
+            Some(fix)
+        }
+    }
+}
+
+/// Place the time of day of `timestamp` on the day which makes it closest to `reference`.
+fn closest_day(timestamp: DateTime<Utc>, reference: DateTime<Utc>) -> DateTime<Utc> {
+    let candidate = Utc.from_utc_datetime(&reference.date_naive().and_time(timestamp.time()));
+    let diff = candidate.signed_duration_since(reference);
+    if diff > chrono::Duration::hours(12) {
+        candidate - chrono::Duration::days(1)
+    } else if diff < chrono::Duration::hours(-12) {
+        candidate + chrono::Duration::days(1)
+    } else {
+        candidate
+    }
+}
+
+fn millis_between(a: &FixRecord, b: &FixRecord) -> i64 {
+    b.timestamp
+        .signed_duration_since(a.timestamp)
+        .num_milliseconds()
+}
+
+/// Linear interpolation (or extrapolation) between two fixes of different times. Longitude and
+/// course take the shorter way around.
+fn interpolate(
+    a: &FixRecord,
+    b: &FixRecord,
+    timestamp: DateTime<Utc>,
+    extrapolated: bool,
+) -> InterpolatedFix {
+    let f = timestamp
+        .signed_duration_since(a.timestamp)
+        .num_milliseconds() as f64
+        / millis_between(a, b) as f64;
+    let lerp = |x: f64, y: f64| x + (y - x) * f;
+    let lerp_angle = |x: f64, y: f64, range: f64| {
+        let mut d = y - x;
+        if d > range / 2.0 {
+            d -= range;
+        } else if d < -range / 2.0 {
+            d += range;
+        }
+        x + d * f
+    };
+    let mut longitude = lerp_angle(a.longitude, b.longitude, 360.0);
+    if longitude > 180.0 {
+        longitude -= 360.0;
+    } else if longitude < -180.0 {
+        longitude += 360.0;
+    }
+    InterpolatedFix {
+        timestamp,
+        latitude: lerp(a.latitude, b.latitude),
+        longitude,
+        sog_knots: match (a.sog_knots, b.sog_knots) {
+            (Some(x), Some(y)) => Some(lerp(x, y)),
+            _ => None,
+        },
+        cog: match (a.cog, b.cog) {
+            (Some(x), Some(y)) => Some(normalize_angle(lerp_angle(x, y, 360.0))),
+            _ => None,
+        },
+        extrapolated,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn utc(day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2020, 11, day, hour, minute, second)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fix_history_interpolation() {
+        let mut p = NmeaParser::new();
+        let mut h = FixHistory::new(4, 30.0);
+        let mut push = |h: &mut FixHistory, sentence: &str| {
+            let msg = p.parse_sentence(sentence).unwrap();
+            h.push(&msg)
+        };
+
+        // RMC gives the date for the following GGA fixes
+        assert!(push(
+            &mut h,
+            "$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67"
+        ));
+        assert!(push(
+            &mut h,
+            "$GPGGA,225456,4916.55,N,12311.12,W,1,08,0.9,545.4,M,46.9,M,,"
+        ));
+        assert_eq!(h.latest().unwrap().timestamp, utc(19, 22, 54, 56));
+        assert_eq!(h.latest().unwrap().sog_knots, None);
+
+        // Halfway between the fixes
+        let fix = h.at(utc(19, 22, 54, 51)).unwrap();
+        assert::close(fix.latitude, 49.2750, 0.000001);
+        assert::close(fix.longitude, -123.1853, 0.0001);
+        assert!(!fix.extrapolated);
+
+        // Exact fix
+        let fix = h.at(utc(19, 22, 54, 46)).unwrap();
+        assert_eq!(fix.sog_knots, Some(0.5));
+        assert_eq!(fix.cog, Some(54.7));
+
+        // Extrapolated within the maximum gap
+        let fix = h.at(utc(19, 22, 55, 6)).unwrap();
+        assert::close(fix.latitude, 49.2775, 0.000001);
+        assert!(fix.extrapolated);
+        assert_eq!(h.at(utc(19, 22, 55, 30)), None);
+
+        // Before the history
+        assert_eq!(h.at(utc(19, 22, 54, 40)), None);
+
+        // Fixes without a position or with an invalid one are rejected
+        assert!(!push(&mut h, "$GPGGA,225500,,,,,0,00,,,M,,M,,"));
+        assert!(!push(
+            &mut h,
+            "$GPGGA,225500,4916.55,N,12311.12,W,0,00,,,M,,M,,"
+        ));
+        assert!(!push(&mut h, "$GPRMC,225500,V,,,,,,,191120,,"));
+        assert!(!push(&mut h, "$HEHDT,274.07,T"));
+        assert_eq!(h.len(), 2);
+    }
+
+    #[test]
+    fn test_fix_history_order_and_capacity() {
+        let mut p = NmeaParser::new();
+        let mut h = FixHistory::new(3, 60.0);
+        let mut push = |h: &mut FixHistory, sentence: &str| {
+            let msg = p.parse_sentence(sentence).unwrap();
+            h.push(&msg)
+        };
+        assert!(push(&mut h, "$GPGLL,4916.45,N,12311.12,W,225444,A"));
+        assert!(push(&mut h, "$GPGLL,4916.65,N,12311.12,W,225504,A"));
+
+        // Out of order fix is placed between the others
+        assert!(push(&mut h, "$GPGLL,4916.55,N,12311.12,W,225454,A"));
+        let minutes: Vec<f64> = h.iter().map(|fix| (fix.latitude - 49.0) * 60.0).collect();
+        assert::close(minutes[0], 16.45, 0.0001);
+        assert::close(minutes[1], 16.55, 0.0001);
+        assert::close(minutes[2], 16.65, 0.0001);
+
+        // RMC of the same time is combined and dates the earlier fixes
+        assert!(push(
+            &mut h,
+            "$GPRMC,225454,A,4916.55,N,12311.12,W,000.5,054.7,191120,020.3,E"
+        ));
+        assert_eq!(h.len(), 3);
+        assert_eq!(h.iter().next().unwrap().timestamp, utc(19, 22, 54, 44));
+        assert_eq!(h.iter().nth(1).unwrap().sog_knots, Some(0.5));
+
+        // The oldest fix is dropped when the history is full, and fixes older than the whole
+        // history are rejected
+        assert!(push(&mut h, "$GPGLL,4916.75,N,12311.12,W,225514,A"));
+        assert_eq!(h.len(), 3);
+        assert_eq!(h.iter().next().unwrap().timestamp, utc(19, 22, 54, 54));
+        assert!(!push(&mut h, "$GPGLL,4916.35,N,12311.12,W,225434,A"));
+    }
+
+    #[test]
+    fn test_fix_history_midnight() {
+        let mut p = NmeaParser::new();
+        let mut h = FixHistory::new(8, 30.0);
+        let mut push = |h: &mut FixHistory, sentence: &str| {
+            let msg = p.parse_sentence(sentence).unwrap();
+            h.push(&msg)
+        };
+        assert!(push(
+            &mut h,
+            "$GPRMC,235955,A,4916.45,N,17959.99,E,010.0,090.0,191120,020.3,E"
+        ));
+
+        // GGA after midnight belongs to the next day
+        assert!(push(
+            &mut h,
+            "$GPGGA,000005,4916.45,N,17959.99,W,1,08,0.9,545.4,M,46.9,M,,"
+        ));
+        assert_eq!(h.latest().unwrap().timestamp, utc(20, 0, 0, 5));
+
+        // Interpolation across midnight and the antimeridian
+        let fix = h.at(utc(20, 0, 0, 0)).unwrap();
+        assert::close(fix.latitude, 49.2742, 0.0001);
+        assert::close(fix.longitude.abs(), 180.0, 0.0001);
+        assert_eq!(fix.sog_knots, None);
+    }
+}
@@ -35,12 +35,16 @@ pub(crate) mod mtw;
 pub(crate) mod vhw;
 pub(crate) mod hdt;
 pub(crate) mod mwv;
+pub(crate) mod rmb;
+pub(crate) mod summary;
+#[cfg(feature = "time")]
+pub(crate) mod history;
 
 use super::*;
 pub use gga::{GgaData, GgaQualityIndicator};
 pub use gll::GllData;
-pub use gns::GnsData;
-pub use gsa::{GsaData, GsaFixMode};
+pub use gns::{GnsData, GnsModeIndicator};
+pub use gsa::{group_gsa_by_system_id, GsaData, GsaFixMode};
 pub use gsv::GsvData;
 pub use rmc::RmcData;
 use serde::Serialize;
@@ -57,11 +61,17 @@ pub use mtw::MtwData;
 pub use vhw::VhwData;
 pub use hdt::HdtData;
 pub use mwv::MwvData;
+pub use rmb::RmbData;
+pub use crate::util::seconds_of_day;
+pub use summary::{SatelliteInfo, SatelliteSummary, SatelliteSummaryBuilder};
+#[cfg(feature = "time")]
+pub use history::{FixHistory, FixRecord, InterpolatedFix};
 
 // -------------------------------------------------------------------------------------------------
 
 /// Navigation system, identified with NMEA GNSS sentence prefix (e.g. $BDGGA)
-#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum NavigationSystem {
     /// Combination of several satellite systems
     Combination, // GNxxx
@@ -91,6 +101,28 @@ pub enum NavigationSystem {
     Other,
 }
 
+impl NavigationSystem {
+    /// Map NMEA 4.1 GNSS system ID (used e.g. in GSA and GSV) to the navigation system.
+    /// `None` is returned for unknown IDs.
+    pub fn from_system_id(system_id: u8) -> Option<NavigationSystem> {
+        match system_id {
+            1 => Some(NavigationSystem::Gps),
+            2 => Some(NavigationSystem::Glonass),
+            3 => Some(NavigationSystem::Galileo),
+            4 => Some(NavigationSystem::Beidou),
+            5 => Some(NavigationSystem::Qzss),
+            6 => Some(NavigationSystem::Navic),
+            _ => None,
+        }
+    }
+}
+
+impl Default for NavigationSystem {
+    fn default() -> NavigationSystem {
+        NavigationSystem::Other
+    }
+}
+
 impl core::fmt::Display for NavigationSystem {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -140,7 +172,8 @@ impl core::str::FromStr for NavigationSystem {
 
 // -------------------------------------------------------------------------------------------------
 /// VTG/GLL FAA mode (NMEA 2.3 standard has this information)
-#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum FaaMode {
     /// Autonomous mode (automatic 2D/3D)
     Autonomous,
@@ -181,3 +214,590 @@ impl core::fmt::Display for FaaMode {
         }
     }
 }
+
+// -------------------------------------------------------------------------------------------------
+
+/// Direction to steer to correct the cross track error (RMB)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SteerDirection {
+    /// Steer left (port)
+    Left,
+
+    /// Steer right (starboard)
+    Right,
+}
+
+impl SteerDirection {
+    pub fn new(val: &str) -> Result<SteerDirection, String> {
+        match val {
+            "L" => Ok(SteerDirection::Left),
+            "R" => Ok(SteerDirection::Right),
+            _ => Err(format!("Unrecognized steer direction: {}", val)),
+        }
+    }
+}
+
+impl core::fmt::Display for SteerDirection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SteerDirection::Left => write!(f, "L"),
+            SteerDirection::Right => write!(f, "R"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Fix quality in a form common to GGA quality indicator and GNS mode indicator. The variants are
+/// ordered from the worst to the best quality.
+///
+/// | `FixQuality`    | `GgaQualityIndicator`    | `GnsModeIndicator`       |
+/// |-----------------|--------------------------|--------------------------|
+/// | `NoFix`         | `Invalid`                | `Invalid`                |
+/// | `Simulation`    | `SimulationMode`         | `SimulationMode`         |
+/// | `Manual`        | `ManualInputMode`        | `ManualInputMode`        |
+/// | `DeadReckoning` | `DeadReckoning`          | `DeadReckoning`          |
+/// | `Autonomous`    | `GpsFix`                 | `Autonomous`             |
+/// | `Differential`  | `DGpsFix`                | `Differential`           |
+/// | `Pps`           | `PpsFix`                 | -                        |
+/// | `Precise`       | -                        | `Precise`                |
+/// | `FloatRtk`      | `RealTimeKinematicFloat` | `RealTimeKinematicFloat` |
+/// | `Rtk`           | `RealTimeKinematic`      | `RealTimeKinematic`      |
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FixQuality {
+    /// No fix or the fix is not valid
+    NoFix,
+
+    /// Simulated fix
+    Simulation,
+
+    /// Manually entered position
+    Manual,
+
+    /// Estimated (dead reckoning) fix
+    DeadReckoning,
+
+    /// Autonomous (non-differential) fix
+    Autonomous,
+
+    /// Differential fix
+    Differential,
+
+    /// PPS fix
+    Pps,
+
+    /// Precise fix (no deliberate degradation, P-code used)
+    Precise,
+
+    /// Real-Time Kinematic fix with floating integers
+    FloatRtk,
+
+    /// Real-Time Kinematic fix with fixed integers
+    Rtk,
+}
+
+impl Default for FixQuality {
+    fn default() -> FixQuality {
+        FixQuality::NoFix
+    }
+}
+
+impl From<GgaQualityIndicator> for FixQuality {
+    fn from(q: GgaQualityIndicator) -> FixQuality {
+        match q {
+            GgaQualityIndicator::Invalid => FixQuality::NoFix,
+            GgaQualityIndicator::GpsFix => FixQuality::Autonomous,
+            GgaQualityIndicator::DGpsFix => FixQuality::Differential,
+            GgaQualityIndicator::PpsFix => FixQuality::Pps,
+            GgaQualityIndicator::RealTimeKinematic => FixQuality::Rtk,
+            GgaQualityIndicator::RealTimeKinematicFloat => FixQuality::FloatRtk,
+            GgaQualityIndicator::DeadReckoning => FixQuality::DeadReckoning,
+            GgaQualityIndicator::ManualInputMode => FixQuality::Manual,
+            GgaQualityIndicator::SimulationMode => FixQuality::Simulation,
+        }
+    }
+}
+
+impl From<GnsModeIndicator> for FixQuality {
+    fn from(m: GnsModeIndicator) -> FixQuality {
+        match m {
+            GnsModeIndicator::Invalid => FixQuality::NoFix,
+            GnsModeIndicator::Autonomous => FixQuality::Autonomous,
+            GnsModeIndicator::Differential => FixQuality::Differential,
+            GnsModeIndicator::Precise => FixQuality::Precise,
+            GnsModeIndicator::RealTimeKinematic => FixQuality::Rtk,
+            GnsModeIndicator::RealTimeKinematicFloat => FixQuality::FloatRtk,
+            GnsModeIndicator::DeadReckoning => FixQuality::DeadReckoning,
+            GnsModeIndicator::ManualInputMode => FixQuality::Manual,
+            GnsModeIndicator::SimulationMode => FixQuality::Simulation,
+        }
+    }
+}
+
+impl core::fmt::Display for FixQuality {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FixQuality::NoFix => write!(f, "no fix"),
+            FixQuality::Simulation => write!(f, "simulation"),
+            FixQuality::Manual => write!(f, "manual input"),
+            FixQuality::DeadReckoning => write!(f, "dead reckoning"),
+            FixQuality::Autonomous => write!(f, "autonomous fix"),
+            FixQuality::Differential => write!(f, "differential fix"),
+            FixQuality::Pps => write!(f, "PPS fix"),
+            FixQuality::Precise => write!(f, "precise fix"),
+            FixQuality::FloatRtk => write!(f, "Real-Time Kinematic (floating point)"),
+            FixQuality::Rtk => write!(f, "Real-Time Kinematic"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Dilution of precision (PDOP, HDOP or VDOP). Smaller values are better.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Dop(pub f64);
+
+impl Dop {
+    /// Largest value classified as `DopClass::Ideal` (exclusive)
+    pub const IDEAL_MAX: f64 = 1.0;
+
+    /// Largest value classified as `DopClass::Excellent`
+    pub const EXCELLENT_MAX: f64 = 2.0;
+
+    /// Largest value classified as `DopClass::Good`
+    pub const GOOD_MAX: f64 = 5.0;
+
+    /// Largest value classified as `DopClass::Moderate`
+    pub const MODERATE_MAX: f64 = 10.0;
+
+    /// Largest value classified as `DopClass::Fair`
+    pub const FAIR_MAX: f64 = 20.0;
+
+    /// Classify the value: below 1 is ideal, up to 2 excellent, up to 5 good, up to 10 moderate,
+    /// up to 20 fair and anything larger (or not a number) poor.
+    pub fn classification(&self) -> DopClass {
+        match self.0 {
+            v if v < Dop::IDEAL_MAX => DopClass::Ideal,
+            v if v <= Dop::EXCELLENT_MAX => DopClass::Excellent,
+            v if v <= Dop::GOOD_MAX => DopClass::Good,
+            v if v <= Dop::MODERATE_MAX => DopClass::Moderate,
+            v if v <= Dop::FAIR_MAX => DopClass::Fair,
+            _ => DopClass::Poor,
+        }
+    }
+}
+
+impl From<f64> for Dop {
+    fn from(value: f64) -> Dop {
+        Dop(value)
+    }
+}
+
+impl From<Dop> for f64 {
+    fn from(dop: Dop) -> f64 {
+        dop.0
+    }
+}
+
+impl core::fmt::Display for Dop {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:.1}", self.0)
+    }
+}
+
+/// Quality class of a dilution of precision value, ordered from the best to the worst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DopClass {
+    /// Highest possible confidence level (DOP below 1)
+    Ideal,
+
+    /// Accurate enough for all but the most sensitive applications (DOP 1-2)
+    Excellent,
+
+    /// Accurate enough for in-route navigation (DOP 2-5)
+    Good,
+
+    /// Usable, but a better view of the sky is recommended (DOP 5-10)
+    Moderate,
+
+    /// Rough estimate only (DOP 10-20)
+    Fair,
+
+    /// Inaccurate (DOP over 20)
+    Poor,
+}
+
+impl core::fmt::Display for DopClass {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DopClass::Ideal => write!(f, "ideal"),
+            DopClass::Excellent => write!(f, "excellent"),
+            DopClass::Good => write!(f, "good"),
+            DopClass::Moderate => write!(f, "moderate"),
+            DopClass::Fair => write!(f, "fair"),
+            DopClass::Poor => write!(f, "poor"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Temperature in degrees Celsius.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Celsius(pub f64);
+
+impl Celsius {
+    /// Temperature in degrees Fahrenheit.
+    pub fn to_fahrenheit(self) -> f64 {
+        self.0 * 1.8 + 32.0
+    }
+
+    /// Temperature in kelvins.
+    pub fn to_kelvin(self) -> f64 {
+        self.0 + 273.15
+    }
+}
+
+impl From<f64> for Celsius {
+    fn from(value: f64) -> Celsius {
+        Celsius(value)
+    }
+}
+
+impl From<Celsius> for f64 {
+    fn from(celsius: Celsius) -> f64 {
+        celsius.0
+    }
+}
+
+impl core::fmt::Display for Celsius {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:.1} \u{b0}C", self.0)
+    }
+}
+
+/// Distance or depth in meters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Meters(pub f64);
+
+impl Meters {
+    /// Construct from feet.
+    pub fn from_feet(feet: f64) -> Meters {
+        Meters(feet * 0.3048)
+    }
+
+    /// Construct from fathoms.
+    pub fn from_fathoms(fathoms: f64) -> Meters {
+        Meters(fathoms * 1.8288)
+    }
+
+    /// Distance in feet.
+    pub fn to_feet(self) -> f64 {
+        self.0 / 0.3048
+    }
+}
+
+impl From<f64> for Meters {
+    fn from(value: f64) -> Meters {
+        Meters(value)
+    }
+}
+
+impl From<Meters> for f64 {
+    fn from(meters: Meters) -> f64 {
+        meters.0
+    }
+}
+
+impl core::fmt::Display for Meters {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:.1} m", self.0)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Return water depth below surface in meters from a depth sentence, regardless of its type:
+///
+/// - DPT: depth relative to transducer plus the transducer offset. A positive offset is the
+///   distance from the transducer to the water line. A negative offset (transducer to keel)
+///   or a missing offset doesn't give the depth below surface, so `None` is returned.
+/// - DBS: depth below surface in meters, converted from feet or fathoms if meters are missing.
+///
+/// `None` is returned for other messages, including DBT and DBK, which this crate doesn't parse.
+pub fn depth_meters(msg: &ParsedMessage) -> Option<f64> {
+    match msg {
+        ParsedMessage::Dpt(dpt) => dpt.depth_below_surface(),
+        ParsedMessage::Dbs(dbs) => dbs
+            .depth_meters
+            .or_else(|| dbs.depth_feet.map(|feet| Meters::from_feet(feet).0))
+            .or_else(|| {
+                dbs.depth_fathoms
+                    .map(|fathoms| Meters::from_fathoms(fathoms).0)
+            }),
+        _ => None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Dispatch a GNSS sentence to its handler by the three character sentence type (e.g. `b"GGA"`).
+/// The result is returned with the sentence type, or `None` if the sentence type isn't supported.
+pub(crate) fn dispatch_gnss(
+    kind: &[u8],
+    sentence: &str,
+    nav_system: NavigationSystem,
+    store: &mut ParseContext,
+) -> Option<(&'static str, Result<ParsedMessage, ParseError>)> {
+    let (sentence_type, result) = match kind {
+        // $xxGGA - Global Positioning System Fix Data
+        b"GGA" => ("GGA", gga::handle(sentence, nav_system, store)),
+        // $xxRMC - Recommended minimum specific GPS/Transit data
+        b"RMC" => ("RMC", rmc::handle(sentence, nav_system, store)),
+        // $xxGNS - GNSS fix data
+        b"GNS" => ("GNS", gns::handle(sentence, nav_system)),
+        // $xxGSA - GPS DOP and active satellites
+        b"GSA" => ("GSA", gsa::handle(sentence, nav_system)),
+        // $xxGSV - GPS Satellites in view
+        b"GSV" => ("GSV", gsv::handle(sentence, nav_system, store)),
+        // $xxVTG - Track made good and ground speed
+        b"VTG" => ("VTG", vtg::handle(sentence, nav_system, store)),
+        // $xxGLL - Geographic position, latitude / longitude
+        b"GLL" => ("GLL", gll::handle(sentence, nav_system)),
+        // $xxALM - Almanac Data
+        b"ALM" => ("ALM", alm::handle(sentence, nav_system, store)),
+        // $xxDTM - Datum reference
+        b"DTM" => ("DTM", dtm::handle(sentence, nav_system)),
+        // $xxMSS - MSK receiver signal
+        b"MSS" => ("MSS", mss::handle(sentence, nav_system, store)),
+        // $xxSTN - Multiple Data ID
+        b"STN" => ("STN", stn::handle(sentence, nav_system, store)),
+        // $xxVBW - MSK Receiver Signal
+        b"VBW" => ("VBW", vbw::handle(sentence, nav_system)),
+        // $xxZDA - Date and time
+        b"ZDA" => ("ZDA", zda::handle(sentence, nav_system)),
+        // $xxDPT - Depth of water
+        b"DPT" => ("DPT", dpt::handle(sentence, nav_system)),
+        // $xxDBS - Depth below surface
+        b"DBS" => ("DBS", dbs::handle(sentence, nav_system)),
+        // $xxMTW - Mean temperature of water
+        b"MTW" => ("MTW", mtw::handle(sentence, nav_system)),
+        // $xxVHW - Water speed and heading
+        b"VHW" => ("VHW", vhw::handle(sentence, nav_system)),
+        // $xxHDT - Heading, true
+        b"HDT" => ("HDT", hdt::handle(sentence, nav_system)),
+        // $xxMWV - Wind speed and angle
+        b"MWV" => ("MWV", mwv::handle(sentence, nav_system)),
+        // $xxRMB - Recommended minimum navigation information
+        b"RMB" => ("RMB", rmb::handle(sentence, nav_system)),
+        _ => {
+            return None;
+        }
+    };
+    Some((sentence_type, result))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_depth_meters() {
+        let mut p = NmeaParser::new();
+        let mut depth = |sentence| depth_meters(&p.parse_sentence(sentence).unwrap());
+
+        // DPT with offset from transducer to water line
+        assert::close(depth("$SDDPT,17.5,0.3*67").unwrap_or(0.0), 17.8, 0.001);
+
+        // DPT with offset to keel or without offset
+        assert_eq!(depth("$SDDPT,17.5,-1.2"), None);
+        assert_eq!(depth("$SDDPT,17.5,"), None);
+
+        // DBS in meters, or converted from feet or fathoms
+        assert::close(
+            depth("$SDDBS,16.9,f,5.2,M,2.8,F*32").unwrap_or(0.0),
+            5.2,
+            0.001,
+        );
+        assert::close(depth("$SDDBS,16.9,f,,M,,F").unwrap_or(0.0), 5.151, 0.001);
+        assert::close(depth("$SDDBS,,f,,M,2.8,F").unwrap_or(0.0), 5.121, 0.001);
+
+        // Not a depth sentence
+        assert_eq!(depth("$HEHDT,274.07,T"), None);
+    }
+
+    #[test]
+    fn test_unit_newtypes() {
+        let water = Celsius(17.9);
+        assert_eq!(water, Celsius::from(17.9));
+        assert!(Celsius(4.0) < water);
+        assert_eq!(f64::from(water), 17.9);
+        assert::close(Celsius(100.0).to_fahrenheit(), 212.0, 0.001);
+        assert::close(Celsius(-273.15).to_kelvin(), 0.0, 0.001);
+        assert_eq!(water.to_string(), "17.9 \u{b0}C");
+
+        let depth = Meters(5.2);
+        assert_eq!(depth, Meters::from(5.2));
+        assert!(depth > Meters(0.3));
+        assert::close(Meters::from_feet(16.9).0, 5.151, 0.001);
+        assert::close(Meters::from_fathoms(2.8).0, 5.121, 0.001);
+        assert::close(Meters::from_feet(16.9).to_feet(), 16.9, 0.001);
+        assert_eq!(depth.to_string(), "5.2 m");
+    }
+
+    #[test]
+    fn test_data_constructors() {
+        assert_eq!(NavigationSystem::default(), NavigationSystem::Other);
+        let s = NavigationSystem::Galileo;
+        assert_eq!(AlmData::new(s).source, s);
+        assert_eq!(DbsData::new(s).source, s);
+        assert_eq!(DptData::new(s).source, s);
+        assert_eq!(DtmData::new(s).source, s);
+        assert_eq!(GgaData::new(s).source, s);
+        assert_eq!(GllData::new(s).source, s);
+        assert_eq!(GnsData::new(s).source, s);
+        assert_eq!(GsaData::new(s).source, s);
+        assert_eq!(GsvData::new(s).source, s);
+        assert_eq!(HdtData::new(s).source, s);
+        assert_eq!(MssData::new(s).source, s);
+        assert_eq!(MtwData::new(s).source, s);
+        assert_eq!(MwvData::new(s).source, s);
+        assert_eq!(RmbData::new(s).source, s);
+        assert_eq!(RmcData::new(s).source, s);
+        assert_eq!(StnData::new(s).source, s);
+        assert_eq!(VbwData::new(s).source, s);
+        assert_eq!(VhwData::new(s).source, s);
+        assert_eq!(VtgData::new(s).source, s);
+        assert_eq!(ZdaData::new(s).source, s);
+
+        // Remaining fields are defaults
+        let gga = GgaData::new(s);
+        assert_eq!(
+            gga,
+            GgaData {
+                source: s,
+                ..Default::default()
+            }
+        );
+        assert_eq!(gga.latitude, None);
+        assert_eq!(RmcData::new(s).timestamp, None);
+    }
+
+    #[test]
+    fn test_fix_quality_from_gga() {
+        let cases = [
+            (GgaQualityIndicator::Invalid, FixQuality::NoFix),
+            (GgaQualityIndicator::GpsFix, FixQuality::Autonomous),
+            (GgaQualityIndicator::DGpsFix, FixQuality::Differential),
+            (GgaQualityIndicator::PpsFix, FixQuality::Pps),
+            (GgaQualityIndicator::RealTimeKinematic, FixQuality::Rtk),
+            (
+                GgaQualityIndicator::RealTimeKinematicFloat,
+                FixQuality::FloatRtk,
+            ),
+            (
+                GgaQualityIndicator::DeadReckoning,
+                FixQuality::DeadReckoning,
+            ),
+            (GgaQualityIndicator::ManualInputMode, FixQuality::Manual),
+            (GgaQualityIndicator::SimulationMode, FixQuality::Simulation),
+        ];
+        for (q, expected) in cases.iter() {
+            assert_eq!(FixQuality::from(*q), *expected);
+        }
+    }
+
+    #[test]
+    fn test_dop_classification() {
+        let cases = [
+            (0.0, DopClass::Ideal),
+            (0.99, DopClass::Ideal),
+            (1.0, DopClass::Excellent),
+            (2.0, DopClass::Excellent),
+            (2.01, DopClass::Good),
+            (5.0, DopClass::Good),
+            (5.01, DopClass::Moderate),
+            (10.0, DopClass::Moderate),
+            (10.01, DopClass::Fair),
+            (20.0, DopClass::Fair),
+            (20.01, DopClass::Poor),
+            (f64::NAN, DopClass::Poor),
+        ];
+        for (value, expected) in cases.iter() {
+            assert_eq!(Dop(*value).classification(), *expected, "{}", value);
+        }
+        assert!(DopClass::Ideal < DopClass::Poor);
+        assert!(Dop(1.2) < Dop(1.3));
+        assert_eq!(Dop(1.25).to_string(), "1.2");
+        assert_eq!(Dop::from(3.0), Dop(3.0));
+        assert_eq!(f64::from(Dop(3.0)), 3.0);
+    }
+
+    #[test]
+    fn test_seconds_of_day() {
+        use chrono::Timelike;
+        let dt = Utc
+            .with_ymd_and_hms(2020, 11, 19, 22, 54, 46)
+            .single()
+            .unwrap();
+        assert_eq!(seconds_of_day(&dt), 82486);
+        let dt = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).single().unwrap();
+        assert_eq!(seconds_of_day(&dt), 0);
+        let leap = Utc
+            .with_ymd_and_hms(2016, 12, 31, 23, 59, 59)
+            .single()
+            .unwrap()
+            .with_nanosecond(1_500_000_000)
+            .unwrap();
+        assert_eq!(seconds_of_day(&leap), 86399);
+    }
+
+    #[test]
+    fn test_fix_quality_ordering() {
+        assert!(FixQuality::Rtk > FixQuality::FloatRtk);
+        assert!(FixQuality::FloatRtk > FixQuality::Differential);
+        assert!(FixQuality::Differential > FixQuality::Autonomous);
+        assert!(FixQuality::Autonomous > FixQuality::DeadReckoning);
+        assert!(FixQuality::DeadReckoning > FixQuality::NoFix);
+        assert_eq!(
+            FixQuality::from(GgaQualityIndicator::RealTimeKinematic),
+            FixQuality::from(GnsModeIndicator::RealTimeKinematic)
+        );
+        assert_eq!(
+            FixQuality::from(GgaQualityIndicator::DGpsFix),
+            FixQuality::from(GnsModeIndicator::Differential)
+        );
+    }
+
+    #[test]
+    fn test_fix_quality_from_gns() {
+        let cases = [
+            (GnsModeIndicator::Invalid, FixQuality::NoFix),
+            (GnsModeIndicator::Autonomous, FixQuality::Autonomous),
+            (GnsModeIndicator::Differential, FixQuality::Differential),
+            (GnsModeIndicator::Precise, FixQuality::Precise),
+            (GnsModeIndicator::RealTimeKinematic, FixQuality::Rtk),
+            (
+                GnsModeIndicator::RealTimeKinematicFloat,
+                FixQuality::FloatRtk,
+            ),
+            (GnsModeIndicator::DeadReckoning, FixQuality::DeadReckoning),
+            (GnsModeIndicator::ManualInputMode, FixQuality::Manual),
+            (GnsModeIndicator::SimulationMode, FixQuality::Simulation),
+        ];
+        for (m, expected) in cases.iter() {
+            assert_eq!(FixQuality::from(*m), *expected);
+        }
+    }
+}
@@ -16,8 +16,9 @@ limitations under the License.
 
 use super::*;
 
-/// MSS - Multiple Data ID
-#[derive(Clone, Debug, PartialEq, Serialize)]
+/// MSS - MSK receiver signal
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct MssData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -25,25 +26,37 @@ pub struct MssData {
     /// Signal strength (dB)
     pub ss: Option<u8>,
 
-    /// Signal-to-noise ratio
+    /// Signal-to-noise ratio (dB)
     pub snr: Option<u8>,
 
-    /// Beacon frequency
+    /// Beacon frequency (283.5-325.0 kHz). `None` if the value is missing or out of range.
     pub frequency: Option<f64>,
 
-    /// Beacon bit rate
+    /// Beacon bit rate (25, 50, 100 or 200 bits per second). `None` if the value is missing or
+    /// not one of the valid rates.
     pub bit_rate: Option<u32>,
 
-    /// Channel number
+    /// Channel number of multi-channel receivers
     pub channel: Option<u32>,
 }
 
+impl MssData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> MssData {
+        MssData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
-/// xxMSS: Multiple Data ID
+/// xxMSS: MSK receiver signal
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut ParseContext,
 ) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
@@ -51,8 +64,28 @@ pub(crate) fn handle(
         source: nav_system,
         ss: pick_number_field(&split, 1)?,
         snr: pick_number_field(&split, 2)?,
-        frequency: pick_number_field(&split, 3)?,
-        bit_rate: pick_number_field(&split, 4)?,
+        frequency: {
+            let frequency: Option<f64> = pick_number_field(&split, 3)?;
+            match frequency {
+                Some(f) if !(283.5..=325.0).contains(&f) => {
+                    warn!("MSS beacon frequency out of range: {}", f);
+                    store.add_warning(ParseWarning::MssFrequencyOutOfRange(f));
+                    None
+                }
+                _ => frequency,
+            }
+        },
+        bit_rate: {
+            let bit_rate: Option<u32> = pick_number_field(&split, 4)?;
+            match bit_rate {
+                Some(25) | Some(50) | Some(100) | Some(200) | None => bit_rate,
+                Some(b) => {
+                    warn!("Invalid MSS beacon bit rate: {}", b);
+                    store.add_warning(ParseWarning::InvalidMssBitRate(b));
+                    None
+                }
+            }
+        },
         channel: pick_number_field(&split, 5)?,
     }))
 }
@@ -87,4 +120,51 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_crmss() {
+        // Radiobeacon receiver without channel number
+        match NmeaParser::new().parse_sentence("$CRMSS,48,15,290.0,200,*6F") {
+            Ok(ps) => match ps {
+                ParsedMessage::Mss(mss) => {
+                    assert_eq!(mss.source, NavigationSystem::Other);
+                    assert_eq!(mss.ss, Some(48));
+                    assert_eq!(mss.snr, Some(15));
+                    assert_eq!(mss.frequency, Some(290.0));
+                    assert_eq!(mss.bit_rate, Some(200));
+                    assert_eq!(mss.channel, None);
+                }
+                ParsedMessage::Incomplete => {
+                    assert!(false);
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_mss_out_of_range() {
+        match NmeaParser::new().parse_sentence("$GPMSS,55,27,118.0,300,1") {
+            Ok(ps) => match ps {
+                ParsedMessage::Mss(mss) => {
+                    assert_eq!(mss.ss, Some(55));
+                    assert_eq!(mss.snr, Some(27));
+                    assert_eq!(mss.frequency, None);
+                    assert_eq!(mss.bit_rate, None);
+                    assert_eq!(mss.channel, Some(1));
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
 }
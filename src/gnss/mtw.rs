@@ -17,19 +17,42 @@ limitations under the License.
 use super::*;
 
 /// MTW - Mean Temperature of Water
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct MtwData {
+    /// Navigation system
+    pub source: NavigationSystem,
+
     /// Water temperature in degrees Celsius
     pub temperature: Option<f64>,
 }
 
+impl MtwData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> MtwData {
+        MtwData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Water temperature as `Celsius`.
+    pub fn typed_temperature(&self) -> Option<Celsius> {
+        self.temperature.map(Celsius)
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxMTW: Mean Temperature of Water
-pub(crate) fn handle(sentence: &str) -> Result<ParsedMessage, ParseError> {
+pub(crate) fn handle(
+    sentence: &str,
+    nav_system: NavigationSystem,
+) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Mtw(MtwData {
+        source: nav_system,
         temperature: pick_number_field(&split, 1)?,
     }))
 }
@@ -46,7 +69,8 @@ mod test {
         match NmeaParser::new().parse_sentence("$INMTW,17.9,C*1B") {
             Ok(ps) => match ps {
                 ParsedMessage::Mtw(mtw) => {
-                    assert_eq!(mtw.temperature, Some(17.9))
+                    assert_eq!(mtw.temperature, Some(17.9));
+                    assert_eq!(mtw.typed_temperature(), Some(Celsius(17.9)));
                 }
                 ParsedMessage::Incomplete => {
                     assert!(false);
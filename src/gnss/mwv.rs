@@ -17,8 +17,12 @@ limitations under the License.
 use super::*;
 
 /// MWV - Wind speed and angle
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct MwvData {
+    /// Navigation system
+    pub source: NavigationSystem,
+
     /// wind angle, 0 to 359 degrees
     pub wind_angle: Option<f64>,
 
@@ -32,14 +36,28 @@ pub struct MwvData {
     pub wind_speed_kmh: Option<f64>,
 }
 
+impl MwvData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> MwvData {
+        MwvData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxMWV: Wind speed and angle
 
-pub(crate) fn handle(sentence: &str) -> Result<ParsedMessage, ParseError> {
+pub(crate) fn handle(
+    sentence: &str,
+    nav_system: NavigationSystem,
+) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Mwv(MwvData {
+        source: nav_system,
         wind_angle: pick_number_field(&split, 1)?,
         relative: match pick_string_field(&split, 2)
             .ok_or(ParseError::CorruptedSentence(
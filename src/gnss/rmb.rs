@@ -0,0 +1,168 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use super::*;
+
+/// RMB - Recommended minimum navigation information, i.e. navigation to the destination
+/// waypoint as consumed by autopilots
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RmbData {
+    /// Navigation system
+    pub source: NavigationSystem,
+
+    /// True = data valid, false = navigation receiver warning.
+    pub status_valid: Option<bool>,
+
+    /// Cross track error in nautical miles
+    pub cross_track_error: Option<f64>,
+
+    /// Direction to steer to correct the cross track error
+    pub steer_direction: Option<SteerDirection>,
+
+    /// Origin waypoint ID
+    pub origin_waypoint: Option<String>,
+
+    /// Destination waypoint ID
+    pub dest_waypoint: Option<String>,
+
+    /// Destination waypoint latitude in degrees
+    pub dest_lat: Option<f64>,
+
+    /// Destination waypoint longitude in degrees
+    pub dest_lon: Option<f64>,
+
+    /// Range to destination in nautical miles
+    pub range_nm: Option<f64>,
+
+    /// Bearing to destination in degrees true
+    pub bearing_true: Option<f64>,
+
+    /// Destination closing velocity in knots
+    pub dest_closing_velocity: Option<f64>,
+
+    /// True = arrival circle entered or perpendicular passed, false = not arrived
+    pub arrival_status: Option<bool>,
+
+    /// FAA mode indicator (NMEA 2.3 and later).
+    pub faa_mode: Option<FaaMode>,
+}
+
+impl RmbData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> RmbData {
+        RmbData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
+impl LatLon for RmbData {
+    fn latitude(&self) -> Option<f64> {
+        self.dest_lat
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.dest_lon
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// xxRMB: Recommended minimum navigation information
+pub(crate) fn handle(
+    sentence: &str,
+    nav_system: NavigationSystem,
+) -> Result<ParsedMessage, ParseError> {
+    let split: Vec<&str> = sentence.split(',').collect();
+    let status = |num: usize| match *split.get(num).unwrap_or(&"") {
+        "A" => Some(true),
+        "V" => Some(false),
+        _ => None,
+    };
+
+    Ok(ParsedMessage::Rmb(RmbData {
+        source: nav_system,
+        status_valid: status(1),
+        cross_track_error: pick_number_field(&split, 2)?,
+        steer_direction: SteerDirection::new(split.get(3).unwrap_or(&"")).ok(),
+        origin_waypoint: pick_string_field(&split, 4),
+        dest_waypoint: pick_string_field(&split, 5),
+        dest_lat: parse_latitude_ddmm_mmm(
+            split.get(6).unwrap_or(&""),
+            split.get(7).unwrap_or(&""),
+        )?,
+        dest_lon: parse_longitude_dddmm_mmm(
+            split.get(8).unwrap_or(&""),
+            split.get(9).unwrap_or(&""),
+        )?,
+        range_nm: pick_number_field(&split, 10)?,
+        bearing_true: pick_number_field(&split, 11)?,
+        dest_closing_velocity: pick_number_field(&split, 12)?,
+        arrival_status: status(13),
+        faa_mode: FaaMode::new(split.get(14).unwrap_or(&"")).ok(),
+    }))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_rmb() {
+        let mut p = NmeaParser::new();
+        match p
+            .parse_sentence("$ECRMB,A,0.66,L,003,004,4917.24,N,12309.57,W,001.3,052.5,000.5,V,A*5C")
+        {
+            Ok(ParsedMessage::Rmb(rmb)) => {
+                assert_eq!(rmb.source, NavigationSystem::Other);
+                assert_eq!(rmb.status_valid, Some(true));
+                assert_eq!(rmb.cross_track_error, Some(0.66));
+                assert_eq!(rmb.steer_direction, Some(SteerDirection::Left));
+                assert_eq!(rmb.origin_waypoint, Some("003".into()));
+                assert_eq!(rmb.dest_waypoint, Some("004".into()));
+                assert::close(rmb.dest_lat.unwrap_or(0.0), 49.287, 0.001);
+                assert::close(rmb.dest_lon.unwrap_or(0.0), -123.1595, 0.001);
+                assert_eq!(rmb.range_nm, Some(1.3));
+                assert_eq!(rmb.bearing_true, Some(52.5));
+                assert_eq!(rmb.dest_closing_velocity, Some(0.5));
+                assert_eq!(rmb.arrival_status, Some(false));
+                assert_eq!(rmb.faa_mode, Some(FaaMode::Autonomous));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Pre-2.3 sentence without the mode field and with empty fields
+        match p.parse_sentence("$GPRMB,V,,,,004,,,,,,,,V") {
+            Ok(ParsedMessage::Rmb(rmb)) => {
+                assert_eq!(rmb.status_valid, Some(false));
+                assert_eq!(rmb.cross_track_error, None);
+                assert_eq!(rmb.steer_direction, None);
+                assert_eq!(rmb.origin_waypoint, None);
+                assert_eq!(rmb.dest_waypoint, Some("004".into()));
+                assert_eq!(rmb.dest_lat, None);
+                assert_eq!(rmb.faa_mode, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+}
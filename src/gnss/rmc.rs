@@ -16,14 +16,21 @@ limitations under the License.
 use super::*;
 
 /// RMC - position, velocity, and time (Recommended Minimum sentence C)
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct RmcData {
     /// Navigation system
     pub source: NavigationSystem,
 
+    /// AIS station type if the talker is an AIS station (e.g. `$ABGGA` of a base station),
+    /// otherwise `None`
+    #[cfg(feature = "ais")]
+    pub station: Option<ais::Station>,
+
     /// Fix datetime based on HHMMSS and DDMMYY
-    #[serde(with = "json_date_time_utc")]
-    pub timestamp: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "time", serde(with = "json_date_time_utc"))]
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp: Option<Timestamp>,
 
     /// Status: true = active, false = void.
     pub status_active: Option<bool>,
@@ -37,7 +44,8 @@ pub struct RmcData {
     /// Speed over ground in knots
     pub sog_knots: Option<f64>,
 
-    /// Track angle in degrees (True)
+    /// Track angle in degrees (True). Values from 360 up to 720 are wrapped into range `[0, 360)`
+    /// and larger values are returned as `None` with `ParseWarning::CourseOutOfRange`.
     pub bearing: Option<f64>,
 
     /// Magnetic variation in degrees
@@ -54,19 +62,59 @@ impl LatLon for RmcData {
     }
 }
 
+impl PositionReport for RmcData {
+    fn sog_knots(&self) -> Option<f64> {
+        self.sog_knots
+    }
+
+    fn cog(&self) -> Option<f64> {
+        self.bearing
+    }
+
+    fn fix_time_seconds(&self) -> Option<u32> {
+        self.time_of_day_secs()
+    }
+}
+
+impl RmcData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> RmcData {
+        RmcData {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Seconds since midnight of the fix timestamp.
+    pub fn time_of_day_secs(&self) -> Option<u32> {
+        self.timestamp.as_ref().map(seconds_of_day)
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxRMC: Recommended minimum specific GPS/Transit data
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut ParseContext,
 ) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Rmc(RmcData {
         source: nav_system,
-        timestamp: parse_yymmdd_hhmmss(split.get(9).unwrap_or(&""), split.get(1).unwrap_or(&""))
-            .ok(),
+        #[cfg(feature = "ais")]
+        station: None,
+        timestamp: {
+            let date = split.get(9).unwrap_or(&"");
+            let time = split.get(1).unwrap_or(&"");
+            if time.is_empty() && store.settings.lenient {
+                // Date only, e.g. during acquisition
+                parse_yymmdd_hhmmss(date, "000000").ok()
+            } else {
+                parse_yymmdd_hhmmss(date, time).ok()
+            }
+        },
         status_active: {
             let s = split.get(2).unwrap_or(&"");
             match *s {
@@ -87,7 +135,7 @@ pub(crate) fn handle(
             split.get(6).unwrap_or(&""),
         )?,
         sog_knots: pick_number_field(&split, 7)?,
-        bearing: pick_number_field(&split, 8)?,
+        bearing: pick_number_field(&split, 8)?.and_then(|bearing| wrap_full_circle(bearing, store)),
         variation: {
             if let Some(val) = pick_number_field::<f64>(&split, 10)? {
                 let side = split.get(11).unwrap_or(&"");
@@ -125,6 +173,7 @@ mod test {
                         assert_eq!(rmc.timestamp, {
                             Utc.with_ymd_and_hms(2020, 11, 19, 22, 54, 46).single()
                         });
+                        assert_eq!(rmc.time_of_day_secs(), Some(82486));
                         assert_eq!(rmc.sog_knots.unwrap(), 0.5);
                         assert::close(rmc.bearing.unwrap_or(0.0), 54.7, 0.1);
                         assert_eq!(rmc.variation.unwrap(), 20.3);
@@ -170,4 +219,45 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_rmc_empty_time() {
+        let sentence = "$GPRMC,,V,,,,,,,191120,,";
+
+        // Strict: no timestamp without time
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(sentence) {
+            Ok(ParsedMessage::Rmc(rmc)) => {
+                assert_eq!(rmc.timestamp, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Lenient: date at midnight
+        p.set_lenient(true);
+        match p.parse_sentence(sentence) {
+            Ok(ParsedMessage::Rmc(rmc)) => {
+                assert_eq!(rmc.status_active, Some(false));
+                assert_eq!(
+                    rmc.timestamp,
+                    Utc.with_ymd_and_hms(2020, 11, 19, 0, 0, 0).single()
+                );
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Lenient: no date, no timestamp
+        match p.parse_sentence("$GPRMC,,V,,,,,,,,,") {
+            Ok(ParsedMessage::Rmc(rmc)) => {
+                assert_eq!(rmc.timestamp, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
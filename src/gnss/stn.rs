@@ -16,28 +16,44 @@ limitations under the License.
 
 use super::*;
 
-/// STN - MSK Receiver Signal
-#[derive(Clone, Debug, PartialEq, Serialize)]
+/// STN - Multiple data ID. The talker ID number identifies the device which sends the
+/// sentences following the STN sentence. The latest number is also available with
+/// `NmeaParser::stn_talker_id()`.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct StnData {
     /// Navigation system
     pub source: NavigationSystem,
 
-    /// Talker id numer (0-99)
+    /// Talker ID number (0-99)
     pub talker_id: Option<u8>,
 }
 
+impl StnData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> StnData {
+        StnData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
-/// xxSTN: MSK Receiver Signal
+/// xxSTN: Multiple data ID
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut ParseContext,
 ) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
+    let talker_id = pick_number_field(&split, 1)?;
+    store.state.stn_talker_id = talker_id;
     Ok(ParsedMessage::Stn(StnData {
         source: nav_system,
-        talker_id: pick_number_field(&split, 1)?,
+        talker_id,
     }))
 }
 
@@ -67,4 +83,50 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_instn() {
+        match NmeaParser::new().parse_sentence("$INSTN,05*67") {
+            Ok(ps) => match ps {
+                ParsedMessage::Stn(stn) => {
+                    assert_eq!(stn.source, NavigationSystem::Other);
+                    assert_eq!(stn.talker_id, Some(5));
+                }
+                ParsedMessage::Incomplete => {
+                    assert!(false);
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_stn_followed_by_dpt() {
+        let mut p = NmeaParser::new();
+        assert_eq!(p.stn_talker_id(), None);
+        match p.parse_sentence("$INSTN,05*67") {
+            Ok(ParsedMessage::Stn(stn)) => {
+                assert_eq!(stn.talker_id, Some(5));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        match p.parse_sentence("$SDDPT,17.5,0.3*67") {
+            Ok(ParsedMessage::Dpt(dpt)) => {
+                assert_eq!(dpt.depth_relative_to_transducer, Some(17.5));
+                assert_eq!(p.stn_talker_id(), Some(5));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        p.reset();
+        assert_eq!(p.stn_talker_id(), None);
+    }
 }
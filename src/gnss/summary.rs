@@ -0,0 +1,325 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::*;
+
+/// Satellite record of `SatelliteSummary` combining GSA and GSV data of one satellite
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SatelliteInfo {
+    /// Navigation system of the satellite
+    pub source: NavigationSystem,
+
+    /// Satellite PRN number in NMEA numbering (GLONASS 65-96)
+    pub prn_number: u8,
+
+    /// True if the satellite is used in the fix (listed in GSA)
+    pub used: bool,
+
+    /// True if the satellite is in view (listed in GSV)
+    pub in_view: bool,
+
+    /// Elevation in degrees, `None` if not in view
+    pub elevation: Option<f32>,
+
+    /// Azimuth in degrees from True north, `None` if not in view
+    pub azimuth: Option<f32>,
+
+    /// SNR in dB, `None` when not tracking or not in view
+    pub snr: Option<f32>,
+}
+
+/// Summary of used and visible satellites correlated from GSA and GSV sentences. Use
+/// `SatelliteSummary::builder()` to create one.
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SatelliteSummary {
+    /// Satellites in the order they were seen in GSV, followed by the used satellites which
+    /// weren't in view
+    pub satellites: Vec<SatelliteInfo>,
+
+    /// Number of satellites used in the fix
+    pub used_count: usize,
+
+    /// Number of satellites in view
+    pub in_view_count: usize,
+
+    /// Average SNR of the used satellites which have SNR available
+    pub average_snr_used: Option<f32>,
+}
+
+impl SatelliteSummary {
+    /// Create a builder for the summary.
+    pub fn builder() -> SatelliteSummaryBuilder {
+        SatelliteSummaryBuilder::default()
+    }
+}
+
+/// Builder of `SatelliteSummary`. GSA and GSV data can be added in any order and from several
+/// navigation systems.
+#[derive(Default, Clone, Debug)]
+pub struct SatelliteSummaryBuilder {
+    satellites: Vec<SatelliteInfo>,
+}
+
+impl SatelliteSummaryBuilder {
+    /// Mark the satellites listed in the GSA data as used. The navigation system is taken from
+    /// `GsaData::system()`, so the system ID of combined `$GNGSA` sentences is respected.
+    pub fn add_gsa(&mut self, gsa: &GsaData) -> &mut Self {
+        let system = gsa.system();
+        for prn in gsa.prn_numbers.iter() {
+            self.entry(system, *prn).used = true;
+        }
+        self
+    }
+
+    /// Add the visible satellites of GSV data. A satellite reported more than once keeps the
+    /// latest values.
+    pub fn add_gsv(&mut self, gsv: &[GsvData]) -> &mut Self {
+        for sat in gsv.iter() {
+            let info = self.entry(sat.source, sat.prn_number);
+            info.in_view = true;
+            info.elevation = sat.elevation;
+            info.azimuth = sat.azimuth;
+            info.snr = sat.snr;
+        }
+        self
+    }
+
+    /// Create the summary.
+    pub fn build(&self) -> SatelliteSummary {
+        // Satellites seen in GSV first, the ones only in GSA after them
+        let satellites: Vec<SatelliteInfo> = self
+            .satellites
+            .iter()
+            .filter(|s| s.in_view)
+            .chain(self.satellites.iter().filter(|s| !s.in_view))
+            .cloned()
+            .collect();
+
+        let used_snrs: Vec<f32> = satellites
+            .iter()
+            .filter(|s| s.used)
+            .filter_map(|s| s.snr)
+            .collect();
+        SatelliteSummary {
+            used_count: satellites.iter().filter(|s| s.used).count(),
+            in_view_count: satellites.iter().filter(|s| s.in_view).count(),
+            average_snr_used: {
+                if used_snrs.is_empty() {
+                    None
+                } else {
+                    Some(used_snrs.iter().sum::<f32>() / used_snrs.len() as f32)
+                }
+            },
+            satellites,
+        }
+    }
+
+    /// Find or create the record of the given satellite.
+    fn entry(&mut self, source: NavigationSystem, prn_number: u8) -> &mut SatelliteInfo {
+        let (source, prn_number) = satellite_id(source, prn_number);
+        let pos = match self
+            .satellites
+            .iter()
+            .position(|s| s.source == source && s.prn_number == prn_number)
+        {
+            Some(pos) => pos,
+            None => {
+                self.satellites.push(SatelliteInfo {
+                    source,
+                    prn_number,
+                    ..Default::default()
+                });
+                self.satellites.len() - 1
+            }
+        };
+        &mut self.satellites[pos]
+    }
+}
+
+/// Resolve the navigation system and PRN number identifying a satellite. Combined (GN) sentences
+/// without a system ID use NMEA PRN ranges to tell the systems apart: 1-64 are GPS (including
+/// SBAS) and 65-96 GLONASS. Some GLONASS receivers report slot numbers 1-32 in GL sentences,
+/// which are offset to 65-96. Other systems are identified by the talker ID only.
+fn satellite_id(source: NavigationSystem, prn_number: u8) -> (NavigationSystem, u8) {
+    match source {
+        NavigationSystem::Combination | NavigationSystem::Other => match prn_number {
+            65..=96 => (NavigationSystem::Glonass, prn_number),
+            _ => (NavigationSystem::Gps, prn_number),
+        },
+        NavigationSystem::Glonass => match prn_number {
+            1..=32 => (NavigationSystem::Glonass, prn_number + 64),
+            _ => (NavigationSystem::Glonass, prn_number),
+        },
+        _ => (source, prn_number),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gsv(source: NavigationSystem, prn_number: u8, snr: Option<f32>) -> GsvData {
+        GsvData {
+            source,
+            prn_number,
+            elevation: Some(45.0),
+            azimuth: Some(180.0),
+            snr,
+            signal_id: None,
+        }
+    }
+
+    #[test]
+    fn test_satellite_summary_gps_glonass() {
+        // Combined GSA lists GPS 3, 7, 30 and GLONASS 67, 70
+        let gsa = GsaData {
+            source: NavigationSystem::Combination,
+            mode1_automatic: Some(true),
+            mode2_3d: Some(GsaFixMode::Fix3D),
+            prn_numbers: vec![3, 7, 30, 67, 70],
+            pdop: Some(1.8),
+            hdop: Some(1.0),
+            vdop: Some(1.5),
+            system_id: None,
+        };
+        let gp_gsv = vec![
+            gsv(NavigationSystem::Gps, 3, Some(40.0)),
+            gsv(NavigationSystem::Gps, 7, Some(30.0)),
+            gsv(NavigationSystem::Gps, 12, None),
+        ];
+        // GLONASS 3 is reported by its slot number and corresponds to PRN 67
+        let gl_gsv = vec![
+            gsv(NavigationSystem::Glonass, 3, Some(35.0)),
+            gsv(NavigationSystem::Glonass, 70, None),
+            gsv(NavigationSystem::Glonass, 80, Some(20.0)),
+        ];
+
+        let summary = SatelliteSummary::builder()
+            .add_gsa(&gsa)
+            .add_gsv(&gp_gsv)
+            .add_gsv(&gl_gsv)
+            .build();
+
+        assert_eq!(summary.used_count, 5);
+        assert_eq!(summary.in_view_count, 6);
+        assert_eq!(summary.satellites.len(), 7);
+        assert::close(summary.average_snr_used.unwrap_or(0.0) as f64, 35.0, 0.001);
+
+        let find = |source, prn| {
+            summary
+                .satellites
+                .iter()
+                .find(|s| s.source == source && s.prn_number == prn)
+                .unwrap()
+        };
+
+        // Used and in view
+        let s = find(NavigationSystem::Gps, 3);
+        assert!(s.used && s.in_view);
+        assert_eq!(s.snr, Some(40.0));
+        let s = find(NavigationSystem::Glonass, 67);
+        assert!(s.used && s.in_view);
+        assert_eq!(s.snr, Some(35.0));
+
+        // In view but not used
+        let s = find(NavigationSystem::Gps, 12);
+        assert!(!s.used && s.in_view);
+        let s = find(NavigationSystem::Glonass, 80);
+        assert!(!s.used && s.in_view);
+
+        // Used but missing from GSV, listed last
+        let s = summary.satellites.last().unwrap();
+        assert_eq!((s.source, s.prn_number), (NavigationSystem::Gps, 30));
+        assert!(s.used && !s.in_view);
+        assert_eq!(s.elevation, None);
+        assert_eq!(s.snr, None);
+    }
+
+    #[test]
+    fn test_satellite_summary_system_id() {
+        // Combined GSA sentences of Galileo and BeiDou with PRN numbers inside the GPS range
+        let gsa = |system_id, prn_numbers| GsaData {
+            source: NavigationSystem::Combination,
+            prn_numbers,
+            system_id: Some(system_id),
+            ..Default::default()
+        };
+        let summary = SatelliteSummary::builder()
+            .add_gsa(&gsa(3, vec![4, 9, 11]))
+            .add_gsa(&gsa(4, vec![9, 21]))
+            .add_gsv(&[gsv(NavigationSystem::Galileo, 9, Some(38.0))])
+            .build();
+
+        assert_eq!(summary.used_count, 5);
+        assert_eq!(summary.in_view_count, 1);
+        assert!(summary
+            .satellites
+            .iter()
+            .all(|s| s.source != NavigationSystem::Gps));
+        let count = |source| {
+            summary
+                .satellites
+                .iter()
+                .filter(|s| s.source == source && s.used)
+                .count()
+        };
+        assert_eq!(count(NavigationSystem::Galileo), 3);
+        assert_eq!(count(NavigationSystem::Beidou), 2);
+
+        // Galileo 9 is matched with GSV, BeiDou 9 is a different satellite
+        let s = &summary.satellites[0];
+        assert_eq!((s.source, s.prn_number), (NavigationSystem::Galileo, 9));
+        assert!(s.used && s.in_view);
+        assert_eq!(s.snr, Some(38.0));
+    }
+
+    #[test]
+    fn test_satellite_summary_empty() {
+        let summary = SatelliteSummary::builder().build();
+        assert!(summary.satellites.is_empty());
+        assert_eq!(summary.used_count, 0);
+        assert_eq!(summary.in_view_count, 0);
+        assert_eq!(summary.average_snr_used, None);
+    }
+
+    #[test]
+    fn test_satellite_id() {
+        assert_eq!(
+            satellite_id(NavigationSystem::Combination, 5),
+            (NavigationSystem::Gps, 5)
+        );
+        assert_eq!(
+            satellite_id(NavigationSystem::Combination, 46),
+            (NavigationSystem::Gps, 46)
+        );
+        assert_eq!(
+            satellite_id(NavigationSystem::Combination, 72),
+            (NavigationSystem::Glonass, 72)
+        );
+        assert_eq!(
+            satellite_id(NavigationSystem::Glonass, 8),
+            (NavigationSystem::Glonass, 72)
+        );
+        assert_eq!(
+            satellite_id(NavigationSystem::Galileo, 8),
+            (NavigationSystem::Galileo, 8)
+        );
+    }
+}
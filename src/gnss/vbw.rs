@@ -17,7 +17,8 @@ limitations under the License.
 use super::*;
 
 /// VBW - Dual Ground/Water Speed
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct VbwData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -41,6 +42,16 @@ pub struct VbwData {
     pub ground_speed_valid: Option<bool>,
 }
 
+impl VbwData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> VbwData {
+        VbwData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxVBW: Dual Ground/Water Speed
@@ -17,8 +17,12 @@ limitations under the License.
 use super::*;
 
 /// VHW - Water speed and heading
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct VhwData {
+    /// Navigation system
+    pub source: NavigationSystem,
+
     /// Heading - true
     pub heading_true: Option<f64>,
 
@@ -32,14 +36,28 @@ pub struct VhwData {
     pub speed_through_water_kmh: Option<f64>,
 }
 
+impl VhwData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> VhwData {
+        VhwData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 // xxVHW: Water speed and heading
 
-pub(crate) fn handle(sentence: &str) -> Result<ParsedMessage, ParseError> {
+pub(crate) fn handle(
+    sentence: &str,
+    nav_system: NavigationSystem,
+) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Vhw(VhwData {
+        source: nav_system,
         heading_true: pick_number_field(&split, 1)?,
         heading_magnetic: pick_number_field(&split, 3)?,
         speed_through_water_knots: pick_number_field(&split, 5)?,
@@ -16,15 +16,18 @@ limitations under the License.
 use super::*;
 
 /// VTG - track made good and speed over ground
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct VtgData {
     /// Navigation system
     pub source: NavigationSystem,
 
-    /// Course over ground (CoG), degrees True
+    /// Course over ground (CoG), degrees True. Values from 360 up to 720 are wrapped into
+    /// range `[0, 360)` and larger values are returned as `None` with a warning.
     pub cog_true: Option<f64>,
 
-    /// Course over ground (CoG), degrees Magnetic
+    /// Course over ground (CoG), degrees Magnetic. Values from 360 up to 720 are wrapped into
+    /// range `[0, 360)` and larger values are returned as `None` with a warning.
     pub cog_magnetic: Option<f64>,
 
     /// Speed over ground (SoG), knots
@@ -37,19 +40,36 @@ pub struct VtgData {
     pub faa_mode: Option<FaaMode>,
 }
 
+impl VtgData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> VtgData {
+        VtgData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxVTG: Track Made Good and Ground Speed
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut ParseContext,
 ) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Vtg(VtgData {
         source: nav_system,
-        cog_true: pick_number_field(&split, 1).ok().unwrap_or(None),
-        cog_magnetic: pick_number_field(&split, 3).ok().unwrap_or(None),
+        cog_true: pick_number_field(&split, 1)
+            .ok()
+            .unwrap_or(None)
+            .and_then(|cog| wrap_full_circle(cog, store)),
+        cog_magnetic: pick_number_field(&split, 3)
+            .ok()
+            .unwrap_or(None)
+            .and_then(|cog| wrap_full_circle(cog, store)),
         sog_knots: pick_number_field(&split, 5).ok().unwrap_or(None),
         sog_kph: pick_number_field(&split, 7).ok().unwrap_or(None),
         faa_mode: FaaMode::new(split.get(9).unwrap_or(&"")).ok(),
@@ -87,4 +107,33 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vtg_full_circle() {
+        match NmeaParser::new().parse_sentence("$GPVTG,360.0,T,365.0,M,005.5,N,010.2,K") {
+            Ok(ParsedMessage::Vtg(vtg)) => {
+                assert_eq!(vtg.cog_true, Some(0.0));
+                assert_eq!(vtg.cog_magnetic, Some(5.0));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Corrupt values aren't wrapped
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$GPVTG,725.0,T,054.7,M,005.5,N,010.2,K") {
+            Ok(ParsedMessage::Vtg(vtg)) => {
+                assert_eq!(vtg.cog_true, None);
+                assert_eq!(vtg.cog_magnetic, Some(54.7));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(
+            p.take_warnings(),
+            vec![ParseWarning::CourseOutOfRange(725.0)]
+        );
+    }
 }
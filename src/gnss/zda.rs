@@ -17,20 +17,43 @@ limitations under the License.
 use super::*;
 
 /// ZDA - Time and date
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ZdaData {
     /// Navigation system
     pub source: NavigationSystem,
 
     /// UTC
-    #[serde(with = "json_date_time_utc")]
-    pub timestamp_utc: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "time", serde(with = "json_date_time_utc"))]
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp_utc: Option<Timestamp>,
 
-    /// Local time zone offset
+    /// Local time zone offset (requires `time` feature)
+    #[cfg(feature = "time")]
     #[serde(with = "json_fixed_offset")]
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
     pub timezone_local: Option<FixedOffset>,
 }
 
+impl ZdaData {
+    /// Construct data of the given navigation system with the other fields set to defaults.
+    pub fn new(source: NavigationSystem) -> ZdaData {
+        ZdaData {
+            source,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl ZdaData {
+    /// Local date and time, i.e. the UTC timestamp with the local time zone offset applied.
+    /// `None` is returned if either of them is missing.
+    pub fn local_datetime(&self) -> Option<DateTime<FixedOffset>> {
+        Some(self.timestamp_utc?.with_timezone(&self.timezone_local?))
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// xxZDA: MSK Receiver Signal
@@ -42,11 +65,15 @@ pub(crate) fn handle(
 
     Ok(ParsedMessage::Zda(ZdaData {
         source: nav_system,
+        #[cfg(feature = "time")]
         timestamp_utc: parse_hhmmss_ss(
             split.get(1).unwrap_or(&""),
             pick_date_with_fields(&split, 4, 3, 2, 0, 0, 0, 0)?,
         )
         .ok(),
+        #[cfg(not(feature = "time"))]
+        timestamp_utc: parse_time_of_day(split.get(1).unwrap_or(&"")).ok(),
+        #[cfg(feature = "time")]
         timezone_local: pick_timezone_with_fields(&split, 5, 6).ok(),
     }))
 }
@@ -68,6 +95,10 @@ mod test {
                         Utc.with_ymd_and_hms(2018, 5, 31, 7, 29, 14).single()
                     );
                     assert_eq!(zda.timezone_local, FixedOffset::east_opt(-3 * 3600));
+                    assert_eq!(
+                        zda.local_datetime().map(|dt| dt.naive_local()),
+                        NaiveDate::from_ymd_opt(2018, 5, 31).and_then(|d| d.and_hms_opt(4, 29, 14))
+                    );
                 }
                 ParsedMessage::Incomplete => {
                     assert!(false);
@@ -81,4 +112,92 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_zda_local_time() {
+        match NmeaParser::new().parse_sentence("$GPZDA,223000.00,31,12,2019,+02,00*47") {
+            Ok(ps) => match ps {
+                ParsedMessage::Zda(zda) => {
+                    assert_eq!(zda.timezone_local, FixedOffset::east_opt(2 * 3600));
+                    let local = zda.local_datetime().unwrap();
+                    assert_eq!(local.to_rfc3339(), "2020-01-01T00:30:00+02:00");
+                    assert_eq!(Some(local.with_timezone(&Utc)), zda.timestamp_utc);
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+
+        // Negative offset with zero hours
+        match NmeaParser::new().parse_sentence("$GPZDA,223000.00,31,12,2019,-00,30") {
+            Ok(ParsedMessage::Zda(zda)) => {
+                assert_eq!(zda.timezone_local, FixedOffset::east_opt(-30 * 60));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Missing zone
+        match NmeaParser::new().parse_sentence("$GPZDA,223000.00,31,12,2019,,") {
+            Ok(ParsedMessage::Zda(zda)) => {
+                assert_eq!(zda.timezone_local, None);
+                assert_eq!(zda.local_datetime(), None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_zda_timing_receiver() {
+        // Fractional seconds
+        match NmeaParser::new().parse_sentence("$GPZDA,160012.71,11,03,2004,-1,00*7D") {
+            Ok(ParsedMessage::Zda(zda)) => {
+                let ts = zda.timestamp_utc.unwrap();
+                assert_eq!(ts.to_rfc3339(), "2004-03-11T16:00:12.710+00:00");
+                assert_eq!(ts.timestamp_subsec_millis(), 710);
+                assert_eq!(zda.timezone_local, FixedOffset::east_opt(-3600));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Empty zone fields with checksum
+        match NmeaParser::new().parse_sentence("$GPZDA,072914.00,31,05,2018,,*63") {
+            Ok(ParsedMessage::Zda(zda)) => {
+                assert_eq!(
+                    zda.timestamp_utc,
+                    Utc.with_ymd_and_hms(2018, 5, 31, 7, 29, 14).single()
+                );
+                assert_eq!(zda.timezone_local, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Negative offset with minutes, e.g. Newfoundland
+        match NmeaParser::new().parse_sentence("$GPZDA,072914.00,31,05,2018,-03,30*4E") {
+            Ok(ParsedMessage::Zda(zda)) => {
+                assert_eq!(
+                    zda.timezone_local,
+                    FixedOffset::east_opt(-(3 * 3600 + 30 * 60))
+                );
+                assert_eq!(
+                    zda.local_datetime().map(|dt| dt.to_rfc3339()),
+                    Some("2018-05-31T03:59:14-03:30".to_string())
+                );
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
 }
@@ -21,173 +21,393 @@ limitations under the License.
 //! GLONASS, Galileo, BeiDou, NavIC and QZSS satellite systems.
 //!
 //! Usage in a `#[no_std]` environment is also possible though an allocator is required
+//!
+//! ## Features
+//!
+//! The default feature `time` pulls in `chrono` for timestamps. For minimal embedded builds it
+//! can be disabled with `default-features = false`, in which case timestamp fields are
+//! `(hour, minute, second)` tuples (see `Timestamp`), dates are not parsed and
+//! `NmeaParser::set_current_time()`, `ZdaData::timezone_local` and `ZdaData::local_datetime()`
+//! are not available.
+//!
+//! The default features `ais` and `gnss` enable the AIS (VDM/VDO) and the GNSS sentences
+//! respectively. Either can be left out to save code size, e.g. with
+//! `default-features = false, features = ["gnss", "time"]`; the modules, the `ParsedMessage`
+//! variants and the related parser functions of the disabled protocol are left out, and its
+//! sentences are returned as `ParseError::UnsupportedSentenceType`.
+//!
+//! The size of the parser code with each combination of the features, measured as the size of
+//! a release build (`opt-level = "s"`, LTO, stripped) of a program which parses lines from
+//! standard input on x86_64 Linux minus the size of the same program without the parser:
+//!
+//! | Features                        | Size    |
+//! |---------------------------------|---------|
+//! | `ais`, `gnss`, `time` (default) | 228 KiB |
+//! | `ais`, `time`                   | 140 KiB |
+//! | `gnss`, `time`                  | 133 KiB |
+//! | `ais`                           | 128 KiB |
+//! | `gnss`                          | 119 KiB |
+//!
+//! The feature combinations are checked in CI (`.github/workflows/features.yml`).
+//!
+//! The optional feature `geo-types` adds conversions of the types implementing `LatLon` into
+//! `geo_types::Point<f64>`.
+//!
+//! ## Stability
+//!
+//! All fields of the result structs are public and the structs implement `Default`. New fields
+//! may be added in minor versions, so construct the structs with struct update syntax, e.g.
+//! `ais::VesselDynamicData { mmsi: 230992580, ..Default::default() }`, instead of listing all
+//! the fields. `ParsedMessage`, `ParseError` and `ParseWarning` are `#[non_exhaustive]` because
+//! new message types and errors may be added in minor versions; match them with a wildcard arm.
+//! Renaming or removing fields is a breaking change.
 
 #![forbid(unsafe_code)]
 #![allow(dead_code)]
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(not(feature = "defmt"))]
 #[macro_use]
 extern crate log;
 
+#[cfg(feature = "defmt")]
+use defmt::{debug, warn};
+
 extern crate num_traits;
 
 #[macro_use]
 extern crate alloc;
 
 use alloc::string::{String, ToString};
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 use bitvec::prelude::*;
+pub use bitvec;
+#[cfg(feature = "time")]
 pub use chrono;
+#[cfg(feature = "time")]
 use chrono::prelude::*;
+#[cfg(feature = "time")]
 use chrono::{DateTime, TimeZone};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
+#[cfg(feature = "ais")]
 use core::cmp::max;
+#[cfg(any(feature = "ais", feature = "gnss"))]
 use core::str::FromStr;
 
+// Float methods for `no_std` builds with toolchains whose `core` doesn't provide them
 #[cfg(not(test))]
+#[allow(unused_imports)]
 use num_traits::float::FloatCore;
 
+#[cfg(feature = "ais")]
 pub mod ais;
 mod error;
+mod filter;
+#[cfg(feature = "gnss")]
 pub mod gnss;
 mod util;
+mod warning;
+#[cfg(feature = "time")]
 mod json_date_time_utc;
+#[cfg(feature = "time")]
 mod json_fixed_offset;
 
-pub use error::ParseError;
+pub use error::{ChecksumError, ParseError, StructureError};
+pub use filter::MessageFilter;
+pub use warning::ParseWarning;
+pub use util::{
+    finalize_sentence, format_latitude, format_longitude, fragment_info, is_valid_sentence,
+    is_within_length_limit, parse_latitude_ddmm_mmm, parse_longitude_dddmm_mmm,
+    sanity_check_position, validate_sentence, MAX_NMEA_SENTENCE_LENGTH,
+};
 use util::*;
 
+/// Timestamp of the result structs: `chrono::DateTime<Utc>` with the `time` feature (default).
+#[cfg(feature = "time")]
+pub type Timestamp = DateTime<Utc>;
+
+/// Timestamp of the result structs: time of day as (hour, minute, second) tuple when the `time`
+/// feature is disabled. Dates are not available.
+#[cfg(not(feature = "time"))]
+pub type Timestamp = (u8, u8, u8);
+
 // -------------------------------------------------------------------------------------------------
 
 /// Result from function `NmeaParser::parse_sentence()`. If the given sentence represents only a
 /// partial message `ParsedMessage::Incomplete` is returned.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum ParsedMessage {
     /// The given sentence is only part of multi-sentence message and we need more data to
     /// create the actual result. State is stored in `NmeaParser` object.
     Incomplete,
 
+    /// The given AIS message has been received recently and it was ignored. De-duplication
+    /// is enabled with `NmeaParser::set_dedup_window()`.
+    Duplicate,
+
+    /// The sentence type or AIS message type is rejected by the filter set with
+    /// `NmeaParser::set_message_filter()` or `NmeaParser::set_enabled_types()` and the sentence
+    /// was not decoded.
+    Ignored,
+
     /// AIS VDM/VDO t1, t2, t3, t18 and t27
+    #[cfg(feature = "ais")]
     VesselDynamicData(ais::VesselDynamicData),
 
     /// AIS VDM/VDO t5 and t24
+    #[cfg(feature = "ais")]
     VesselStaticData(ais::VesselStaticData),
 
     /// AIS VDM/VDO type 4
+    #[cfg(feature = "ais")]
     BaseStationReport(ais::BaseStationReport),
 
     /// AIS VDM/VDO type 6
+    #[cfg(feature = "ais")]
     BinaryAddressedMessage(ais::BinaryAddressedMessage),
-    //
-    //    /// AIS VDM/VDO type 7
-    //    BinaryAcknowledge(ais::BinaryAcknowledge),
+
+    /// AIS VDM/VDO type 7
+    #[cfg(feature = "ais")]
+    BinaryAcknowledge(ais::SafetyRelatedAcknowledgement),
     //
     //    /// AIS VDM/VDO type 8
     //    BinaryBroadcastMessage(ais::BinaryBroadcastMessage),
 
     // AIS VDM/VDO type 9
+    #[cfg(feature = "ais")]
     StandardSarAircraftPositionReport(ais::StandardSarAircraftPositionReport),
 
     // AIS VDM/VDO type 10
+    #[cfg(feature = "ais")]
     UtcDateInquiry(ais::UtcDateInquiry),
 
     // AIS VDM/VDO type 11
+    #[cfg(feature = "ais")]
     UtcDateResponse(ais::BaseStationReport),
 
     // AIS VDM/VDO type 12
+    #[cfg(feature = "ais")]
     AddressedSafetyRelatedMessage(ais::AddressedSafetyRelatedMessage),
 
     // AIS VDM/VDO type 13
+    #[cfg(feature = "ais")]
     SafetyRelatedAcknowledgement(ais::SafetyRelatedAcknowledgement),
 
     // AIS VDM/VDO type 14
+    #[cfg(feature = "ais")]
     SafetyRelatedBroadcastMessage(ais::SafetyRelatedBroadcastMessage),
 
     // AIS VDM/VRO type 15
+    #[cfg(feature = "ais")]
     Interrogation(ais::Interrogation),
 
     // AIS VDM/VRO type 16
+    #[cfg(feature = "ais")]
     AssignmentModeCommand(ais::AssignmentModeCommand),
 
     // AIS VDM/VRO type 17
+    #[cfg(feature = "ais")]
     DgnssBroadcastBinaryMessage(ais::DgnssBroadcastBinaryMessage),
 
+    // AIS VDM/VDO type 19
+    #[cfg(feature = "ais")]
+    ExtendedClassBPositionReport(ais::ExtendedClassBPositionReport),
+
     // AIS VDM/VRO type 20
+    #[cfg(feature = "ais")]
     DataLinkManagementMessage(ais::DataLinkManagementMessage),
 
     // AIS VDM/VDO type 21
+    #[cfg(feature = "ais")]
     AidToNavigationReport(ais::AidToNavigationReport),
 
     // AIS VDM/VDO type 22
+    #[cfg(feature = "ais")]
     ChannelManagement(ais::ChannelManagement),
 
     // AIS VDM/VDO type 23
+    #[cfg(feature = "ais")]
     GroupAssignmentCommand(ais::GroupAssignmentCommand),
 
     // AIS VDM/VDO type 25
+    #[cfg(feature = "ais")]
     SingleSlotBinaryMessage(ais::SingleSlotBinaryMessage),
 
     // AIS VDM/VDO type 26
+    #[cfg(feature = "ais")]
     MultipleSlotBinaryMessage(ais::MultipleSlotBinaryMessage),
 
     /// GGA
+    #[cfg(feature = "gnss")]
     Gga(gnss::GgaData),
 
     /// RMC
+    #[cfg(feature = "gnss")]
     Rmc(gnss::RmcData),
 
     /// GNS
+    #[cfg(feature = "gnss")]
     Gns(gnss::GnsData),
 
     /// GSA
+    #[cfg(feature = "gnss")]
     Gsa(gnss::GsaData),
 
     /// GSV
+    #[cfg(feature = "gnss")]
     Gsv(Vec<gnss::GsvData>),
 
     /// VTG
+    #[cfg(feature = "gnss")]
     Vtg(gnss::VtgData),
 
     /// GLL
+    #[cfg(feature = "gnss")]
     Gll(gnss::GllData),
 
     /// ALM
+    #[cfg(feature = "gnss")]
     Alm(gnss::AlmData),
 
     /// DTM
+    #[cfg(feature = "gnss")]
     Dtm(gnss::DtmData),
 
     /// MSS
+    #[cfg(feature = "gnss")]
     Mss(gnss::MssData),
 
     /// STN
+    #[cfg(feature = "gnss")]
     Stn(gnss::StnData),
 
     /// VBW
+    #[cfg(feature = "gnss")]
     Vbw(gnss::VbwData),
 
     /// ZDA
+    #[cfg(feature = "gnss")]
     Zda(gnss::ZdaData),
 
     /// DPT
+    #[cfg(feature = "gnss")]
     Dpt(gnss::DptData),
 
     /// DBS
+    #[cfg(feature = "gnss")]
     Dbs(gnss::DbsData),
 
     /// MTW
+    #[cfg(feature = "gnss")]
     Mtw(gnss::MtwData),
 
     /// VHW
+    #[cfg(feature = "gnss")]
     Vhw(gnss::VhwData),
 
     /// HDT
+    #[cfg(feature = "gnss")]
     Hdt(gnss::HdtData),
 
     /// MWV
+    #[cfg(feature = "gnss")]
     Mwv(gnss::MwvData),
+
+    /// RMB
+    #[cfg(feature = "gnss")]
+    Rmb(gnss::RmbData),
+}
+
+/// Implement a borrowing accessor and an owning `TryFrom<ParsedMessage>` conversion for each
+/// payload type of `ParsedMessage`. The conversion returns the original message as the error if
+/// the variant doesn't match.
+#[cfg(any(feature = "ais", feature = "gnss"))]
+macro_rules! impl_parsed_message_accessors {
+    ($($as_fn:ident: $($variant:ident)|+ => $t:ty),* $(,)?) => {
+        impl ParsedMessage {
+            $(
+                #[doc = concat!("Borrow the `", stringify!($t), "` payload or return `None` if ")]
+                #[doc = "the message is another variant."]
+                pub fn $as_fn(&self) -> Option<&$t> {
+                    match self {
+                        $(ParsedMessage::$variant(data))|+ => Some(data),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+
+        $(
+            impl core::convert::TryFrom<ParsedMessage> for $t {
+                type Error = ParsedMessage;
+
+                fn try_from(msg: ParsedMessage) -> Result<Self, Self::Error> {
+                    match msg {
+                        $(ParsedMessage::$variant(data))|+ => Ok(data),
+                        _ => Err(msg),
+                    }
+                }
+            }
+        )*
+    };
 }
 
+#[cfg(feature = "ais")]
+impl_parsed_message_accessors!(
+    as_vessel_dynamic: VesselDynamicData => ais::VesselDynamicData,
+    as_vessel_static: VesselStaticData => ais::VesselStaticData,
+    as_base_station_report: BaseStationReport | UtcDateResponse => ais::BaseStationReport,
+    as_binary_addressed: BinaryAddressedMessage => ais::BinaryAddressedMessage,
+    as_sar_aircraft_position: StandardSarAircraftPositionReport
+        => ais::StandardSarAircraftPositionReport,
+    as_utc_date_inquiry: UtcDateInquiry => ais::UtcDateInquiry,
+    as_addressed_safety_related: AddressedSafetyRelatedMessage
+        => ais::AddressedSafetyRelatedMessage,
+    as_safety_related_acknowledgement: SafetyRelatedAcknowledgement | BinaryAcknowledge
+        => ais::SafetyRelatedAcknowledgement,
+    as_safety_related_broadcast: SafetyRelatedBroadcastMessage
+        => ais::SafetyRelatedBroadcastMessage,
+    as_interrogation: Interrogation => ais::Interrogation,
+    as_assignment_mode_command: AssignmentModeCommand => ais::AssignmentModeCommand,
+    as_dgnss_broadcast_binary: DgnssBroadcastBinaryMessage => ais::DgnssBroadcastBinaryMessage,
+    as_extended_class_b_position: ExtendedClassBPositionReport
+        => ais::ExtendedClassBPositionReport,
+    as_data_link_management: DataLinkManagementMessage => ais::DataLinkManagementMessage,
+    as_aid_to_navigation: AidToNavigationReport => ais::AidToNavigationReport,
+    as_channel_management: ChannelManagement => ais::ChannelManagement,
+    as_group_assignment_command: GroupAssignmentCommand => ais::GroupAssignmentCommand,
+    as_single_slot_binary: SingleSlotBinaryMessage => ais::SingleSlotBinaryMessage,
+    as_multiple_slot_binary: MultipleSlotBinaryMessage => ais::MultipleSlotBinaryMessage,
+);
+
+#[cfg(feature = "gnss")]
+impl_parsed_message_accessors!(
+    as_gga: Gga => gnss::GgaData,
+    as_rmc: Rmc => gnss::RmcData,
+    as_gns: Gns => gnss::GnsData,
+    as_gsa: Gsa => gnss::GsaData,
+    as_gsv: Gsv => Vec<gnss::GsvData>,
+    as_vtg: Vtg => gnss::VtgData,
+    as_gll: Gll => gnss::GllData,
+    as_alm: Alm => gnss::AlmData,
+    as_dtm: Dtm => gnss::DtmData,
+    as_mss: Mss => gnss::MssData,
+    as_stn: Stn => gnss::StnData,
+    as_vbw: Vbw => gnss::VbwData,
+    as_zda: Zda => gnss::ZdaData,
+    as_dpt: Dpt => gnss::DptData,
+    as_dbs: Dbs => gnss::DbsData,
+    as_mtw: Mtw => gnss::MtwData,
+    as_vhw: Vhw => gnss::VhwData,
+    as_hdt: Hdt => gnss::HdtData,
+    as_mwv: Mwv => gnss::MwvData,
+    as_rmb: Rmb => gnss::RmbData,
+);
+
 // -------------------------------------------------------------------------------------------------
 
 /// Read-only access to geographical position in the implementing type.
@@ -201,15 +421,573 @@ pub trait LatLon {
     fn longitude(&self) -> Option<f64>;
 }
 
+/// Read-only access to position, motion and fix time of AIS and GNSS position reports, so that
+/// they can be processed uniformly. Values which the implementing type doesn't carry are
+/// `None`. The trait is object safe; `ParsedMessage::as_position_report()` returns it as
+/// `&dyn PositionReport`.
+pub trait PositionReport: LatLon {
+    /// Speed over ground in knots.
+    fn sog_knots(&self) -> Option<f64> {
+        None
+    }
+
+    /// Course over ground in degrees.
+    fn cog(&self) -> Option<f64> {
+        None
+    }
+
+    /// True heading in degrees.
+    fn heading(&self) -> Option<f64> {
+        None
+    }
+
+    /// Time of the fix as seconds since midnight UTC.
+    fn fix_time_seconds(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// Error of converting a type implementing `LatLon` into `geo_types::Point` when the latitude or
+/// the longitude is not available.
+#[cfg(feature = "geo-types")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PositionNotAvailable;
+
+#[cfg(feature = "geo-types")]
+impl core::fmt::Display for PositionNotAvailable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "position not available")
+    }
+}
+
+/// Implement `TryFrom<&T> for geo_types::Point<f64>` for the given `LatLon` types. The point
+/// is (x = longitude, y = latitude). A blanket implementation over `LatLon` isn't allowed by the
+/// orphan rules, so each type is listed explicitly.
+#[cfg(feature = "geo-types")]
+macro_rules! impl_point_from_lat_lon {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl core::convert::TryFrom<&$t> for geo_types::Point<f64> {
+                type Error = PositionNotAvailable;
+
+                fn try_from(value: &$t) -> Result<Self, Self::Error> {
+                    match (value.longitude(), value.latitude()) {
+                        (Some(lon), Some(lat)) => Ok(geo_types::Point::new(lon, lat)),
+                        _ => Err(PositionNotAvailable),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(all(feature = "geo-types", feature = "gnss"))]
+impl_point_from_lat_lon!(gnss::GgaData, gnss::GllData, gnss::GnsData, gnss::RmcData,);
+
+#[cfg(all(feature = "geo-types", feature = "ais"))]
+impl_point_from_lat_lon!(
+    ais::AidToNavigationReport,
+    ais::BaseStationReport,
+    ais::BinaryAddressedMessage,
+    ais::DgnssBroadcastBinaryMessage,
+    ais::ExtendedClassBPositionReport,
+    ais::StandardSarAircraftPositionReport,
+    ais::VesselDynamicData,
+);
+
+// -------------------------------------------------------------------------------------------------
+
+/// Source of a `PositionEvent`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PositionSource {
+    /// GNSS GGA, RMC or GNS sentence
+    #[cfg(feature = "gnss")]
+    Gnss(gnss::NavigationSystem),
+
+    /// AIS position report
+    #[cfg(feature = "ais")]
+    Ais(ais::Station),
+}
+
+/// Position fix in a uniform shape regardless of the sentence it originates from. Created with
+/// `ParsedMessage::as_position_event()`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PositionEvent {
+    /// Source of the position
+    pub source: PositionSource,
+
+    /// MMSI of the reporting AIS station or `None` for GNSS
+    pub mmsi: Option<u32>,
+
+    /// Latitude in degrees
+    pub lat: f64,
+
+    /// Longitude in degrees
+    pub lon: f64,
+
+    /// UTC timestamp of the position fix if known
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub timestamp: Option<Timestamp>,
+
+    /// Speed over ground in knots
+    pub sog: Option<f64>,
+
+    /// Course over ground in degrees
+    pub cog: Option<f64>,
+}
+
+impl ParsedMessage {
+    /// Return the message as a `PositionReport` or `None` if the message isn't a position
+    /// report (AIS dynamic data, SAR aircraft position report, RMC, GGA or GLL).
+    pub fn as_position_report(&self) -> Option<&dyn PositionReport> {
+        match self {
+            #[cfg(feature = "ais")]
+            ParsedMessage::VesselDynamicData(vdd) => Some(vdd),
+            #[cfg(feature = "ais")]
+            ParsedMessage::StandardSarAircraftPositionReport(sar) => Some(sar),
+            #[cfg(feature = "ais")]
+            ParsedMessage::ExtendedClassBPositionReport(ecbpr) => Some(ecbpr),
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Rmc(rmc) => Some(rmc),
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gga(gga) => Some(gga),
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gll(gll) => Some(gll),
+            _ => None,
+        }
+    }
+
+    /// Name of the message variant, e.g. "Gga" or "VesselDynamicData", for logging and
+    /// statistics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ParsedMessage::Incomplete => "Incomplete",
+            ParsedMessage::Duplicate => "Duplicate",
+            ParsedMessage::Ignored => "Ignored",
+            #[cfg(feature = "ais")]
+            ParsedMessage::VesselDynamicData(_) => "VesselDynamicData",
+            #[cfg(feature = "ais")]
+            ParsedMessage::VesselStaticData(_) => "VesselStaticData",
+            #[cfg(feature = "ais")]
+            ParsedMessage::BaseStationReport(_) => "BaseStationReport",
+            #[cfg(feature = "ais")]
+            ParsedMessage::BinaryAddressedMessage(_) => "BinaryAddressedMessage",
+            #[cfg(feature = "ais")]
+            ParsedMessage::BinaryAcknowledge(_) => "BinaryAcknowledge",
+            #[cfg(feature = "ais")]
+            ParsedMessage::StandardSarAircraftPositionReport(_) => {
+                "StandardSarAircraftPositionReport"
+            }
+            #[cfg(feature = "ais")]
+            ParsedMessage::UtcDateInquiry(_) => "UtcDateInquiry",
+            #[cfg(feature = "ais")]
+            ParsedMessage::UtcDateResponse(_) => "UtcDateResponse",
+            #[cfg(feature = "ais")]
+            ParsedMessage::AddressedSafetyRelatedMessage(_) => "AddressedSafetyRelatedMessage",
+            #[cfg(feature = "ais")]
+            ParsedMessage::SafetyRelatedAcknowledgement(_) => "SafetyRelatedAcknowledgement",
+            #[cfg(feature = "ais")]
+            ParsedMessage::SafetyRelatedBroadcastMessage(_) => "SafetyRelatedBroadcastMessage",
+            #[cfg(feature = "ais")]
+            ParsedMessage::Interrogation(_) => "Interrogation",
+            #[cfg(feature = "ais")]
+            ParsedMessage::AssignmentModeCommand(_) => "AssignmentModeCommand",
+            #[cfg(feature = "ais")]
+            ParsedMessage::DgnssBroadcastBinaryMessage(_) => "DgnssBroadcastBinaryMessage",
+            #[cfg(feature = "ais")]
+            ParsedMessage::ExtendedClassBPositionReport(_) => "ExtendedClassBPositionReport",
+            #[cfg(feature = "ais")]
+            ParsedMessage::DataLinkManagementMessage(_) => "DataLinkManagementMessage",
+            #[cfg(feature = "ais")]
+            ParsedMessage::AidToNavigationReport(_) => "AidToNavigationReport",
+            #[cfg(feature = "ais")]
+            ParsedMessage::ChannelManagement(_) => "ChannelManagement",
+            #[cfg(feature = "ais")]
+            ParsedMessage::GroupAssignmentCommand(_) => "GroupAssignmentCommand",
+            #[cfg(feature = "ais")]
+            ParsedMessage::SingleSlotBinaryMessage(_) => "SingleSlotBinaryMessage",
+            #[cfg(feature = "ais")]
+            ParsedMessage::MultipleSlotBinaryMessage(_) => "MultipleSlotBinaryMessage",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gga(_) => "Gga",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Rmc(_) => "Rmc",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gns(_) => "Gns",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gsa(_) => "Gsa",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gsv(_) => "Gsv",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Vtg(_) => "Vtg",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gll(_) => "Gll",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Alm(_) => "Alm",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Dtm(_) => "Dtm",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Mss(_) => "Mss",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Stn(_) => "Stn",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Vbw(_) => "Vbw",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Zda(_) => "Zda",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Dpt(_) => "Dpt",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Dbs(_) => "Dbs",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Mtw(_) => "Mtw",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Vhw(_) => "Vhw",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Hdt(_) => "Hdt",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Mwv(_) => "Mwv",
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Rmb(_) => "Rmb",
+        }
+    }
+
+    /// Return the AIS radio channel the message was received on as 'A' or 'B'. Channel codes
+    /// '1' and '2' used by some receivers are mapped to 'A' and 'B', other codes are returned
+    /// as given. `None` is returned for messages other than AIS and if the code is missing.
+    pub fn radio_channel(&self) -> Option<char> {
+        #[cfg(not(feature = "ais"))]
+        let code: Option<char> = None;
+        #[cfg(feature = "ais")]
+        let code = match self {
+            ParsedMessage::VesselDynamicData(m) => m.radio_channel,
+            ParsedMessage::VesselStaticData(m) => m.radio_channel,
+            ParsedMessage::BaseStationReport(m) | ParsedMessage::UtcDateResponse(m) => {
+                m.radio_channel
+            }
+            ParsedMessage::BinaryAddressedMessage(m) => m.radio_channel,
+            ParsedMessage::StandardSarAircraftPositionReport(m) => m.radio_channel,
+            ParsedMessage::UtcDateInquiry(m) => m.radio_channel,
+            ParsedMessage::AddressedSafetyRelatedMessage(m) => m.radio_channel,
+            ParsedMessage::SafetyRelatedAcknowledgement(m)
+            | ParsedMessage::BinaryAcknowledge(m) => m.radio_channel,
+            ParsedMessage::SafetyRelatedBroadcastMessage(m) => m.radio_channel,
+            ParsedMessage::Interrogation(m) => m.radio_channel,
+            ParsedMessage::AssignmentModeCommand(m) => m.radio_channel,
+            ParsedMessage::DgnssBroadcastBinaryMessage(m) => m.radio_channel,
+            ParsedMessage::ExtendedClassBPositionReport(m) => m.radio_channel,
+            ParsedMessage::DataLinkManagementMessage(m) => m.radio_channel,
+            ParsedMessage::AidToNavigationReport(m) => m.radio_channel,
+            ParsedMessage::ChannelManagement(m) => m.radio_channel,
+            ParsedMessage::GroupAssignmentCommand(m) => m.radio_channel,
+            ParsedMessage::SingleSlotBinaryMessage(m) => m.radio_channel,
+            ParsedMessage::MultipleSlotBinaryMessage(m) => m.radio_channel,
+            _ => None,
+        };
+        code.map(|c| match c {
+            '1' => 'A',
+            '2' => 'B',
+            c => c,
+        })
+    }
+
+    /// Return the sequential message ID (0-9) of the VDM/VDO sentence the AIS message was
+    /// decoded from. Together with MMSI and timestamp it identifies messages received via
+    /// redundant receivers. `None` is returned for messages other than AIS and if the ID is
+    /// missing, which is typical for single-fragment sentences.
+    pub fn message_id(&self) -> Option<u8> {
+        #[cfg(not(feature = "ais"))]
+        let id = None;
+        #[cfg(feature = "ais")]
+        let id = match self {
+            ParsedMessage::VesselDynamicData(m) => m.message_id,
+            ParsedMessage::VesselStaticData(m) => m.message_id,
+            ParsedMessage::BaseStationReport(m) | ParsedMessage::UtcDateResponse(m) => m.message_id,
+            ParsedMessage::BinaryAddressedMessage(m) => m.message_id,
+            ParsedMessage::StandardSarAircraftPositionReport(m) => m.message_id,
+            ParsedMessage::UtcDateInquiry(m) => m.message_id,
+            ParsedMessage::AddressedSafetyRelatedMessage(m) => m.message_id,
+            ParsedMessage::SafetyRelatedAcknowledgement(m)
+            | ParsedMessage::BinaryAcknowledge(m) => m.message_id,
+            ParsedMessage::SafetyRelatedBroadcastMessage(m) => m.message_id,
+            ParsedMessage::Interrogation(m) => m.message_id,
+            ParsedMessage::AssignmentModeCommand(m) => m.message_id,
+            ParsedMessage::DgnssBroadcastBinaryMessage(m) => m.message_id,
+            ParsedMessage::ExtendedClassBPositionReport(m) => m.message_id,
+            ParsedMessage::DataLinkManagementMessage(m) => m.message_id,
+            ParsedMessage::AidToNavigationReport(m) => m.message_id,
+            ParsedMessage::ChannelManagement(m) => m.message_id,
+            ParsedMessage::GroupAssignmentCommand(m) => m.message_id,
+            ParsedMessage::SingleSlotBinaryMessage(m) => m.message_id,
+            ParsedMessage::MultipleSlotBinaryMessage(m) => m.message_id,
+            _ => None,
+        };
+        id
+    }
+
+    /// Return the message with the AIS station set if it's a GNSS position message sent by an
+    /// AIS station. `Station::Other` is stored as `None`. Other messages are returned intact.
+    #[cfg(all(feature = "ais", feature = "gnss"))]
+    fn with_station(mut self, station: ais::Station) -> ParsedMessage {
+        let station = Some(station).filter(|s| *s != ais::Station::Other);
+        match &mut self {
+            ParsedMessage::Gga(gga) => gga.station = station,
+            ParsedMessage::Gll(gll) => gll.station = station,
+            ParsedMessage::Gns(gns) => gns.station = station,
+            ParsedMessage::Rmc(rmc) => rmc.station = station,
+            _ => {}
+        }
+        self
+    }
+
+    /// Set the sequential message ID of AIS messages. Other messages are left intact.
+    #[cfg(feature = "ais")]
+    fn set_message_id(&mut self, message_id: Option<u8>) {
+        let field = match self {
+            ParsedMessage::VesselDynamicData(m) => Some(&mut m.message_id),
+            ParsedMessage::VesselStaticData(m) => Some(&mut m.message_id),
+            ParsedMessage::BaseStationReport(m) | ParsedMessage::UtcDateResponse(m) => {
+                Some(&mut m.message_id)
+            }
+            ParsedMessage::BinaryAddressedMessage(m) => Some(&mut m.message_id),
+            ParsedMessage::StandardSarAircraftPositionReport(m) => Some(&mut m.message_id),
+            ParsedMessage::UtcDateInquiry(m) => Some(&mut m.message_id),
+            ParsedMessage::AddressedSafetyRelatedMessage(m) => Some(&mut m.message_id),
+            ParsedMessage::SafetyRelatedAcknowledgement(m)
+            | ParsedMessage::BinaryAcknowledge(m) => Some(&mut m.message_id),
+            ParsedMessage::SafetyRelatedBroadcastMessage(m) => Some(&mut m.message_id),
+            ParsedMessage::Interrogation(m) => Some(&mut m.message_id),
+            ParsedMessage::AssignmentModeCommand(m) => Some(&mut m.message_id),
+            ParsedMessage::DgnssBroadcastBinaryMessage(m) => Some(&mut m.message_id),
+            ParsedMessage::ExtendedClassBPositionReport(m) => Some(&mut m.message_id),
+            ParsedMessage::DataLinkManagementMessage(m) => Some(&mut m.message_id),
+            ParsedMessage::AidToNavigationReport(m) => Some(&mut m.message_id),
+            ParsedMessage::ChannelManagement(m) => Some(&mut m.message_id),
+            ParsedMessage::GroupAssignmentCommand(m) => Some(&mut m.message_id),
+            ParsedMessage::SingleSlotBinaryMessage(m) => Some(&mut m.message_id),
+            ParsedMessage::MultipleSlotBinaryMessage(m) => Some(&mut m.message_id),
+            _ => None,
+        };
+        if let Some(field) = field {
+            *field = message_id;
+        }
+    }
+
+    /// Convert the message into a `PositionEvent`. `None` is returned if the message doesn't
+    /// carry a position or if the position is not available.
+    pub fn as_position_event(&self) -> Option<PositionEvent> {
+        match self {
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gga(gga) => Some(PositionEvent {
+                source: PositionSource::Gnss(gga.source),
+                mmsi: None,
+                lat: gga.latitude?,
+                lon: gga.longitude?,
+                timestamp: gga.timestamp,
+                sog: None,
+                cog: None,
+            }),
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Rmc(rmc) => Some(PositionEvent {
+                source: PositionSource::Gnss(rmc.source),
+                mmsi: None,
+                lat: rmc.latitude?,
+                lon: rmc.longitude?,
+                timestamp: rmc.timestamp,
+                sog: rmc.sog_knots,
+                cog: rmc.bearing,
+            }),
+            #[cfg(feature = "gnss")]
+            ParsedMessage::Gns(gns) => Some(PositionEvent {
+                source: PositionSource::Gnss(gns.source),
+                mmsi: None,
+                lat: gns.latitude?,
+                lon: gns.longitude?,
+                timestamp: gns.timestamp,
+                sog: None,
+                cog: None,
+            }),
+            #[cfg(feature = "ais")]
+            ParsedMessage::VesselDynamicData(vdd) => Some(PositionEvent {
+                source: PositionSource::Ais(vdd.station),
+                mmsi: Some(vdd.mmsi),
+                lat: vdd.latitude?,
+                lon: vdd.longitude?,
+                timestamp: vdd.timestamp,
+                sog: vdd.sog_knots,
+                cog: vdd.cog,
+            }),
+            #[cfg(feature = "ais")]
+            ParsedMessage::BaseStationReport(bsr) | ParsedMessage::UtcDateResponse(bsr) => {
+                Some(PositionEvent {
+                    source: PositionSource::Ais(bsr.station),
+                    mmsi: Some(bsr.mmsi),
+                    lat: bsr.latitude?,
+                    lon: bsr.longitude?,
+                    timestamp: bsr.timestamp,
+                    sog: None,
+                    cog: None,
+                })
+            }
+            #[cfg(feature = "ais")]
+            ParsedMessage::StandardSarAircraftPositionReport(sapr) => Some(PositionEvent {
+                source: PositionSource::Ais(sapr.station),
+                mmsi: Some(sapr.mmsi),
+                lat: sapr.latitude?,
+                lon: sapr.longitude?,
+                timestamp: None,
+                sog: sapr.sog_knots.map(|sog| sog as f64),
+                cog: sapr.cog,
+            }),
+            #[cfg(feature = "ais")]
+            ParsedMessage::ExtendedClassBPositionReport(ecbpr) => Some(PositionEvent {
+                source: PositionSource::Ais(ecbpr.station),
+                mmsi: Some(ecbpr.mmsi),
+                lat: ecbpr.latitude?,
+                lon: ecbpr.longitude?,
+                timestamp: None,
+                sog: ecbpr.sog_knots,
+                cog: ecbpr.cog,
+            }),
+            #[cfg(feature = "ais")]
+            ParsedMessage::AidToNavigationReport(atnr) => Some(PositionEvent {
+                source: PositionSource::Ais(atnr.station),
+                mmsi: Some(atnr.mmsi),
+                lat: atnr.latitude?,
+                lon: atnr.longitude?,
+                timestamp: None,
+                sog: None,
+                cog: None,
+            }),
+            _ => None,
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
+/// Default maximum length of a sentence accepted by `NmeaParser`. The standard limits sentences
+/// to 82 characters but some room is left for prefixes such as tag blocks.
+pub const DEFAULT_MAX_SENTENCE_LENGTH: usize = 256;
+
+/// Default maximum number of parse calls between the parts A and B of AIS type 24 for them to be
+/// combined. See `NmeaParser::set_vsd_pairing_window()`.
+pub const DEFAULT_VSD_PAIRING_WINDOW: usize = 10_000;
+
+/// Default maximum number of parse calls after an AIS base station report during which its time
+/// is used to resolve `VesselDynamicData::timestamp`. See
+/// `NmeaParser::set_ais_reference_window()`.
+pub const DEFAULT_AIS_REFERENCE_WINDOW: usize = 1_000;
+
+/// Maximum length of armored AIS payload of a complete message (1008 bits).
+const MAX_AIS_PAYLOAD_LENGTH: usize = 168;
+
+/// Reject armored AIS payloads (complete or fragment) longer than the largest AIS message.
+#[cfg(feature = "ais")]
+fn check_ais_payload_length(payload: &str) -> Result<(), ParseError> {
+    if payload.len() > MAX_AIS_PAYLOAD_LENGTH {
+        return Err(ParseError::InvalidSentence(format!(
+            "AIS payload length {} exceeds the maximum of {}",
+            payload.len(),
+            MAX_AIS_PAYLOAD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// Decode the armored payload of a complete AIS message into bits. Payloads longer than the
+/// largest AIS message are rejected.
+#[cfg(feature = "ais")]
+fn decode_ais_payload(payload: &str) -> Result<BitVec, ParseError> {
+    check_ais_payload_length(payload)?;
+    parse_payload(payload).map_err(ParseError::CorruptedSentence)
+}
+
+/// Decoder function for AIS binary messages of a specific DAC/FID. See
+/// `NmeaParser::register_binary_decoder()`. Implemented for all the cloneable functions and
+/// closures with the matching signature.
+pub trait BinaryDecoder: Fn(&BitVec) -> Result<ParsedMessage, ParseError> + Send + Sync {
+    /// Clone the decoder into a new box, which makes `NmeaParser` cloneable.
+    fn clone_box(&self) -> Box<dyn BinaryDecoder>;
+}
+
+impl<F> BinaryDecoder for F
+where
+    F: Fn(&BitVec) -> Result<ParsedMessage, ParseError> + Clone + Send + Sync + 'static,
+{
+    fn clone_box(&self) -> Box<dyn BinaryDecoder> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn BinaryDecoder> {
+    fn clone(&self) -> Self {
+        // Dereference to the decoder itself; the box implements `BinaryDecoder` too
+        (**self).clone_box()
+    }
+}
+
 /// NMEA sentence parser which keeps multi-sentence state between `parse_sentence` calls.
 /// The parser tries to be as permissible as possible about the field formats because some NMEA
 /// encoders don't follow the standards strictly.
+///
+/// The parser is `Send` and `Sync`. To feed one parser from several threads, e.g. when several
+/// connections make up one AIS picture, wrap it in `std::sync::Mutex` and hold the lock for each
+/// `parse_sentence()` call, so fragments of the same message may arrive on different threads
+/// and in any order. Take the warnings with `take_warnings()` under the same lock. Sentences
+/// which don't need the multi-sentence state can be parsed with `parse_sentence_stateless()`
+/// under a shared lock, e.g. a read lock of `std::sync::RwLock`.
 #[derive(Clone)]
 pub struct NmeaParser {
+    settings: ParserSettings,
+    state: ParserState,
+}
+
+/// Settings of `NmeaParser`, which parse calls only read.
+#[derive(Clone)]
+struct ParserSettings {
+    vsd_pairing_window: usize,
+    #[cfg(feature = "time")]
+    current_time: Option<DateTime<Utc>>,
+    #[cfg(feature = "time")]
+    ais_reference_window: usize,
+    #[cfg(feature = "time")]
+    propagate_date: bool,
+    dedup_window: usize,
+    require_checksum: bool,
+    max_sentence_length: usize,
+    binary_decoders: HashMap<(u16, u8), Box<dyn BinaryDecoder>>,
+    filter: MessageFilter,
+    reject_implausible_positions: bool,
+    lenient: bool,
+    strict: bool,
+    normalize_angles: bool,
+}
+
+/// Multi-sentence state of `NmeaParser`, which parse calls update.
+#[derive(Clone, Default)]
+struct ParserState {
     saved_fragments: HashMap<String, String>,
-    saved_vsds: HashMap<u32, ais::VesselStaticData>,
+    #[cfg(feature = "ais")]
+    saved_vsds: HashMap<u32, (u64, ais::VesselStaticData)>,
+    parse_count: u64,
+    warnings: Vec<ParseWarning>,
+    #[cfg(feature = "time")]
+    ais_reference_time: Option<(u64, DateTime<Utc>)>,
+    #[cfg(feature = "time")]
+    gnss_reference_time: Option<DateTime<Utc>>,
+    recent_payloads: VecDeque<String>,
+    recent_payload_set: HashSet<String>,
+    stn_talker_id: Option<u8>,
+}
+
+/// A parse call in progress: the settings of the parser and the state the call updates. The
+/// sentence handlers receive the context instead of the parser.
+pub(crate) struct ParseContext<'a> {
+    settings: &'a ParserSettings,
+    state: &'a mut ParserState,
+    stateless: bool,
 }
 
 impl Default for NmeaParser {
@@ -222,59 +1000,582 @@ impl NmeaParser {
     /// Construct an empty parser which is ready to receive sentences.
     pub fn new() -> NmeaParser {
         NmeaParser {
-            saved_fragments: HashMap::new(),
-            saved_vsds: HashMap::new(),
+            settings: ParserSettings {
+                vsd_pairing_window: DEFAULT_VSD_PAIRING_WINDOW,
+                #[cfg(feature = "time")]
+                current_time: None,
+                #[cfg(feature = "time")]
+                ais_reference_window: DEFAULT_AIS_REFERENCE_WINDOW,
+                #[cfg(feature = "time")]
+                propagate_date: false,
+                dedup_window: 0,
+                require_checksum: false,
+                max_sentence_length: DEFAULT_MAX_SENTENCE_LENGTH,
+                binary_decoders: HashMap::new(),
+                filter: MessageFilter::new(),
+                reject_implausible_positions: false,
+                lenient: false,
+                strict: false,
+                normalize_angles: false,
+            },
+            state: ParserState::default(),
         }
     }
 
-    /// Clear internal state of the parser. Multi-sentence state is lost when this function
-    /// is called.
+    /// Set the current time used as a reference when resolving partial dates, such as
+    /// the year of AIS type 5 ETA. If the time isn't set, 2000-01-01 is used instead.
+    ///
+    /// The time is also used as the reference clock of `VesselDynamicData::timestamp` until the
+    /// next AIS base station report (type 4 or 11) is received or the reference window (see
+    /// `set_ais_reference_window()`) passes, so keep it updated if the timestamps are needed.
+    #[cfg(feature = "time")]
+    pub fn set_current_time(&mut self, now: DateTime<Utc>) {
+        self.settings.current_time = Some(now);
+        self.state.ais_reference_time = Some((self.state.parse_count, now));
+    }
+
+    /// Clear internal state of the parser. Multi-sentence state, the AIS and GNSS reference
+    /// times and the pending warnings are lost when this function is called. The settings are
+    /// kept, but the time given with `set_current_time()` is used as the AIS reference time
+    /// again only after it's set again.
     pub fn reset(&mut self) {
-        self.saved_fragments.clear();
-        self.saved_vsds.clear();
+        self.state = ParserState {
+            parse_count: self.state.parse_count,
+            ..Default::default()
+        };
+    }
+
+    /// Drain and return the AIS type 24 records whose other part (A or B) hasn't arrived. The
+    /// records have only the fields of the received part. Calling this periodically delivers
+    /// e.g. vessel names when part B gets lost.
+    #[cfg(feature = "ais")]
+    pub fn flush_partial_vsds(&mut self) -> Vec<ais::VesselStaticData> {
+        self.state
+            .saved_vsds
+            .drain()
+            .map(|(_, (_, vsd))| vsd)
+            .collect()
+    }
+
+    /// Register a decoder for AIS binary messages (types 6, 8, 25 and 26) with the given
+    /// designated area code (DAC) and function identifier (FID). The decoder receives the bits
+    /// of the whole message and its result is returned from `parse_sentence()` instead of the
+    /// generic binary message. A previously registered decoder of the same DAC/FID is replaced.
+    /// Cloning the parser clones the decoders too, so closures have to be `Clone`.
+    #[cfg(feature = "ais")]
+    pub fn register_binary_decoder<F>(&mut self, dac: u16, fid: u8, decoder: F)
+    where
+        F: Fn(&BitVec) -> Result<ParsedMessage, ParseError> + Clone + Send + Sync + 'static,
+    {
+        self.settings
+            .binary_decoders
+            .insert((dac, fid), Box::new(decoder));
+    }
+
+    /// Return the talker ID number of the latest STN sentence. It identifies the device which
+    /// sent the sentences following the STN sentence.
+    pub fn stn_talker_id(&self) -> Option<u8> {
+        self.state.stn_talker_id
+    }
+
+    /// Set the maximum number of parse calls (`parse_sentence()` and `parse_ais_payload()`)
+    /// between the parts A and B of AIS type 24 for them to be combined. When the gap is
+    /// larger, the cached part is discarded and the new part is treated as the first one, so
+    /// that data of different voyages doesn't get mixed. The default is
+    /// `DEFAULT_VSD_PAIRING_WINDOW`.
+    pub fn set_vsd_pairing_window(&mut self, n: usize) {
+        self.settings.vsd_pairing_window = n;
+    }
+
+    /// Set the maximum number of parse calls (`parse_sentence()` and `parse_ais_payload()`)
+    /// after an AIS base station report (type 4 or 11) or `set_current_time()` during which its
+    /// time is used to resolve `VesselDynamicData::timestamp`. When the reference is older, it's
+    /// discarded and the timestamps are `None` until the next report, so that a gap in the
+    /// reports doesn't resolve the timestamps to a wrong minute. The default is
+    /// `DEFAULT_AIS_REFERENCE_WINDOW`.
+    #[cfg(feature = "time")]
+    pub fn set_ais_reference_window(&mut self, n: usize) {
+        self.settings.ais_reference_window = n;
+    }
+
+    /// Enable detection of duplicate AIS messages. The payloads of the last `n` AIS messages are
+    /// remembered and `ParsedMessage::Duplicate` is returned when the same payload is received
+    /// again, regardless of the talker and radio channel. Value 0 disables the detection, which
+    /// is the default.
+    pub fn set_dedup_window(&mut self, n: usize) {
+        self.settings.dedup_window = n;
+        self.state.evict_recent_payloads(n);
+    }
+
+    /// Decode only the given sentence types, e.g. `&["GGA", "RMC", "VDM"]`. The types are given
+    /// without the talker ID. Sentences of other types are not decoded and
+    /// `ParsedMessage::Ignored` is returned for them, which saves processing time and memory of
+    /// e.g. GSV and AIS fragment reassembly. By default all the types are enabled.
+    /// This replaces the filter set with `set_message_filter()`.
+    pub fn set_enabled_types(&mut self, types: &[&str]) {
+        let mut filter = MessageFilter::new();
+        filter.include_sentence_types(types);
+        self.settings.filter = filter;
+    }
+
+    /// Enable all the sentence types, which is the default. This removes the filter set with
+    /// `set_message_filter()` too.
+    pub fn enable_all_types(&mut self) {
+        self.settings.filter = MessageFilter::new();
+    }
+
+    /// Decode only the sentences accepted by the given filter, e.g. to skip AIS binary and
+    /// safety messages. `ParsedMessage::Ignored` is returned for the other sentences, which are
+    /// not decoded or stored for reassembly. This replaces the types given with
+    /// `set_enabled_types()`.
+    pub fn set_message_filter(&mut self, filter: MessageFilter) {
+        self.settings.filter = filter;
+    }
+
+    /// Accept sentences which deviate from the standard in ways known to be harmless. Currently
+    /// this makes RMC sentences with a date but an empty time field produce a timestamp at
+    /// midnight of the date, and GGA altitudes with an unknown unit produce a missing value and
+    /// a warning instead of an error. By default such deviations lead to missing values or
+    /// errors respectively.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.settings.lenient = lenient;
+    }
+
+    /// Reject values outside the ranges defined by the standard instead of accepting or
+    /// truncating them. Currently this applies to ALM PRN numbers above 32 and week numbers
+    /// above 1023, which are otherwise accepted as is and truncated to 10 bits respectively,
+    /// to AIS ship and cargo types above 99 and reserved position fix types 9-14, which
+    /// otherwise fall back to defaults with `ParseWarning::UnexpectedShipType` and
+    /// `ParseWarning::UnrecognizedPositionFixType`, and to sentences other than AIS which exceed
+    /// `MAX_NMEA_SENTENCE_LENGTH` (see `is_within_length_limit()`). The rejected sentences
+    /// return `ParseError::InvalidSentence`. `NavAidType` isn't affected as all of its 5-bit
+    /// values are defined.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.settings.strict = strict;
+    }
+
+    /// Wrap course and heading values into range `[0, 360)`, so that e.g. 360.0 becomes 0.0
+    /// and -2.5 becomes 357.5. Applies to RMC bearing, VTG true and magnetic course, HDT heading,
+    /// VHW true and magnetic heading and RMB bearing to destination. By default the values are
+    /// returned as given, except that RMC bearing and VTG courses from 360 up to 720 are always
+    /// wrapped; larger RMC and VTG values are considered corrupt and returned as `None` regardless
+    /// of this setting.
+    pub fn set_normalize_angles(&mut self, normalize: bool) {
+        self.settings.normalize_angles = normalize;
+    }
+
+    /// Apply the date of the latest RMC or ZDA sentence to the timestamps of GGA, GLL and GNS
+    /// sentences which carry only the time of day. The time of day is placed within half a day
+    /// of the latest RMC or ZDA timestamp, so midnight rollovers get the correct date. Until
+    /// the first RMC or ZDA is received, and by default, the date of the timestamps is
+    /// 2000-01-01.
+    #[cfg(feature = "time")]
+    pub fn set_propagate_date(&mut self, propagate: bool) {
+        self.settings.propagate_date = propagate;
+    }
+
+    /// Reject GNSS positions (GGA, RMC, GNS and GLL) which fail `sanity_check_position()`, such as
+    /// the all-zero positions some receivers emit before they have a fix. The rejected sentences
+    /// return `ParseError::InvalidSentence`. By default all the positions are accepted.
+    pub fn set_reject_implausible_positions(&mut self, reject: bool) {
+        self.settings.reject_implausible_positions = reject;
+    }
+
+    /// Require every sentence to end with a two-digit checksum. When enabled, sentences without
+    /// a checksum or with a truncated one are rejected with `ParseError::Checksum`.
+    /// By default sentences without a checksum are accepted.
+    pub fn set_require_checksum(&mut self, require: bool) {
+        self.settings.require_checksum = require;
+    }
+
+    /// Set the maximum length of sentences given to `parse_sentence()`, including possible
+    /// prefix characters and the line ending. Longer sentences are rejected. The default is
+    /// `DEFAULT_MAX_SENTENCE_LENGTH`. See `set_strict()` for the limit of the standard.
+    pub fn set_max_sentence_length(&mut self, n: usize) {
+        self.settings.max_sentence_length = n;
+    }
+
+    /// Take the warnings of the latest `parse_sentence()` or `parse_ais_payload()` call. The
+    /// warnings describe non-fatal problems, such as reserved field values, which were also
+    /// logged. Calling this again before the next parse call returns an empty vector.
+    pub fn take_warnings(&mut self) -> Vec<ParseWarning> {
+        core::mem::take(&mut self.state.warnings)
+    }
+
+    /// Parse an AIS message from an armored payload which has already been extracted from
+    /// VDM/VDO sentences. Multi-part payloads are given concatenated. Fragment reassembly and
+    /// checksums are not involved but the parser state is: AIS type 24 parts are combined and
+    /// registered binary decoders are used. Fill bits (0-5) are removed from the end of the
+    /// type 17 data, as in `parse_sentence()`.
+    #[cfg(feature = "ais")]
+    pub fn parse_ais_payload(
+        &mut self,
+        payload: &str,
+        fill_bits: u8,
+        station: ais::Station,
+        own_vessel: bool,
+    ) -> Result<ParsedMessage, ParseError> {
+        self.context()
+            .parse_ais_payload(payload, fill_bits, station, own_vessel)
+    }
+
+    /// Parse NMEA sentence like `parse_sentence()` but return also the raw sentence the result
+    /// originates from, e.g. for audit trails. For multipart messages the raw sentence is the
+    /// part which was given in the call.
+    pub fn parse_sentence_with_raw(
+        &mut self,
+        sentence: &str,
+    ) -> Result<(ParsedMessage, String), ParseError> {
+        self.parse_sentence(sentence)
+            .map(|msg| (msg, sentence.to_string()))
+    }
+
+    /// Parse a sentence which doesn't need the multi-sentence state of the parser, e.g. GGA,
+    /// RMC or a single-fragment AIS position report, without mutating the parser. This allows
+    /// sharing the parser between threads. `ParseError::RequiresState` is returned for
+    /// multi-fragment VDM/VDO sentences, AIS types 5 and 24, GSV and STN, which need to be
+    /// passed to `parse_sentence()` instead.
+    ///
+    /// The settings of the parser apply, but the call doesn't update its state: de-duplication
+    /// is skipped, base station reports don't update the AIS reference time and warnings are
+    /// only logged, not available from `take_warnings()`. Nothing of the parser is cloned.
+    pub fn parse_sentence_stateless(&self, sentence: &str) -> Result<ParsedMessage, ParseError> {
+        if let Some(reason) = stateful_sentence_reason(sentence) {
+            return Err(ParseError::RequiresState(reason));
+        }
+        let mut scratch = self.state.scratch();
+        ParseContext {
+            settings: &self.settings,
+            state: &mut scratch,
+            stateless: true,
+        }
+        .parse_sentence(sentence)
+    }
+
+    /// Parse NMEA sentence into `ParsedMessage` enum. If the given sentence is part of
+    /// a multipart message the related state is saved into the parser and
+    /// `ParsedMessage::Incomplete` is returned. The actual result is returned when all the parts
+    /// have been sent to the parser.
+    ///
+    /// Characters preceding the first '$' or '!', e.g. a sequence tag or a tag block added by
+    /// a logger, are skipped and the sentence is parsed from there. `ParseError::InvalidSentence`
+    /// is returned if there's no start delimiter at all.
+    pub fn parse_sentence(&mut self, sentence: &str) -> Result<ParsedMessage, ParseError> {
+        self.context().parse_sentence(sentence)
+    }
+
+    /// Start a parse call which updates the state of the parser.
+    fn context(&mut self) -> ParseContext<'_> {
+        ParseContext {
+            settings: &self.settings,
+            state: &mut self.state,
+            stateless: false,
+        }
+    }
+
+    /// Return number of string-to-string mappings stored.
+    fn strings_count(&self) -> usize {
+        self.state.saved_fragments.len()
+    }
+
+    /// Return number of MMSI-to-VesselStaticData mappings in store.
+    #[cfg(feature = "ais")]
+    fn vsds_count(&self) -> usize {
+        self.state.saved_vsds.len()
+    }
+}
+
+impl ParserSettings {
+    /// Return the reference time set with `set_current_time` or 2000-01-01 if it isn't set.
+    #[cfg(feature = "time")]
+    fn current_time(&self) -> DateTime<Utc> {
+        self.current_time
+            .unwrap_or_else(|| Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).single().unwrap())
+    }
+
+    /// Apply the sentence length policy before the input is scanned in any way. The whole input
+    /// may be at most `max_sentence_length` bytes. In strict mode sentences other than AIS may
+    /// additionally be at most `MAX_NMEA_SENTENCE_LENGTH` characters long, counted from the
+    /// start delimiter (see `is_within_length_limit()`), as overlong sentences indicate framing
+    /// errors.
+    fn check_sentence_length(&self, sentence: &str) -> Result<(), ParseError> {
+        if sentence.len() > self.max_sentence_length {
+            return Err(ParseError::InvalidSentence(format!(
+                "Sentence length {} exceeds the maximum of {}",
+                sentence.len(),
+                self.max_sentence_length
+            )));
+        }
+        if self.strict {
+            if let Some(len) = standard_sentence_length(sentence.trim_end())
+                .filter(|len| *len > MAX_NMEA_SENTENCE_LENGTH)
+            {
+                return Err(ParseError::InvalidSentence(format!(
+                    "Sentence length {} exceeds the maximum of {}",
+                    len, MAX_NMEA_SENTENCE_LENGTH
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ParserState {
+    /// Drop the oldest payloads of the de-duplication window until at most `n` are left.
+    fn evict_recent_payloads(&mut self, n: usize) {
+        while self.recent_payloads.len() > n {
+            if let Some(evicted) = self.recent_payloads.pop_back() {
+                self.recent_payload_set.remove(&evicted);
+            }
+        }
+    }
+
+    /// Return a state for a stateless parse call: the fragment and VSD stores and the warnings
+    /// are empty, which doesn't allocate, and the reference times are copied.
+    fn scratch(&self) -> ParserState {
+        ParserState {
+            parse_count: self.parse_count,
+            #[cfg(feature = "time")]
+            ais_reference_time: self.ais_reference_time,
+            #[cfg(feature = "time")]
+            gnss_reference_time: self.gnss_reference_time,
+            stn_talker_id: self.stn_talker_id,
+            ..Default::default()
+        }
+    }
+}
+
+impl ParseContext<'_> {
+    /// Record a warning of the current parse call.
+    pub(crate) fn add_warning(&mut self, warning: ParseWarning) {
+        self.state.warnings.push(warning);
     }
 
     /// Push string-to-string mapping to store.
     fn push_string(&mut self, key: String, value: String) {
-        self.saved_fragments.insert(key, value);
+        self.state.saved_fragments.insert(key, value);
     }
 
     /// Pull string-to-string mapping by key from store.
     fn pull_string(&mut self, key: String) -> Option<String> {
-        self.saved_fragments.remove(&key)
+        self.state.saved_fragments.remove(&key)
     }
 
     /// Tests whether the given string-to-string mapping exists in the store.
     fn contains_key(&mut self, key: String) -> bool {
-        self.saved_fragments.contains_key(&key)
-    }
-
-    /// Return number of string-to-string mappings stored.
-    fn strings_count(&self) -> usize {
-        self.saved_fragments.len()
+        self.state.saved_fragments.contains_key(&key)
     }
 
     /// Push MMSI-to-VesselStaticData mapping to store.
+    #[cfg(feature = "ais")]
     fn push_vsd(&mut self, mmsi: u32, vsd: ais::VesselStaticData) {
-        self.saved_vsds.insert(mmsi, vsd);
+        self.state
+            .saved_vsds
+            .insert(mmsi, (self.state.parse_count, vsd));
     }
 
-    /// Pull MMSI-to-VesselStaticData mapping from store.
+    /// Pull MMSI-to-VesselStaticData mapping from store. Mappings older than the pairing window
+    /// are discarded and `None` is returned for them.
+    #[cfg(feature = "ais")]
     fn pull_vsd(&mut self, mmsi: u32) -> Option<ais::VesselStaticData> {
-        self.saved_vsds.remove(&mmsi)
+        let (pushed_at, vsd) = self.state.saved_vsds.remove(&mmsi)?;
+        if self.state.parse_count - pushed_at > self.settings.vsd_pairing_window as u64 {
+            debug!("Discarded stale AIS type 24 part of MMSI {}", mmsi);
+            return None;
+        }
+        Some(vsd)
     }
 
-    /// Return number of MMSI-to-VesselStaticData mappings in store.
-    fn vsds_count(&self) -> usize {
-        self.saved_vsds.len()
+    /// Tests whether the given AIS payload has been seen within the de-duplication window and
+    /// adds it to the window. The payloads are looked up from a hash set, and the queue keeps
+    /// their order for eviction.
+    fn is_duplicate_payload(&mut self, payload: &str) -> bool {
+        if self.settings.dedup_window == 0 || self.stateless {
+            return false;
+        }
+        if self.state.recent_payload_set.contains(payload) {
+            return true;
+        }
+        self.state
+            .evict_recent_payloads(self.settings.dedup_window - 1);
+        self.state.recent_payloads.push_front(payload.to_string());
+        self.state.recent_payload_set.insert(payload.to_string());
+        false
     }
 
-    /// Parse NMEA sentence into `ParsedMessage` enum. If the given sentence is part of
-    /// a multipart message the related state is saved into the parser and
-    /// `ParsedMessage::Incomplete` is returned. The actual result is returned when all the parts
-    /// have been sent to the parser.
-    pub fn parse_sentence(&mut self, sentence: &str) -> Result<ParsedMessage, ParseError> {
-        // Shed characters prefixing the message if they exist
+    /// Check the position of a GNSS message if rejection of implausible positions is enabled.
+    #[cfg(feature = "gnss")]
+    fn check_gnss_position(&self, msg: ParsedMessage) -> Result<ParsedMessage, ParseError> {
+        if self.settings.reject_implausible_positions {
+            let position = match &msg {
+                ParsedMessage::Gga(gga) => (gga.latitude(), gga.longitude()),
+                ParsedMessage::Rmc(rmc) => (rmc.latitude(), rmc.longitude()),
+                ParsedMessage::Gns(gns) => (gns.latitude(), gns.longitude()),
+                ParsedMessage::Gll(gll) => (gll.latitude(), gll.longitude()),
+                _ => (None, None),
+            };
+            if let (Some(lat), Some(lon)) = position {
+                if !sanity_check_position(lat, lon) {
+                    return Err(ParseError::InvalidSentence(format!(
+                        "Implausible position: {}, {}",
+                        lat, lon
+                    )));
+                }
+            }
+        }
+        Ok(msg)
+    }
+
+    /// Take the reference time from AIS base station reports and resolve the full timestamp of
+    /// AIS dynamic data by snapping its UTC second to the nearest minute of the reference time.
+    /// References older than the reference window are discarded.
+    #[cfg(all(feature = "ais", feature = "time"))]
+    fn resolve_ais_timestamp(&mut self, mut msg: ParsedMessage) -> ParsedMessage {
+        match &mut msg {
+            ParsedMessage::BaseStationReport(bsr) | ParsedMessage::UtcDateResponse(bsr)
+                if bsr.timestamp.is_some() =>
+            {
+                self.state.ais_reference_time =
+                    bsr.timestamp.map(|ts| (self.state.parse_count, ts));
+            }
+            // Type 27 doesn't have the UTC second
+            ParsedMessage::VesselDynamicData(vdd) if !vdd.low_resolution_position => {
+                if let Some((taken_at, _)) = self.state.ais_reference_time {
+                    if self.state.parse_count - taken_at > self.settings.ais_reference_window as u64
+                    {
+                        self.state.ais_reference_time = None;
+                    }
+                }
+                vdd.timestamp = self
+                    .state
+                    .ais_reference_time
+                    .and_then(|(_, reference)| snap_utc_second(reference, vdd.timestamp_seconds));
+            }
+            _ => {}
+        }
+        msg
+    }
+
+    /// Full timestamps of AIS dynamic data require the `time` feature.
+    #[cfg(all(feature = "ais", not(feature = "time")))]
+    fn resolve_ais_timestamp(&mut self, msg: ParsedMessage) -> ParsedMessage {
+        msg
+    }
+
+    /// Take the reference date from RMC and ZDA sentences and apply it to GGA, GLL and GNS
+    /// timestamps if enabled.
+    #[cfg(all(feature = "gnss", feature = "time"))]
+    fn propagate_gnss_date(&mut self, mut msg: ParsedMessage) -> ParsedMessage {
+        if self.settings.propagate_date {
+            match &mut msg {
+                ParsedMessage::Rmc(gnss::RmcData {
+                    timestamp: Some(ts),
+                    ..
+                })
+                | ParsedMessage::Zda(gnss::ZdaData {
+                    timestamp_utc: Some(ts),
+                    ..
+                }) => {
+                    self.state.gnss_reference_time = Some(*ts);
+                }
+                ParsedMessage::Gga(gnss::GgaData { timestamp, .. })
+                | ParsedMessage::Gll(gnss::GllData { timestamp, .. })
+                | ParsedMessage::Gns(gnss::GnsData { timestamp, .. }) => {
+                    if let (Some(reference), Some(ts)) =
+                        (self.state.gnss_reference_time, *timestamp)
+                    {
+                        *timestamp = snap_time_of_day(reference, ts);
+                    }
+                }
+                _ => {}
+            }
+        }
+        msg
+    }
+
+    /// Dates of GNSS timestamps require the `time` feature.
+    #[cfg(all(feature = "gnss", not(feature = "time")))]
+    fn propagate_gnss_date(&mut self, msg: ParsedMessage) -> ParsedMessage {
+        msg
+    }
+
+    /// Wrap course and heading values of a GNSS message into `[0, 360)` if enabled.
+    #[cfg(feature = "gnss")]
+    fn normalize_gnss_angles(&self, mut msg: ParsedMessage) -> ParsedMessage {
+        if self.settings.normalize_angles {
+            let wrap = |angle: &mut Option<f64>| {
+                if let Some(degrees) = angle {
+                    *degrees = normalize_angle(*degrees);
+                }
+            };
+            match &mut msg {
+                ParsedMessage::Rmc(rmc) => wrap(&mut rmc.bearing),
+                ParsedMessage::Vtg(vtg) => {
+                    wrap(&mut vtg.cog_true);
+                    wrap(&mut vtg.cog_magnetic);
+                }
+                ParsedMessage::Hdt(hdt) => wrap(&mut hdt.heading_true),
+                ParsedMessage::Rmb(rmb) => wrap(&mut rmb.bearing_true),
+                ParsedMessage::Vhw(vhw) => {
+                    wrap(&mut vhw.heading_true);
+                    wrap(&mut vhw.heading_magnetic);
+                }
+                _ => {}
+            }
+        }
+        msg
+    }
+
+    /// See `NmeaParser::current_time()`.
+    #[cfg(feature = "time")]
+    pub(crate) fn current_time(&self) -> DateTime<Utc> {
+        self.settings.current_time()
+    }
+
+    /// See `NmeaParser::parse_ais_payload()`.
+    #[cfg(feature = "ais")]
+    fn parse_ais_payload(
+        &mut self,
+        payload: &str,
+        fill_bits: u8,
+        station: ais::Station,
+        own_vessel: bool,
+    ) -> Result<ParsedMessage, ParseError> {
+        self.state.parse_count += 1;
+        self.state.warnings.clear();
+        if fill_bits > 5 {
+            return Err(ParseError::InvalidSentence(format!(
+                "Invalid number of fill bits: {}",
+                fill_bits
+            )));
+        }
+        let bv = decode_ais_payload(payload)?;
+        ais::dispatch_message(&bv, fill_bits, station, None, own_vessel, self)
+    }
+
+    /// See `NmeaParser::parse_sentence()`.
+    #[cfg_attr(
+        not(any(feature = "ais", feature = "gnss")),
+        allow(unreachable_code, unused_variables)
+    )]
+    fn parse_sentence(&mut self, sentence: &str) -> Result<ParsedMessage, ParseError> {
+        self.state.parse_count += 1;
+        self.state.warnings.clear();
+
+        // Reject excessively long input before doing anything else, so that the scans below
+        // are bounded
+        self.settings.check_sentence_length(sentence)?;
+
+        // Shed trailing CR LF and other whitespace, e.g. of lines read from a serial port
+        let sentence = sentence.trim_end();
+
+        // Shed characters prefixing the message if they exist. A byte order mark or other
+        // garbage such as an ANSI escape sequence is reported, while printable prefixes (e.g.
+        // tag blocks) are skipped silently.
+        if let Some((_, len)) = strip_sentence_prefix(sentence) {
+            warn!("Skipped {} bytes before the start delimiter", len);
+            self.add_warning(ParseWarning::SkippedPrefix(len));
+        }
         let sentence = {
             if let Some(start_idx) = sentence.find(['$', '!']) {
                 &sentence[start_idx..]
@@ -286,34 +1587,30 @@ impl NmeaParser {
             }
         };
 
+        let original_sentence = sentence;
+
         // Calculate NMEA checksum and compare it to the given one. Also, remove the checksum part
         // from the sentence to simplify next processing steps.
-        let mut checksum = 0;
-        let (sentence, checksum_hex_given) = {
-            if let Some(pos) = sentence.rfind('*') {
-                if pos + 3 <= sentence.len() {
-                    (
-                        sentence[0..pos].to_string(),
-                        sentence[(pos + 1)..(pos + 3)].to_string(),
-                    )
-                } else {
-                    debug!("Invalid checksum found for sentence: {}", sentence);
-                    (sentence[0..pos].to_string(), "".to_string())
+        let (sentence, checksum_hex_given) = match split_checksum(sentence) {
+            (body, Some(given)) if given.len() >= 2 => (body, given),
+            (body, Some(_)) => {
+                if self.settings.require_checksum {
+                    return Err(ParseError::Checksum(ChecksumError::Truncated));
+                }
+                debug!("Invalid checksum found for sentence: {}", sentence);
+                (body, "")
+            }
+            (body, None) => {
+                if self.settings.require_checksum {
+                    return Err(ParseError::Checksum(ChecksumError::Missing));
                 }
-            } else {
                 debug!("No checksum found for sentence: {}", sentence);
-                (sentence.to_string(), "".to_string())
+                (body, "")
             }
         };
-        for c in sentence.as_str().chars().skip(1) {
-            checksum ^= c as u8;
-        }
-        let checksum_hex_calculated = format!("{:02X?}", checksum);
-        if checksum_hex_calculated != checksum_hex_given && !checksum_hex_given.is_empty() {
-            return Err(ParseError::CorruptedSentence(format!(
-                "Corrupted NMEA sentence: {:02X?} != {:02X?}",
-                checksum_hex_calculated, checksum_hex_given
-            )));
+        let checksum = calculate_checksum(sentence);
+        if !checksum_hex_given.is_empty() && !checksum_matches(checksum, checksum_hex_given) {
+            return Err(checksum_mismatch_error(checksum, checksum_hex_given));
         }
 
         // Pick sentence type
@@ -339,91 +1636,76 @@ impl NmeaParser {
             )));
         }
 
-        let (nav_system, station, sentence_type) = if sentence_type.starts_with('$') {
-            // Identify GNSS system by talker ID.
-            let nav_system = gnss::NavigationSystem::from_str(
+        // Identify GNSS system or AIS station by talker ID
+        #[cfg(feature = "gnss")]
+        let nav_system = if sentence_type.starts_with('$') {
+            gnss::NavigationSystem::from_str(
                 sentence_type
                     .get(1..)
                     .ok_or(ParseError::CorruptedSentence("Empty String".to_string()))?,
-            )?;
-            let sentence_type = if !sentence_type.starts_with('P') && sentence_type.len() == 6 {
-                format!(
-                    "${}",
-                    sentence_type
-                        .get(3..6)
-                        .ok_or(ParseError::InvalidSentence(format!(
-                            "{sentence_type} is too short."
-                        )))?
-                )
-            } else {
-                String::from(sentence_type)
-            };
-            (nav_system, ais::Station::Other, sentence_type)
-        } else if sentence_type.starts_with('!') {
-            // Identify AIS station
-            let station = ais::Station::from_str(
+            )?
+        } else {
+            gnss::NavigationSystem::Other
+        };
+        #[cfg(feature = "ais")]
+        let station = if sentence_type.starts_with('!') {
+            ais::Station::from_str(
                 sentence_type
                     .get(1..)
                     .ok_or(ParseError::CorruptedSentence("Empty String".to_string()))?,
-            )?;
-            let sentence_type = if sentence_type.len() == 6 {
-                format!(
-                    "!{}",
-                    sentence_type
-                        .get(3..6)
-                        .ok_or(ParseError::InvalidSentence(format!(
-                            "{sentence_type} is too short."
-                        )))?
-                )
-            } else {
-                String::from(sentence_type)
-            };
-            (gnss::NavigationSystem::Other, station, sentence_type)
+            )?
+        } else {
+            // GNSS sentences of an AIS station, e.g. "$ABGGA"
+            ais::Station::from_str(sentence_type.get(1..).unwrap_or(""))
+                .unwrap_or(ais::Station::Other)
+        };
+
+        // Sentence type without the delimiter and the talker ID (e.g. "GGA" of "$GPGGA"). The
+        // type characters have been validated to be ASCII above, so slicing by bytes is safe.
+        let delimiter = &sentence_type[0..1];
+        let kind = if sentence_type.len() == 6 {
+            &sentence_type[3..6]
         } else {
-            (
-                gnss::NavigationSystem::Other,
-                ais::Station::Other,
-                String::from(sentence_type),
-            )
+            &sentence_type[1..]
         };
 
+        // Skip disabled sentence types
+        if !self.settings.filter.accepts_sentence_type(kind) {
+            return Ok(ParsedMessage::Ignored);
+        }
+
         // Handle sentence types
-        match sentence_type.as_str() {
-            // $xxGGA - Global Positioning System Fix Data
-            "$GGA" => gnss::gga::handle(sentence.as_str(), nav_system),
-            // $xxRMC - Recommended minimum specific GPS/Transit data
-            "$RMC" => gnss::rmc::handle(sentence.as_str(), nav_system),
-            // $xxGNS - GNSS fix data
-            "$GNS" => gnss::gns::handle(sentence.as_str(), nav_system),
-            // $xxGSA - GPS DOP and active satellites
-            "$GSA" => gnss::gsa::handle(sentence.as_str(), nav_system),
-            // $xxGSV - GPS Satellites in view
-            "$GSV" => gnss::gsv::handle(sentence.as_str(), nav_system, self),
-            // $xxVTG - Track made good and ground speed
-            "$VTG" => gnss::vtg::handle(sentence.as_str(), nav_system),
-            // $xxGLL - Geographic position, latitude / longitude
-            "$GLL" => gnss::gll::handle(sentence.as_str(), nav_system),
-            // $xxALM - Almanac Data
-            "$ALM" => gnss::alm::handle(sentence.as_str(), nav_system),
-            // $xxDTM - Datum reference
-            "$DTM" => gnss::dtm::handle(sentence.as_str(), nav_system),
-            // $xxMSS - MSK receiver signal
-            "$MSS" => gnss::mss::handle(sentence.as_str(), nav_system),
-            // $xxSTN - Multiple Data ID
-            "$STN" => gnss::stn::handle(sentence.as_str(), nav_system),
-            // $xxVBW - MSK Receiver Signal
-            "$VBW" => gnss::vbw::handle(sentence.as_str(), nav_system),
-            // $xxZDA - Date and time
-            "$ZDA" => gnss::zda::handle(sentence.as_str(), nav_system),
+        let (context_type, result): (&str, Result<_, ParseError>) = match (delimiter, kind) {
+            #[cfg(feature = "gnss")]
+            ("$", _) => match gnss::dispatch_gnss(kind.as_bytes(), sentence, nav_system, self) {
+                Some((gnss_type, result)) => {
+                    #[cfg(feature = "ais")]
+                    let result = result.map(|msg| msg.with_station(station));
+                    let result = result
+                        .and_then(|msg| self.check_gnss_position(msg))
+                        .map(|msg| self.normalize_gnss_angles(msg))
+                        .map(|msg| self.propagate_gnss_date(msg));
+                    (gnss_type, result)
+                }
+                None => {
+                    return Err(ParseError::UnsupportedSentenceType(format!(
+                        "Unsupported sentence type: {}{}",
+                        delimiter, kind
+                    )));
+                }
+            },
 
             // Received AIS data from other or own vessel
-            "!VDM" | "!VDO" => {
-                let own_vessel = sentence_type.as_str() == "!VDO";
+            #[cfg(feature = "ais")]
+            ("!", "VDM") | ("!", "VDO") => {
+                let own_vessel = kind == "VDO";
+                let sentence_type = if own_vessel { "!VDO" } else { "!VDM" };
                 let mut fragment_count = 0;
                 let mut fragment_number = 0;
                 let mut message_id = None;
                 let mut radio_channel_code = None;
                 let mut payload_string: String = "".into();
+                let mut fill_bits = 0;
                 for (num, s) in sentence.split(',').enumerate() {
                     match num {
                         1 => {
@@ -446,7 +1728,7 @@ impl NmeaParser {
                                 }
                                 Err(_) => {
                                     return Err(ParseError::InvalidSentence(format!(
-                                        "Failed to parse fragment count: {}",
+                                        "Failed to parse fragment number: {}",
                                         s
                                     )));
                                 }
@@ -463,48 +1745,89 @@ impl NmeaParser {
                             payload_string = s.to_string();
                         }
                         6 => {
-                            // fill bits
+                            fill_bits = s
+                                .split('*')
+                                .next()
+                                .and_then(|s| s.parse::<u8>().ok())
+                                .filter(|n| *n <= 5)
+                                .unwrap_or(0);
                         }
                         _ => {}
                     }
                 }
 
-                // Try parse the payload
-                let mut bv: Option<BitVec> = None;
+                // Reject inconsistent fragment metadata, which would never complete
+                if fragment_count == 0 {
+                    return Err(ParseError::InvalidSentence(
+                        "Fragment count must be at least 1".to_string(),
+                    ));
+                }
+                if fragment_number == 0 || fragment_number > fragment_count {
+                    return Err(ParseError::InvalidSentence(format!(
+                        "Fragment number {} out of range 1..={}",
+                        fragment_number, fragment_count
+                    )));
+                }
+
+                // AIS message type is in the first fragment. Fragments of rejected types aren't
+                // stored, but an empty marker is left for the second fragment.
+                let rejected = fragment_number == 1
+                    && !self.settings.filter.accepts_all_ais_types()
+                    && matches!(peek_ais_message_type(&payload_string),
+                        Some(t) if !self.settings.filter.accepts_ais_type(t));
+
+                // Collect the complete payload
+                let mut payload: Option<String> = None;
                 match fragment_count {
-                    1 => bv = parse_payload(&payload_string).ok(),
+                    1 if rejected => return Ok(ParsedMessage::Ignored),
+                    1 => payload = Some(payload_string),
                     2 => {
                         if let Some(msg_id) = message_id {
                             let key1 = make_fragment_key(
-                                &sentence_type.to_string(),
+                                sentence_type,
                                 msg_id,
                                 fragment_count,
                                 1,
                                 radio_channel_code.unwrap_or(""),
                             );
                             let key2 = make_fragment_key(
-                                &sentence_type.to_string(),
+                                sentence_type,
                                 msg_id,
                                 fragment_count,
                                 2,
                                 radio_channel_code.unwrap_or(""),
                             );
                             match fragment_number {
+                                1 if rejected => {
+                                    if self.pull_string(key2).is_none() {
+                                        self.push_string(key1, String::new());
+                                    }
+                                    return Ok(ParsedMessage::Ignored);
+                                }
                                 1 => {
                                     if let Some(p) = self.pull_string(key2) {
                                         let mut payload_string_combined = payload_string;
                                         payload_string_combined.push_str(p.as_str());
-                                        bv = parse_payload(&payload_string_combined).ok();
+                                        payload = Some(payload_string_combined);
+                                        // Fill bits of the last fragment aren't known
+                                        fill_bits = 0;
                                     } else {
+                                        // Oversized fragments aren't stored
+                                        check_ais_payload_length(&payload_string)?;
                                         self.push_string(key1, payload_string);
                                     }
                                 }
                                 2 => {
                                     if let Some(p) = self.pull_string(key1) {
+                                        if p.is_empty() {
+                                            // The first fragment was rejected by the filter
+                                            return Ok(ParsedMessage::Ignored);
+                                        }
                                         let mut payload_string_combined = p;
                                         payload_string_combined.push_str(payload_string.as_str());
-                                        bv = parse_payload(&payload_string_combined).ok();
+                                        payload = Some(payload_string_combined);
                                     } else {
+                                        check_ais_payload_length(&payload_string)?;
                                         self.push_string(key2, payload_string);
                                     }
                                 }
@@ -513,6 +1836,10 @@ impl NmeaParser {
                                         "Unexpected NMEA fragment number: {}/{}",
                                         fragment_number, fragment_count
                                     );
+                                    self.add_warning(ParseWarning::UnexpectedFragmentNumber {
+                                        fragment_number,
+                                        fragment_count,
+                                    });
                                 }
                             }
                         } else {
@@ -520,6 +1847,7 @@ impl NmeaParser {
                                 "NMEA message_id missing from {} than supported 2",
                                 sentence_type
                             );
+                            self.add_warning(ParseWarning::MissingMessageId);
                         }
                     }
                     _ => {
@@ -527,94 +1855,45 @@ impl NmeaParser {
                             "NMEA sentence fragment count greater ({}) than supported 2",
                             fragment_count
                         );
+                        self.add_warning(ParseWarning::UnsupportedFragmentCount(fragment_count));
                     }
                 }
 
-                if let Some(bv) = bv {
-                    let message_type = pick_u64(&bv, 0, 6);
-                    match message_type {
-                        // Position report with SOTDMA/ITDMA
-                        1..=3 => ais::vdm_t1t2t3::handle(&bv, station, own_vessel),
-                        // Base station report
-                        4 => ais::vdm_t4::handle(&bv, station, own_vessel),
-                        // Ship static voyage related data
-                        5 => ais::vdm_t5::handle(&bv, station, own_vessel),
-                        // Addressed binary message
-                        6 => ais::vdm_t6::handle(&bv, station, own_vessel),
-                        // Binary acknowledge
-                        7 => {
-                            // TODO: implementation
-                            Err(ParseError::UnsupportedSentenceType(format!(
-                                "Unsupported {} message type: {}",
-                                sentence_type, message_type
-                            )))
-                        }
-                        // Binary broadcast message
-                        8 => {
-                            // TODO: implementation
-                            Err(ParseError::UnsupportedSentenceType(format!(
-                                "Unsupported {} message type: {}",
-                                sentence_type, message_type
-                            )))
+                // Decode the complete payload and skip payloads which have been seen recently
+                let bv = match payload {
+                    Some(payload) => {
+                        let bv = decode_ais_payload(&payload)?;
+                        if self.is_duplicate_payload(&payload) {
+                            return Ok(ParsedMessage::Duplicate);
                         }
-                        // Standard SAR aircraft position report
-                        9 => ais::vdm_t9::handle(&bv, station, own_vessel),
-                        // UTC and Date inquiry
-                        10 => ais::vdm_t10::handle(&bv, station, own_vessel),
-                        // UTC and date response
-                        11 => ais::vdm_t11::handle(&bv, station, own_vessel),
-                        // Addressed safety related message
-                        12 => ais::vdm_t12::handle(&bv, station, own_vessel),
-                        // Safety related acknowledge
-                        13 => ais::vdm_t13::handle(&bv, station, own_vessel),
-                        // Safety related broadcast message
-                        14 => ais::vdm_t14::handle(&bv, station, own_vessel),
-                        // Interrogation
-                        15 => ais::vdm_t15::handle(&bv, station, own_vessel),
-                        // Assigned mode command
-                        16 => ais::vdm_t16::handle(&bv, station, own_vessel),
-                        // GNSS binary broadcast message
-                        17 => ais::vdm_t17::handle(&bv, station, own_vessel),
-                        // Standard class B CS position report
-                        18 => ais::vdm_t18::handle(&bv, station, own_vessel),
-                        // Extended class B equipment position report
-                        19 => ais::vdm_t19::handle(&bv, station, own_vessel),
-                        // Data link management
-                        20 => ais::vdm_t20::handle(&bv, station, own_vessel),
-                        // Aids-to-navigation report
-                        21 => ais::vdm_t21::handle(&bv, station, own_vessel),
-                        // Channel management
-                        22 => ais::vdm_t22::handle(&bv, station, own_vessel),
-                        // Group assignment command
-                        23 => ais::vdm_t23::handle(&bv, station, own_vessel),
-                        // Class B CS static data report
-                        24 => ais::vdm_t24::handle(&bv, station, self, own_vessel),
-                        // Single slot binary message
-                        25 => ais::vdm_t25::handle(&bv, station, own_vessel),
-                        // Multiple slot binary message
-                        26 => ais::vdm_t26::handle(&bv, station, own_vessel),
-                        // Long range AIS broadcast message
-                        27 => ais::vdm_t27::handle(&bv, station, own_vessel),
-                        _ => Err(ParseError::UnsupportedSentenceType(format!(
-                            "Unsupported {} message type: {}",
-                            sentence_type, message_type
-                        ))),
+                        Some(bv)
                     }
+                    None => None,
+                };
+
+                let radio_channel = radio_channel_code.and_then(|c| c.chars().next());
+                let result = if let Some(bv) = bv {
+                    let message_id = message_id.filter(|id| *id <= 9).map(|id| id as u8);
+                    ais::dispatch_message(&bv, fill_bits, station, radio_channel, own_vessel, self)
+                        .map(|mut msg| {
+                            msg.set_message_id(message_id);
+                            msg
+                        })
                 } else {
                     Ok(ParsedMessage::Incomplete)
-                }
+                };
+                (&sentence_type[1..], result)
             }
-            "$DPT" => gnss::dpt::handle(sentence.as_str()),
-            "$DBS" => gnss::dbs::handle(sentence.as_str()),
-            "$MTW" => gnss::mtw::handle(sentence.as_str()),
-            "$VHW" => gnss::vhw::handle(sentence.as_str()),
-            "$HDT" => gnss::hdt::handle(sentence.as_str()),
-            "$MWV" => gnss::mwv::handle(sentence.as_str()),
-            _ => Err(ParseError::UnsupportedSentenceType(format!(
-                "Unsupported sentence type: {}",
-                sentence_type
-            ))),
-        }
+            _ => {
+                return Err(ParseError::UnsupportedSentenceType(format!(
+                    "Unsupported sentence type: {}{}",
+                    delimiter, kind
+                )));
+            }
+        };
+
+        // Attach the sentence to the possible error
+        result.map_err(|e| e.with_context(context_type, original_sentence))
     }
 }
 
@@ -631,16 +1910,18 @@ mod test {
             ))
         );
         assert_eq!(
-            p.parse_sentence("$WIMWV,295.4,T,"),
+            p.parse_sentence("$WIMWV,295.4,T,")
+                .map_err(|e| e.inner_error().clone()),
             Err(ParseError::CorruptedSentence(
                 "pick string for \"wind_speed_knots\" was None".to_string()
             ))
         );
         assert_eq!(
             p.parse_sentence("!AIVDM,not,a,valid,nmea,string,0*00"),
-            Err(ParseError::CorruptedSentence(
-                "Corrupted NMEA sentence: \"17\" != \"00\"".to_string()
-            ))
+            Err(ParseError::Checksum(ChecksumError::Mismatch {
+                calculated: 0x17,
+                given: *b"00"
+            }))
         );
         assert_eq!(
             p.parse_sentence("!"),
@@ -648,7 +1929,139 @@ mod test {
                 "Invalid NMEA sentence: !".to_string()
             ))
         );
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g2{8CQ,0")
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::CorruptedSentence(
+                "Invalid character in AIS payload: '{'".to_string()
+            ))
+        );
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TK jM8h6g2P8CQ,0")
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::CorruptedSentence(
+                "Invalid character in AIS payload: ' '".to_string()
+            ))
+        );
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g2P8C~,0")
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::CorruptedSentence(
+                "Invalid character in AIS payload: '~'".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_sentence_with_raw() {
+        let mut p = NmeaParser::new();
+        let sentence = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        match p.parse_sentence_with_raw(sentence) {
+            Ok((ParsedMessage::Gga(gga), raw)) => {
+                assert_eq!(raw, sentence);
+                assert_eq!(gga.satellite_count, Some(8));
+            }
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+
+        // Incomplete messages carry the raw fragment too
+        let sentence =
+            "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C";
+        assert_eq!(
+            p.parse_sentence_with_raw(sentence),
+            Ok((ParsedMessage::Incomplete, sentence.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_register_binary_decoder() {
+        let mut p = NmeaParser::new();
+        p.register_binary_decoder(669, 11, |bv| {
+            Ok(ParsedMessage::BinaryAddressedMessage(
+                ais::BinaryAddressedMessage {
+                    mmsi: pick_u64(bv, 8, 30) as u32,
+                    fid: 99,
+                    ..Default::default()
+                },
+            ))
+        });
+        match p.parse_sentence("!AIVDM,1,1,,B,6B?n;be:cbapalgc;i6?Ow4,2*4A") {
+            Ok(ParsedMessage::BinaryAddressedMessage(bam)) => {
+                assert_eq!(bam.mmsi, 150834090);
+                assert_eq!(bam.fid, 99);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Clones of the parser keep the decoder
+        let mut q = p.clone();
+        match q.parse_sentence("!AIVDM,1,1,,B,6B?n;be:cbapalgc;i6?Ow4,2*4A") {
+            Ok(ParsedMessage::BinaryAddressedMessage(bam)) => {
+                assert_eq!(bam.fid, 99);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Messages with other DAC/FID are not affected
+        let mut p = NmeaParser::new();
+        p.register_binary_decoder(669, 12, |_| {
+            Err(ParseError::InvalidSentence("Unexpected call".to_string()))
+        });
+        match p.parse_sentence("!AIVDM,1,1,,B,6B?n;be:cbapalgc;i6?Ow4,2*4A") {
+            Ok(ParsedMessage::BinaryAddressedMessage(bam)) => {
+                assert_eq!(bam.fid, 11);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error_context() {
+        let mut p = NmeaParser::new();
+        let sentence = "$GPGGA,123519,4807.038,N,01131.000,E,1,x,0.9,545.4,M,46.9,M,,";
+        match p.parse_sentence(sentence) {
+            Err(e) => {
+                assert_eq!(e.sentence_type(), Some("GGA"));
+                assert_eq!(e.sentence(), Some(sentence));
+                assert_eq!(
+                    e.inner_error(),
+                    &ParseError::InvalidSentence("Failed to parse field 7: x".to_string())
+                );
+                assert!(e.to_string().contains("GGA"));
+                assert!(e.to_string().contains(sentence));
+            }
+            Ok(_) => {
+                assert!(false);
+            }
+        }
+
+        // Errors which occur before the sentence type is known don't have context
+        let e = p.parse_sentence("$QQ,*2C").unwrap_err();
+        assert_eq!(e.sentence_type(), None);
+        assert_eq!(e.sentence(), None);
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format() {
+        // Compile-time check that the public result types are loggable with defmt
+        fn assert_format<T: defmt::Format>() {}
+        assert_format::<ParsedMessage>();
+        assert_format::<ParseError>();
+        assert_format::<PositionEvent>();
     }
+
     #[test]
     fn test_parse_prefix_chars() {
         // Try a sentence with prefix characters
@@ -657,6 +2070,104 @@ mod test {
             .parse_sentence(",1277,-106*35\r\n!AIVDM,1,1,,A,152IS=iP?w<tSF0l4Q@>4?wp0H:;,0*2")
             .ok()
             .is_some());
+
+        // Sequence tag of a raw feed
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "seq123 $GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47",
+        ) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.satellite_count, Some(8));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert!(p.take_warnings().is_empty());
+        assert!(matches!(
+            p.parse_sentence("seq124 GPGGA,123519"),
+            Err(ParseError::InvalidSentence(_))
+        ));
+    }
+
+    #[test]
+    fn test_gnss_station() {
+        let mut p = NmeaParser::new();
+
+        // AIS base station
+        match p.parse_sentence("$ABGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*53")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.station, Some(ais::Station::BaseStation));
+                assert_eq!(gga.source, gnss::NavigationSystem::Other);
+                assert_eq!(gga.satellite_count, Some(8));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // AIS aid to navigation station
+        match p.parse_sentence("$ANGLL,4916.45,N,12311.12,W,225444,A,D*41") {
+            Ok(ParsedMessage::Gll(gll)) => {
+                assert_eq!(gll.station, Some(ais::Station::AidToNavigationStation));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // GNSS receiver
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.station, None);
+                assert_eq!(gga.source, gnss::NavigationSystem::Gps);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_garbage_prefix() {
+        let mut p = NmeaParser::new();
+
+        // UTF-8 byte order mark of a log file
+        match p.parse_sentence(
+            "\u{feff}$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r\n",
+        ) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.satellite_count, Some(8));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(p.take_warnings(), vec![ParseWarning::SkippedPrefix(3)]);
+
+        // ANSI color code
+        match p.parse_sentence("\x1b[32m!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.mmsi, 371798000);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(p.take_warnings(), vec![ParseWarning::SkippedPrefix(5)]);
+
+        // Checksum is calculated without the prefix
+        assert_eq!(
+            p.parse_sentence(
+                "\u{feff}$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48"
+            ),
+            Err(ParseError::Checksum(ChecksumError::Mismatch {
+                calculated: 0x47,
+                given: *b"48"
+            }))
+        );
     }
 
     #[test]
@@ -680,17 +2191,113 @@ mod test {
     }
 
     #[test]
-    fn test_parse_invalid_utc() {
-        // Try a sentence with invalite utc
+    fn test_input_length_limits() {
         let mut p = NmeaParser::new();
+
+        // Oversized line
+        let sentence = format!("!AIVDM,1,1,,A,{},0", "0".repeat(300));
         assert_eq!(
-            p.parse_sentence("!AIVDM,1,1,,B,4028iqT47wP00wGiNbH8H0700`2H,0*13"),
-            Err(ParseError::InvalidSentence(String::from(
-                "Failed to parse Utc Date from y:4161 m:15 d:31 h:0 m:0 s:0"
-            )))
+            p.parse_sentence(&sentence)
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::InvalidSentence(
+                "Sentence length 316 exceeds the maximum of 256".to_string()
+            ))
+        );
+
+        // Oversized payload fragment isn't stored
+        p.set_max_sentence_length(100_000);
+        let sentence = format!("!AIVDM,2,1,5,A,{},0", "0".repeat(60000));
+        assert_eq!(
+            p.parse_sentence(&sentence)
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::InvalidSentence(
+                "AIS payload length 60000 exceeds the maximum of 168".to_string()
+            ))
+        );
+        assert_eq!(p.strings_count(), 0);
+        let sentence = format!("!AIVDM,2,2,6,A,{},0", "0".repeat(60000));
+        assert!(p.parse_sentence(&sentence).is_err());
+        assert_eq!(p.strings_count(), 0);
+
+        // Oversized single fragment payload
+        let sentence = format!("!AIVDM,1,1,,A,{},0", "0".repeat(60000));
+        assert_eq!(
+            p.parse_sentence(&sentence)
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::InvalidSentence(
+                "AIS payload length 60000 exceeds the maximum of 168".to_string()
+            ))
+        );
+
+        // Combined payload too long
+        let sentence = format!("!AIVDM,2,1,5,A,{},0", "0".repeat(100));
+        assert_eq!(p.parse_sentence(&sentence), Ok(ParsedMessage::Incomplete));
+        let sentence = format!("!AIVDM,2,2,5,A,{},0", "0".repeat(100));
+        assert_eq!(
+            p.parse_sentence(&sentence)
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::InvalidSentence(
+                "AIS payload length 200 exceeds the maximum of 168".to_string()
+            ))
+        );
+        assert_eq!(p.strings_count(), 0);
+
+        // Lower limit
+        p.set_max_sentence_length(82);
+        assert!(p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_require_checksum() {
+        let valid = "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40";
+        let missing = "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0";
+        let truncated = "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*4";
+
+        // Checksum not required (default)
+        let mut p = NmeaParser::new();
+        assert!(p.parse_sentence(valid).is_ok());
+        assert!(p.parse_sentence(missing).is_ok());
+        assert!(p.parse_sentence(truncated).is_ok());
+
+        // Checksum required
+        p.set_require_checksum(true);
+        assert!(p.parse_sentence(valid).is_ok());
+        assert_eq!(
+            p.parse_sentence(missing),
+            Err(ParseError::Checksum(ChecksumError::Missing))
+        );
+        assert_eq!(
+            p.parse_sentence(truncated),
+            Err(ParseError::Checksum(ChecksumError::Truncated))
+        );
+
+        // GNSS sentences are checked too
+        assert_eq!(
+            p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,"),
+            Err(ParseError::Checksum(ChecksumError::Missing))
         );
     }
 
+    #[test]
+    fn test_parse_invalid_utc() {
+        // Try a sentence with invalite utc
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,B,4028iqT47wP00wGiNbH8H0700`2H,0*13") {
+            Ok(ParsedMessage::BaseStationReport(bsr)) => {
+                assert_eq!(bsr.mmsi, 2240998);
+                assert_eq!(bsr.timestamp, None);
+            }
+            Ok(_) => {
+                assert!(false);
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
     #[test]
     fn test_parse_proprietary() {
         /* FIXME: The test fails
@@ -741,23 +2348,23 @@ mod test {
         let mut p = NmeaParser::new();
 
         // String test
-        p.push_string("a".into(), "b".into());
+        p.context().push_string("a".into(), "b".into());
         assert_eq!(p.strings_count(), 1);
-        p.push_string("c".into(), "d".into());
+        p.context().push_string("c".into(), "d".into());
         assert_eq!(p.strings_count(), 2);
-        p.pull_string("a".into());
+        p.context().pull_string("a".into());
         assert_eq!(p.strings_count(), 1);
-        p.pull_string("c".into());
+        p.context().pull_string("c".into());
         assert_eq!(p.strings_count(), 0);
 
         // VesselStaticData test
-        p.push_vsd(1, Default::default());
+        p.context().push_vsd(1, Default::default());
         assert_eq!(p.vsds_count(), 1);
-        p.push_vsd(2, Default::default());
+        p.context().push_vsd(2, Default::default());
         assert_eq!(p.vsds_count(), 2);
-        p.pull_vsd(1);
+        p.context().pull_vsd(1);
         assert_eq!(p.vsds_count(), 1);
-        p.pull_vsd(2);
+        p.context().pull_vsd(2);
         assert_eq!(p.vsds_count(), 0);
     }
 
@@ -779,10 +2386,664 @@ mod test {
         assert_eq!(vsd(0).country(), None);
     }
 
+    #[test]
+    fn test_as_position_event() {
+        let mut p = NmeaParser::new();
+
+        // GNSS position
+        let pe = p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+            .ok()
+            .and_then(|pm| pm.as_position_event())
+            .unwrap();
+        assert_eq!(pe.source, PositionSource::Gnss(gnss::NavigationSystem::Gps));
+        assert_eq!(pe.mmsi, None);
+        assert::close(pe.lat, 48.117, 0.001);
+        assert::close(pe.lon, 11.517, 0.001);
+        assert_eq!(
+            pe.timestamp,
+            Utc.with_ymd_and_hms(2000, 1, 1, 12, 35, 19).single()
+        );
+        assert_eq!(pe.sog, None);
+        assert_eq!(pe.cog, None);
+
+        // AIS position
+        let pe = p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .ok()
+            .and_then(|pm| pm.as_position_event())
+            .unwrap();
+        assert_eq!(pe.source, PositionSource::Ais(ais::Station::MobileStation));
+        assert_eq!(pe.mmsi, Some(371798000));
+        assert::close(pe.lat, 48.382, 0.001);
+        assert::close(pe.lon, -123.395, 0.001);
+        assert_eq!(pe.timestamp, None);
+        assert_eq!(pe.sog, Some(12.3));
+        assert_eq!(pe.cog, Some(224.0));
+
+        // AIS position with the timestamp resolved from the reference time
+        p.set_current_time(
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 20)
+                .single()
+                .unwrap(),
+        );
+        let pe = p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .ok()
+            .and_then(|pm| pm.as_position_event())
+            .unwrap();
+        assert_eq!(
+            pe.timestamp,
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 33).single()
+        );
+
+        // Message without position
+        assert_eq!(ParsedMessage::Incomplete.as_position_event(), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let rmc = "$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67";
+        let gga = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        let vdm = "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A";
+        let mut p = NmeaParser::new();
+        p.set_propagate_date(true);
+        p.set_dedup_window(10);
+        p.set_current_time(
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 20)
+                .single()
+                .unwrap(),
+        );
+        p.parse_sentence(rmc).ok();
+        p.parse_sentence(vdm).ok();
+        p.parse_sentence(
+            "!AIVDM,2,1,3,B,55P5TL01VIaAL@7WKO@mBplU@<PDhh000000001S;AJ::4A80?4i@E53,0",
+        )
+        .ok();
+        p.parse_sentence("\u{feff}$INSTN,05*67").ok();
+        assert_eq!(p.strings_count(), 1);
+        assert_eq!(p.stn_talker_id(), Some(5));
+
+        p.reset();
+        assert_eq!(p.strings_count(), 0);
+        assert_eq!(p.stn_talker_id(), None);
+        assert!(p.take_warnings().is_empty());
+
+        // The reference times are gone
+        match p.parse_sentence(gga) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(
+                    gga.timestamp,
+                    Utc.with_ymd_and_hms(2000, 1, 1, 12, 35, 19).single()
+                );
+            }
+            _ => assert!(false),
+        }
+        match p.parse_sentence(vdm) {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.timestamp, None);
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_dedup_window() {
+        let mut p = NmeaParser::new();
+        p.set_dedup_window(2);
+
+        // Same payload twice under different talker and channel
+        let s1 = "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A";
+        let s2 = "!BSVDM,1,1,,B,15RTgt0PAso;90TKcjM8h6g208CQ,0*50";
+        assert!(matches!(
+            p.parse_sentence(s1),
+            Ok(ParsedMessage::VesselDynamicData(_))
+        ));
+        assert_eq!(p.parse_sentence(s2), Ok(ParsedMessage::Duplicate));
+
+        // Different payloads
+        let s3 = "!AIVDM,1,1,,A,16SteH0P00Jt63hHaa6SagvJ087r,0*42";
+        let s4 = "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40";
+        assert!(matches!(
+            p.parse_sentence(s3),
+            Ok(ParsedMessage::VesselDynamicData(_))
+        ));
+        assert!(matches!(
+            p.parse_sentence(s4),
+            Ok(ParsedMessage::VesselDynamicData(_))
+        ));
+
+        // The first payload has been evicted from the window
+        assert!(matches!(
+            p.parse_sentence(s1),
+            Ok(ParsedMessage::VesselDynamicData(_))
+        ));
+        assert_eq!(p.parse_sentence(s1), Ok(ParsedMessage::Duplicate));
+
+        // Shrinking the window evicts the oldest payloads
+        p.set_dedup_window(1);
+        assert_eq!(p.state.recent_payload_set.len(), 1);
+        assert!(matches!(
+            p.parse_sentence(s4),
+            Ok(ParsedMessage::VesselDynamicData(_))
+        ));
+        assert_eq!(p.parse_sentence(s4), Ok(ParsedMessage::Duplicate));
+        assert_eq!(
+            p.state.recent_payloads.len(),
+            p.state.recent_payload_set.len()
+        );
+
+        // Disabled de-duplication
+        p.set_dedup_window(0);
+        assert!(p.state.recent_payload_set.is_empty());
+        assert!(matches!(
+            p.parse_sentence(s1),
+            Ok(ParsedMessage::VesselDynamicData(_))
+        ));
+    }
+
     /// Create a `VesselStaticData` with the given MMSI
     fn vsd(mmsi: u32) -> ais::VesselStaticData {
         let mut vsd = ais::VesselStaticData::default();
         vsd.mmsi = mmsi;
         vsd
     }
+
+    #[test]
+    fn test_enabled_types() {
+        let mut p = NmeaParser::new();
+        p.set_enabled_types(&["GGA", "VDM"]);
+
+        // Disabled GSV is ignored without storing it for reassembly
+        assert_eq!(
+            p.parse_sentence(
+                "$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*74"
+            ),
+            Ok(ParsedMessage::Ignored)
+        );
+        assert_eq!(p.strings_count(), 0);
+
+        // Enabled types are decoded
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+        {
+            Ok(ParsedMessage::Gga(_)) => {}
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Checksum is still verified for disabled types
+        assert!(p
+            .parse_sentence("$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*00")
+            .is_err());
+
+        p.enable_all_types();
+        assert_eq!(
+            p.parse_sentence(
+                "$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*74"
+            ),
+            Ok(ParsedMessage::Incomplete)
+        );
+        assert_eq!(p.strings_count(), 1);
+    }
+
+    #[test]
+    fn test_message_filter() {
+        let vdm_t1 = "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A";
+        let vdm_t5_1 =
+            "!AIVDM,2,1,1,,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*5D";
+        let vdm_t5_2 = "!AIVDM,2,2,1,,88888888880,2*64";
+        let gsv = "$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*74";
+
+        let mut p = NmeaParser::new();
+        let mut filter = MessageFilter::new();
+        filter
+            .exclude_sentence_types(&["GSV"])
+            .exclude_ais_types(&[5]);
+        p.set_message_filter(filter);
+
+        // Excluded sentence type
+        assert_eq!(p.parse_sentence(gsv), Ok(ParsedMessage::Ignored));
+        assert_eq!(p.strings_count(), 0);
+
+        // Excluded AIS type in fragment order and in reverse order
+        assert_eq!(p.parse_sentence(vdm_t5_1), Ok(ParsedMessage::Ignored));
+        assert_eq!(p.parse_sentence(vdm_t5_2), Ok(ParsedMessage::Ignored));
+        assert_eq!(p.strings_count(), 0);
+        assert_eq!(p.parse_sentence(vdm_t5_2), Ok(ParsedMessage::Incomplete));
+        assert_eq!(p.parse_sentence(vdm_t5_1), Ok(ParsedMessage::Ignored));
+        assert_eq!(p.strings_count(), 0);
+
+        // Included AIS type is decoded
+        match p.parse_sentence(vdm_t1) {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => {
+                assert_eq!(vdd.mmsi, 371798000);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Only the included AIS types are decoded
+        let mut filter = MessageFilter::new();
+        filter.include_ais_types(&[5]);
+        p.set_message_filter(filter);
+        assert_eq!(p.parse_sentence(vdm_t1), Ok(ParsedMessage::Ignored));
+        assert_eq!(p.parse_sentence(vdm_t5_1), Ok(ParsedMessage::Incomplete));
+        match p.parse_sentence(vdm_t5_2) {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.mmsi, 351759000);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        match p.parse_sentence(gsv) {
+            Ok(ParsedMessage::Incomplete) => {}
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_vdm_inconsistent_fragments() {
+        let mut p = NmeaParser::new();
+
+        // Fragment count 0
+        assert_eq!(
+            p.parse_sentence("!AIVDM,0,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4B"),
+            Err(ParseError::InvalidSentence(
+                "Fragment count must be at least 1".to_string()
+            ))
+        );
+
+        // Fragment number 0
+        assert_eq!(
+            p.parse_sentence(
+                "!AIVDM,2,0,3,B,55P5TL01VIaAL@7WKO@mBplU@<PDhh000000001S;AJ::4A80?4i@E53,0*3F"
+            ),
+            Err(ParseError::InvalidSentence(
+                "Fragment number 0 out of range 1..=2".to_string()
+            ))
+        );
+
+        // Fragment number exceeds the count
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,2,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*49"),
+            Err(ParseError::InvalidSentence(
+                "Fragment number 2 out of range 1..=1".to_string()
+            ))
+        );
+        assert_eq!(
+            p.parse_sentence("!AIVDM,2,3,3,B,1@0000000000000,2*54"),
+            Err(ParseError::InvalidSentence(
+                "Fragment number 3 out of range 1..=2".to_string()
+            ))
+        );
+
+        // Nothing was stored for reassembly
+        assert_eq!(p.strings_count(), 0);
+    }
+
+    #[test]
+    fn test_reject_implausible_positions() {
+        let null_island = "$GPGGA,123519,0000.000,N,00000.000,E,1,08,0.9,545.4,M,46.9,M,,";
+        let out_of_range = "$GPGGA,123519,9507.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,";
+
+        // Accepted by default
+        let mut p = NmeaParser::new();
+        assert!(p.parse_sentence(null_island).is_ok());
+        assert!(p.parse_sentence(out_of_range).is_ok());
+
+        p.set_reject_implausible_positions(true);
+        assert_eq!(
+            p.parse_sentence(null_island)
+                .map_err(|e| e.inner_error().clone()),
+            Err(ParseError::InvalidSentence(
+                "Implausible position: 0, 0".to_string()
+            ))
+        );
+        assert!(p.parse_sentence(out_of_range).is_err());
+        assert!(p
+            .parse_sentence("$GPRMC,225446,A,0000.00,N,00000.00,E,000.5,054.7,191120,020.3,E")
+            .is_err());
+
+        // Valid positions and sentences without a position pass
+        assert!(p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+            .is_ok());
+        assert!(p.parse_sentence("$GPGGA,123519,,,,,0,00,,,M,,M,,").is_ok());
+    }
+
+    #[test]
+    fn test_trailing_cr_lf() {
+        let mut p = NmeaParser::new();
+        match p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r\n")
+        {
+            Ok(ParsedMessage::Gga(gga)) => assert_eq!(gga.satellite_count, Some(8)),
+            _ => assert!(false),
+        }
+
+        // Without checksum the CR LF would end up in the last field
+        match p.parse_sentence("$HEHDT,274.07,T\r\n") {
+            Ok(ParsedMessage::Hdt(hdt)) => assert_eq!(hdt.heading_true, Some(274.07)),
+            _ => assert!(false),
+        }
+        match p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A \n") {
+            Ok(ParsedMessage::VesselDynamicData(vdd)) => assert_eq!(vdd.mmsi, 371798000),
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_radio_channel() {
+        let mut p = NmeaParser::new();
+        let msg = p.parse_sentence("!AIVDM,1,1,,B,:5MlU41GMK6@,0*6C").unwrap();
+        assert_eq!(msg.radio_channel(), Some('B'));
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,,2,15RTgt0PAso;90TKcjM8h6g208CQ,0*39")
+            .unwrap();
+        assert_eq!(msg.as_vessel_dynamic().unwrap().radio_channel, Some('2'));
+        assert_eq!(msg.radio_channel(), Some('B'));
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,,,15RTgt0PAso;90TKcjM8h6g208CQ,0*0B")
+            .unwrap();
+        assert_eq!(msg.radio_channel(), None);
+        let msg = p.parse_sentence("$HEHDT,274.07,T").unwrap();
+        assert_eq!(msg.radio_channel(), None);
+    }
+
+    #[test]
+    fn test_message_id() {
+        let mut p = NmeaParser::new();
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,3,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*79")
+            .unwrap();
+        assert_eq!(msg.message_id(), Some(3));
+        assert_eq!(msg.as_vessel_dynamic().unwrap().message_id, Some(3));
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .unwrap();
+        assert_eq!(msg.message_id(), None);
+
+        // Two fragments
+        p.parse_sentence(
+            "!AIVDM,2,1,1,,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*5D",
+        )
+        .unwrap();
+        let msg = p.parse_sentence("!AIVDM,2,2,1,,88888888880,2*64").unwrap();
+        assert_eq!(msg.as_vessel_static().unwrap().message_id, Some(1));
+    }
+
+    #[test]
+    fn test_parse_sentence_stateless() {
+        let p = NmeaParser::new();
+        let shared: &NmeaParser = &p;
+        match shared.parse_sentence_stateless(
+            "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r\n",
+        ) {
+            Ok(ParsedMessage::Gga(gga)) => assert_eq!(gga.satellite_count, Some(8)),
+            _ => {
+                assert!(false);
+            }
+        }
+        assert!(shared
+            .parse_sentence_stateless("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .unwrap()
+            .as_vessel_dynamic()
+            .is_some());
+
+        // Sentences which need the state
+        for sentence in [
+            "!AIVDM,2,1,1,,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*5D",
+            "!AIVDM,2,2,1,,88888888880,2*64",
+            "!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D",
+            "$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*74",
+            "$PSTN,01",
+        ] {
+            match shared.parse_sentence_stateless(sentence) {
+                Err(ParseError::RequiresState(_)) => {}
+                _ => {
+                    assert!(false);
+                }
+            }
+        }
+
+        // Errors are reported as usual
+        assert!(matches!(
+            shared.parse_sentence_stateless("$GPGGA,123519,4807.038,N*00"),
+            Err(ParseError::Checksum(_))
+        ));
+    }
+
+    #[test]
+    fn test_message_kind() {
+        let mut p = NmeaParser::new();
+        let msg = p.parse_sentence("$HEHDT,274.07,T").unwrap();
+        assert_eq!(msg.kind(), "Hdt");
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .unwrap();
+        assert_eq!(msg.kind(), "VesselDynamicData");
+        assert_eq!(ParsedMessage::Incomplete.kind(), "Incomplete");
+    }
+
+    #[test]
+    fn test_position_report() {
+        let mut p = NmeaParser::new();
+
+        // AIS
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .unwrap();
+        let report: &dyn PositionReport = msg.as_position_report().unwrap();
+        assert::close(report.latitude().unwrap_or(0.0), 48.382, 0.001);
+        assert::close(report.longitude().unwrap_or(0.0), -123.395, 0.001);
+        assert_eq!(report.sog_knots(), Some(12.3));
+        assert_eq!(report.cog(), Some(224.0));
+        assert_eq!(report.heading(), Some(215.0));
+        assert_eq!(report.fix_time_seconds(), None);
+
+        // GNSS
+        let msg = p
+            .parse_sentence("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67")
+            .unwrap();
+        let report = msg.as_position_report().unwrap();
+        assert::close(report.latitude().unwrap_or(0.0), 49.274, 0.001);
+        assert_eq!(report.sog_knots(), Some(0.5));
+        assert_eq!(report.cog(), Some(54.7));
+        assert_eq!(report.heading(), None);
+        assert_eq!(report.fix_time_seconds(), Some(82486));
+
+        // GGA with VTG
+        let gga = p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+            .unwrap();
+        assert_eq!(gga.as_position_report().unwrap().sog_knots(), None);
+        let vtg = p
+            .parse_sentence("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K")
+            .unwrap();
+        let fix = (gga.as_gga().unwrap().clone(), vtg.as_vtg().unwrap().clone());
+        let report: &dyn PositionReport = &fix;
+        assert::close(report.latitude().unwrap_or(0.0), 48.117, 0.001);
+        assert_eq!(report.sog_knots(), Some(5.5));
+        assert_eq!(report.cog(), Some(54.7));
+        assert_eq!(report.fix_time_seconds(), Some(45319));
+
+        // Not a position report
+        let msg = p.parse_sentence("$HEHDT,360.0,T").unwrap();
+        assert!(msg.as_position_report().is_none());
+    }
+
+    #[test]
+    fn test_strict_sentence_length() {
+        // 85 characters including CR LF
+        let long_gga = "$GPGGA,123519.000,4807.0380000,N,01131.0000000,E,1,08,0.9,545.4,M,46.9,M,0.0,000*47\r\n";
+        let mut p = NmeaParser::new();
+        assert!(p.parse_sentence(long_gga).is_ok());
+
+        p.set_strict(true);
+        match p.parse_sentence(long_gga) {
+            Err(ParseError::InvalidSentence(e)) => {
+                assert_eq!(e, "Sentence length 85 exceeds the maximum of 82");
+            }
+            _ => assert!(false),
+        }
+        assert!(p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+            .is_ok());
+        assert!(p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .is_ok());
+
+        // Prefixes count towards the maximum input length only
+        let tagged_gga = "\\s:r003669945,c:1241544035*4A\\$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        assert!(p.parse_sentence(tagged_gga).is_ok());
+        p.set_max_sentence_length(82);
+        match p.parse_sentence(tagged_gga) {
+            Err(ParseError::InvalidSentence(e)) => {
+                assert_eq!(e, "Sentence length 95 exceeds the maximum of 82");
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_normalize_angles() {
+        let hdt = "$HEHDT,360.0,T";
+        let vtg = "$GPVTG,-2.5,T,365.0,M,0.1,N,0.2,K";
+
+        // Returned as given by default
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(hdt) {
+            Ok(ParsedMessage::Hdt(hdt)) => assert_eq!(hdt.heading_true, Some(360.0)),
+            _ => assert!(false),
+        }
+
+        p.set_normalize_angles(true);
+        match p.parse_sentence(hdt) {
+            Ok(ParsedMessage::Hdt(hdt)) => assert_eq!(hdt.heading_true, Some(0.0)),
+            _ => assert!(false),
+        }
+        match p.parse_sentence(vtg) {
+            Ok(ParsedMessage::Vtg(vtg)) => {
+                assert_eq!(vtg.cog_true, Some(357.5));
+                assert_eq!(vtg.cog_magnetic, Some(5.0));
+                assert_eq!(vtg.sog_knots, Some(0.1));
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_parsed_message_conversions() {
+        use core::convert::TryFrom;
+
+        let mut p = NmeaParser::new();
+        let msg = p
+            .parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+            .unwrap();
+        assert_eq!(msg.as_gga().and_then(|gga| gga.satellite_count), Some(8));
+        assert_eq!(msg.as_rmc(), None);
+        assert_eq!(msg.as_vessel_dynamic(), None);
+        let gga = gnss::GgaData::try_from(msg.clone()).unwrap();
+        assert_eq!(gga.satellite_count, Some(8));
+
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .unwrap();
+        assert_eq!(msg.as_vessel_dynamic().map(|vdd| vdd.mmsi), Some(371798000));
+        let vdd = ais::VesselDynamicData::try_from(msg.clone()).unwrap();
+        assert_eq!(vdd.mmsi, 371798000);
+
+        // Mismatching variant returns the original message
+        assert_eq!(gnss::RmcData::try_from(msg.clone()), Err(msg));
+
+        // UTC/date responses convert to base station reports too
+        let msg = p
+            .parse_sentence("!AIVDM,1,1,,B,;4R33:1uUK2F`q?mOt@@GoQ00000,0*5D")
+            .unwrap();
+        assert_eq!(
+            msg.as_base_station_report().map(|bsr| bsr.message_type),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn test_propagate_date() {
+        let rmc = "$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67";
+        let gga = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        let gga_after_midnight = "$GPGGA,000010,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,";
+
+        // Date of GGA is 2000-01-01 by default
+        let mut p = NmeaParser::new();
+        p.parse_sentence(rmc).ok();
+        match p.parse_sentence(gga) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(
+                    gga.timestamp,
+                    Utc.with_ymd_and_hms(2000, 1, 1, 12, 35, 19).single()
+                );
+            }
+            _ => assert!(false),
+        }
+
+        // Date of the RMC applied
+        let mut p = NmeaParser::new();
+        p.set_propagate_date(true);
+        match p.parse_sentence(gga) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(
+                    gga.timestamp,
+                    Utc.with_ymd_and_hms(2000, 1, 1, 12, 35, 19).single()
+                );
+            }
+            _ => assert!(false),
+        }
+        p.parse_sentence(rmc).ok();
+        match p.parse_sentence(gga) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(
+                    gga.timestamp,
+                    Utc.with_ymd_and_hms(2020, 11, 19, 12, 35, 19).single()
+                );
+            }
+            _ => assert!(false),
+        }
+        match p.parse_sentence(gga_after_midnight) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(
+                    gga.timestamp,
+                    Utc.with_ymd_and_hms(2020, 11, 20, 0, 0, 10).single()
+                );
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn test_geo_types_point() {
+        use core::convert::TryFrom;
+
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67")
+        {
+            Ok(ParsedMessage::Rmc(rmc)) => {
+                let point = geo_types::Point::try_from(&rmc).unwrap();
+                // x is longitude and y is latitude
+                assert::close(point.x(), -123.185, 0.001);
+                assert::close(point.y(), 49.274, 0.001);
+            }
+            _ => assert!(false),
+        }
+        match p.parse_sentence("$GPRMC,225446,V,,,,,,,191120,,*") {
+            Ok(ParsedMessage::Rmc(rmc)) => {
+                assert_eq!(geo_types::Point::try_from(&rmc), Err(PositionNotAvailable));
+            }
+            _ => assert!(false),
+        }
+    }
 }
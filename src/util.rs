@@ -15,6 +15,7 @@ limitations under the License.
 */
 use super::*;
 
+#[cfg(feature = "time")]
 use chrono::Duration;
 
 const AIS_CHAR_BITS: usize = 6;
@@ -33,11 +34,248 @@ pub(crate) fn make_fragment_key(
     )
 }
 
+/// Maximum length of an NMEA 0183 sentence, including the start delimiter and the terminating
+/// carriage return and line feed.
+pub const MAX_NMEA_SENTENCE_LENGTH: usize = 82;
+
+/// Maximum number of bytes before the start delimiter stripped by `strip_sentence_prefix()`.
+pub(crate) const MAX_SENTENCE_PREFIX_LENGTH: usize = 16;
+
+/// Find a UTF-8 byte order mark or other short garbage, e.g. an ANSI color code, preceding the
+/// start delimiter. Only prefixes starting with a control or non-ASCII character and at most
+/// `MAX_SENTENCE_PREFIX_LENGTH` bytes long qualify, so that e.g. "x$GPGGA" isn't mistaken for a
+/// sentence. Return the sentence without the prefix and the prefix length in bytes.
+pub(crate) fn strip_sentence_prefix(sentence: &str) -> Option<(&str, usize)> {
+    let first = *sentence.as_bytes().first()?;
+    if (0x20..=0x7e).contains(&first) {
+        return None;
+    }
+    let start_idx = sentence
+        .bytes()
+        .take(MAX_SENTENCE_PREFIX_LENGTH + 1)
+        .position(|b| b == b'$' || b == b'!')?;
+    Some((&sentence[start_idx..], start_idx))
+}
+
+/// Split the sentence at the last '*' into the sentence part and the checksum part. The checksum
+/// part is `None` if there's no '*'. Characters following the two checksum digits (e.g.
+/// sentence extensions) are dropped. A truncated checksum part is shorter than two characters.
+pub(crate) fn split_checksum(sentence: &str) -> (&str, Option<&str>) {
+    match sentence.rfind('*') {
+        Some(pos) => {
+            let given = &sentence[(pos + 1)..];
+            (&sentence[0..pos], Some(given.get(0..2).unwrap_or(given)))
+        }
+        None => (sentence, None),
+    }
+}
+
+/// Calculate NMEA checksum of the sentence part, i.e. XOR of the characters between the start
+/// delimiter and the '*' character.
+pub(crate) fn calculate_checksum(sentence: &str) -> u8 {
+    sentence
+        .chars()
+        .skip(1)
+        .fold(0, |checksum, c| checksum ^ c as u8)
+}
+
+/// Test whether the given checksum digits (two uppercase hex digits) match the calculated
+/// checksum.
+pub(crate) fn checksum_matches(checksum: u8, given: &str) -> bool {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    given.as_bytes()
+        == [
+            HEX_DIGITS[(checksum >> 4) as usize],
+            HEX_DIGITS[(checksum & 0x0f) as usize],
+        ]
+}
+
+/// Make the error returned when the given checksum doesn't match the calculated one. Only the
+/// first two characters of `given` are kept. Nothing is allocated.
+pub(crate) fn checksum_mismatch_error(checksum: u8, given: &str) -> ParseError {
+    let mut given_chars = [b'?'; 2];
+    for (c, b) in given_chars.iter_mut().zip(given.bytes()) {
+        *c = b;
+    }
+    ParseError::Checksum(ChecksumError::Mismatch {
+        calculated: checksum,
+        given: given_chars,
+    })
+}
+
+/// Check that the given string is a structurally valid NMEA sentence without parsing its fields
+/// or touching any parser state. The sentence has to start with '$' or '!', consist of printable
+/// ASCII characters, be at most `MAX_NMEA_SENTENCE_LENGTH` characters long (including CR LF,
+/// which may be omitted) and have a valid checksum if it has one. A UTF-8 byte order mark or
+/// other up to 16 bytes starting with a control or non-ASCII character, e.g. an ANSI escape
+/// sequence, may precede the start delimiter. The errors are `ParseError::Structure` and
+/// `ParseError::Checksum`, so nothing is allocated.
+pub fn validate_sentence(sentence: &str) -> Result<(), ParseError> {
+    // CR LF terminator is optional but it's counted in the maximum length
+    let sentence = sentence
+        .strip_suffix("\r\n")
+        .or_else(|| sentence.strip_suffix('\n'))
+        .unwrap_or(sentence);
+    if let Some((rest, _)) = strip_sentence_prefix(sentence) {
+        // A skipped prefix is only accepted in front of an otherwise valid sentence
+        return validate_sentence(rest)
+            .map_err(|_| ParseError::Structure(StructureError::NoStartDelimiter));
+    }
+    if !sentence.starts_with(['$', '!']) {
+        return Err(ParseError::Structure(StructureError::NoStartDelimiter));
+    }
+    if sentence.len() + 2 > MAX_NMEA_SENTENCE_LENGTH {
+        return Err(ParseError::Structure(StructureError::TooLong(
+            sentence.len() + 2,
+        )));
+    }
+    if let Some(i) = sentence.bytes().position(|b| !(0x20..=0x7e).contains(&b)) {
+        return Err(ParseError::Structure(StructureError::InvalidCharacter(i)));
+    }
+    match split_checksum(sentence) {
+        (_, Some(given)) if given.len() < 2 => Err(ParseError::Checksum(ChecksumError::Truncated)),
+        (body, Some(given)) => {
+            let checksum = calculate_checksum(body);
+            if checksum_matches(checksum, given) {
+                Ok(())
+            } else {
+                Err(checksum_mismatch_error(checksum, given))
+            }
+        }
+        (_, None) => Ok(()),
+    }
+}
+
+/// Test whether the given string is a structurally valid NMEA sentence. See
+/// `validate_sentence()` for the checks. This function never allocates.
+pub fn is_valid_sentence(sentence: &str) -> bool {
+    validate_sentence(sentence).is_ok()
+}
+
+/// Test whether the given sentence is within `MAX_NMEA_SENTENCE_LENGTH` characters (including
+/// CR LF, which may be omitted). Characters before the start delimiter aren't counted. AIS
+/// sentences starting with '!' are exempt and always accepted.
+pub fn is_within_length_limit(sentence: &str) -> bool {
+    match standard_sentence_length(sentence) {
+        Some(len) => len <= MAX_NMEA_SENTENCE_LENGTH,
+        None => true,
+    }
+}
+
+/// Length of the sentence as counted by `is_within_length_limit()`: from the start delimiter
+/// including CR LF, which may be omitted. `None` is returned for AIS sentences.
+pub(crate) fn standard_sentence_length(sentence: &str) -> Option<usize> {
+    let sentence = sentence
+        .strip_suffix("\r\n")
+        .or_else(|| sentence.strip_suffix('\n'))
+        .unwrap_or(sentence);
+    match sentence.find(['$', '!']) {
+        Some(start_idx) if sentence[start_idx..].starts_with('!') => None,
+        Some(start_idx) => Some(sentence.len() - start_idx + 2),
+        None => Some(sentence.len() + 2),
+    }
+}
+
+/// Pick fragment count, fragment number and sequential message ID from the header of an AIS
+/// VDM/VDO sentence without decoding the payload. Applications reassembling multi-sentence
+/// messages themselves can use this to detect dropped fragments. `None` is returned if the
+/// sentence isn't VDM/VDO or the fragment fields are missing or inconsistent. The checksum
+/// isn't verified. This function never allocates.
+pub fn fragment_info(sentence: &str) -> Option<(u8, u8, Option<u64>)> {
+    let start_idx = sentence.find('!')?;
+    let (body, _) = split_checksum(&sentence[start_idx..]);
+    let mut fields = body.split(',');
+
+    let sentence_type = fields.next()?;
+    if !sentence_type.ends_with("VDM") && !sentence_type.ends_with("VDO") {
+        return None;
+    }
+    let fragment_count = fields.next()?.parse::<u8>().ok()?;
+    let fragment_number = fields.next()?.parse::<u8>().ok()?;
+    if fragment_number == 0 || fragment_number > fragment_count {
+        return None;
+    }
+    let message_id = fields.next().and_then(|s| s.parse::<u64>().ok());
+    Some((fragment_count, fragment_number, message_id))
+}
+
+/// Complete a hand-built sentence for output. The start delimiter is prepended if it's missing
+/// ('!' for the AIS encapsulation sentences VDM, VDO, ABM and BBM, '$' otherwise) and the
+/// checksum and CR LF are appended. An existing checksum and line terminator are replaced, so
+/// the function can be used to fix up edited sentences too.
+pub fn finalize_sentence(body: &str) -> String {
+    let body = body.trim_end_matches(['\r', '\n']);
+    let (body, _) = split_checksum(body);
+    let mut sentence = String::with_capacity(body.len() + 6);
+    if !body.starts_with(['$', '!']) {
+        let encapsulated = body
+            .split(',')
+            .next()
+            .and_then(|tag| tag.get(tag.len().saturating_sub(3)..))
+            .is_some_and(|t| matches!(t, "VDM" | "VDO" | "ABM" | "BBM"));
+        sentence.push(if encapsulated { '!' } else { '$' });
+    }
+    sentence.push_str(body);
+    let checksum = calculate_checksum(&sentence);
+    sentence.push_str(&format!("*{:02X}\r\n", checksum));
+    sentence
+}
+
+/// Return the reason why the sentence can't be parsed without the multi-sentence state of the
+/// parser, or `None` if it can. Malformed sentences return `None` and are left for the parser to
+/// report.
+pub(crate) fn stateful_sentence_reason(sentence: &str) -> Option<String> {
+    let start_idx = sentence.find(['$', '!'])?;
+    let (body, _) = split_checksum(sentence[start_idx..].trim_end());
+    let mut fields = body.split(',');
+    let tag = fields.next()?;
+    let sentence_type = tag.get(tag.len().saturating_sub(3)..)?;
+    match sentence_type {
+        "GSV" => Some("GSV sentences are grouped".to_string()),
+        "STN" => Some("STN sets the talker ID of the following sentences".to_string()),
+        "VDM" | "VDO" => {
+            if let Some((fragment_count, _, _)) = fragment_info(sentence) {
+                if fragment_count > 1 {
+                    return Some(format!(
+                        "{}-fragment {} message",
+                        fragment_count, sentence_type
+                    ));
+                }
+            }
+            let message_type = peek_ais_message_type(fields.nth(4)?)?;
+            match message_type {
+                5 | 24 => Some(format!(
+                    "AIS type {} is paired with other messages",
+                    message_type
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Return the AIS message type of the given VDM/VDO payload armored string without decoding the
+/// payload. The first character holds the message type.
+pub(crate) fn peek_ais_message_type(payload: &str) -> Option<u8> {
+    match payload.bytes().next()? {
+        c @ b'0'..=b'W' => Some(c - 48),
+        c @ b'`'..=b'w' => Some(c - 56),
+        _ => None,
+    }
+}
+
 /// Convert AIS VDM/VDO payload armored string into a `BitVec`.
 pub(crate) fn parse_payload(payload: &str) -> Result<BitVec, String> {
     let mut bv = BitVec::<usize, LocalBits>::with_capacity(payload.len() * 6);
     for c in payload.chars() {
-        let mut ci = (c as u8) - 48;
+        // Valid characters are '0'..='W' and '`'..='w'
+        let mut ci = match c {
+            '0'..='W' | '`'..='w' => (c as u8) - 48,
+            _ => {
+                return Err(format!("Invalid character in AIS payload: {:?}", c));
+            }
+        };
         if ci > 40 {
             ci -= 8;
         }
@@ -83,6 +321,19 @@ pub(crate) fn pick_i64(bv: &BitVec, index: usize, len: usize) -> i64 {
     }
 }
 
+/// Pick a 6-bit string from BitVec keeping the raw character codes. Field `char_count` defines
+/// the maximum string length in characters; characters beyond the end of the BitVec are left
+/// out.
+#[cfg(feature = "ais")]
+pub(crate) fn pick_6bit_string(bv: &BitVec, index: usize, char_count: usize) -> ais::Ais6BitString {
+    let codes: Vec<u8> = (0..char_count)
+        .map(|i| index + i * AIS_CHAR_BITS)
+        .take_while(|pos| *pos < bv.len())
+        .map(|pos| pick_u64(bv, pos, AIS_CHAR_BITS) as u8)
+        .collect();
+    ais::Ais6BitString::from_codes(&codes)
+}
+
 /// Pick a string from BitVec. Field `char_count` defines string length in characters.
 /// Characters consist of 6 bits.
 pub(crate) fn pick_string(bv: &BitVec, index: usize, char_count: usize) -> String {
@@ -104,7 +355,35 @@ pub(crate) fn pick_string(bv: &BitVec, index: usize, char_count: usize) -> Strin
     res
 }
 
+/// Pick UTC date and time of AIS types 4 and 11 (year, month, day, hour, minute and second).
+/// `None` is returned if any of the components has its "not available" value (year 0,
+/// month 0, day 0, hour 24, minute 60 or second 60), and an error if the combination isn't a
+/// valid date.
+pub(crate) fn pick_utc_date_time(
+    bv: &BitVec,
+    index: usize,
+) -> Result<Option<Timestamp>, ParseError> {
+    let year = pick_u64(bv, index, 14) as i32;
+    let month = pick_u64(bv, index + 14, 4) as u32;
+    let day = pick_u64(bv, index + 18, 5) as u32;
+    let hour = pick_u64(bv, index + 23, 5) as u32;
+    let minute = pick_u64(bv, index + 28, 6) as u32;
+    let second = pick_u64(bv, index + 34, 6) as u32;
+
+    // Check special cases for no value
+    if year == 0 || month == 0 || day == 0 || hour == 24 || minute == 60 || second == 60 {
+        return Ok(None);
+    }
+
+    #[cfg(feature = "time")]
+    let res = parse_ymdhs(year, month, day, hour, minute, second);
+    #[cfg(not(feature = "time"))]
+    let res = parse_valid_hms(hour, minute, second);
+    res.map(Some)
+}
+
 /// Pick ETA based on UTC month, day, hour and minute.
+#[cfg(feature = "time")]
 pub(crate) fn pick_eta(bv: &BitVec, index: usize) -> Result<Option<DateTime<Utc>>, ParseError> {
     pick_eta_with_now(
         bv,
@@ -113,9 +392,10 @@ pub(crate) fn pick_eta(bv: &BitVec, index: usize) -> Result<Option<DateTime<Utc>
     )
 }
 
-/// Pick ETA based on UTC month, day, hour and minute. Define also 'now'. This function is needed
-/// to make tests independent of the system time.
-fn pick_eta_with_now(
+/// Pick ETA based on UTC month, day, hour and minute. Define also 'now' which is used to resolve
+/// the year of the ETA.
+#[cfg(feature = "time")]
+pub(crate) fn pick_eta_with_now(
     bv: &BitVec,
     index: usize,
     now: DateTime<Utc>,
@@ -176,6 +456,74 @@ fn pick_eta_with_now(
     }
 }
 
+/// Pick ETA hour and minute without the date. Unavailable hour or minute is completed as 23:59
+/// like with the `time` feature.
+#[cfg(not(feature = "time"))]
+pub(crate) fn pick_eta(bv: &BitVec, index: usize) -> Result<Option<Timestamp>, ParseError> {
+    let month = pick_u64(bv, index, 4) as u32;
+    let day = pick_u64(bv, index + 4, 5) as u32;
+    let mut hour = pick_u64(bv, index + 4 + 5, 5) as u32;
+    let mut minute = pick_u64(bv, index + 4 + 5 + 5, 6) as u32;
+
+    // Check special case for no value
+    if month == 0 && day == 0 && hour == 24 && minute == 60 {
+        return Ok(None);
+    }
+    if month > 12 || day > 31 {
+        return Err(format!("Invalid ETA date: m:{} d:{}", month, day).into());
+    }
+
+    if hour == 24 {
+        hour = 23;
+        minute = 59;
+    }
+    if minute == 60 {
+        minute = 59;
+    }
+    parse_valid_hms(hour, minute, 0).map(Some)
+}
+
+/// Resolve the full timestamp of an AIS UTC second (0-59) by placing it within half a minute of
+/// the reference time, i.e. in the previous, same or next minute. `None` is returned if the
+/// second isn't available (60-63).
+#[cfg(feature = "time")]
+pub(crate) fn snap_utc_second(reference: DateTime<Utc>, second: u8) -> Option<DateTime<Utc>> {
+    if second > 59 {
+        return None;
+    }
+    let minute_start = reference.with_second(0)?.with_nanosecond(0)?;
+    let candidate = minute_start + Duration::seconds(second as i64);
+    [
+        candidate - Duration::minutes(1),
+        candidate,
+        candidate + Duration::minutes(1),
+    ]
+    .iter()
+    .copied()
+    .min_by_key(|c| (*c - reference).num_milliseconds().abs())
+}
+
+/// Resolve the full timestamp of a time of day by placing it within half a day of the reference
+/// time, i.e. on the previous, same or next day.
+#[cfg(feature = "time")]
+pub(crate) fn snap_time_of_day(
+    reference: DateTime<Utc>,
+    time_of_day: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let candidate = reference
+        .date_naive()
+        .and_time(time_of_day.time())
+        .and_utc();
+    [
+        candidate - Duration::days(1),
+        candidate,
+        candidate + Duration::days(1),
+    ]
+    .iter()
+    .copied()
+    .min_by_key(|c| (*c - reference).num_milliseconds().abs())
+}
+
 /// Pick number field from a comma-separated sentence or `None` in case of an empty field.
 pub(crate) fn pick_number_field<T: core::str::FromStr>(
     split: &[&str],
@@ -216,7 +564,24 @@ pub(crate) fn pick_string_field(split: &[&str], num: usize) -> Option<String> {
     }
 }
 
+/// Parse time field of format HHMMSS into a `Timestamp`. With the `time` feature the date is
+/// set to 2000-01-01.
+pub(crate) fn parse_time_of_day(hhmmss: &str) -> Result<Timestamp, ParseError> {
+    #[cfg(feature = "time")]
+    {
+        let now: DateTime<Utc> = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).single().unwrap();
+        parse_hhmmss(hhmmss, now)
+    }
+    #[cfg(not(feature = "time"))]
+    {
+        let (hour, minute, second) =
+            parse_time(hhmmss).map_err(|_| format!("Invalid time format: {}", hhmmss))?;
+        parse_valid_hms(hour, minute, second)
+    }
+}
+
 /// Parse time field of format HHMMSS and convert it to `DateTime<Utc>` using the current time.
+#[cfg(feature = "time")]
 pub(crate) fn parse_hhmmss(hhmmss: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, ParseError> {
     let (hour, minute, second) =
         parse_time(hhmmss).map_err(|_| format!("Invalid time format: {}", hhmmss))?;
@@ -224,6 +589,7 @@ pub(crate) fn parse_hhmmss(hhmmss: &str, now: DateTime<Utc>) -> Result<DateTime<
 }
 
 /// Parse time fields of formats YYMMDD and HHMMSS and convert them to `DateTime<Utc>`.
+#[cfg(feature = "time")]
 pub(crate) fn parse_yymmdd_hhmmss(yymmdd: &str, hhmmss: &str) -> Result<DateTime<Utc>, ParseError> {
     let now = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
     let century = (now.year() / 100) * 100;
@@ -234,7 +600,22 @@ pub(crate) fn parse_yymmdd_hhmmss(yymmdd: &str, hhmmss: &str) -> Result<DateTime
     parse_valid_utc(century + year, month, day, hour, minute, second, 0)
 }
 
+/// Parse time fields of formats YYMMDD and HHMMSS. The date is checked for format only and
+/// the time of day is returned.
+#[cfg(not(feature = "time"))]
+pub(crate) fn parse_yymmdd_hhmmss(yymmdd: &str, hhmmss: &str) -> Result<Timestamp, ParseError> {
+    let (day, month, _year) =
+        parse_date(yymmdd).map_err(|_| format!("Invalid date format: {}", yymmdd))?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(format!("Invalid date format: {}", yymmdd).into());
+    }
+    let (hour, minute, second) =
+        parse_time(hhmmss).map_err(|_| format!("Invalid time format: {}", hhmmss))?;
+    parse_valid_hms(hour, minute, second)
+}
+
 /// Parse time field of format HHMMSS.SS and convert it to `DateTime<Utc>` using the given date.
+#[cfg(feature = "time")]
 pub(crate) fn parse_hhmmss_ss(
     hhmmss: &str,
     date: DateTime<Utc>,
@@ -253,6 +634,7 @@ pub(crate) fn parse_hhmmss_ss(
 }
 
 /// Pick date by picking the given field numbers. Set time part to midnight.
+#[cfg(feature = "time")]
 pub(crate) fn pick_date_with_fields(
     split: &[&str],
     year_field: usize,
@@ -270,15 +652,22 @@ pub(crate) fn pick_date_with_fields(
 }
 
 /// Pick time zone (`FixedOffset`) with the given field numbers.
+#[cfg(feature = "time")]
 pub(crate) fn pick_timezone_with_fields(
     split: &[&str],
     hour_field: usize,
     minute_field: usize,
 ) -> Result<FixedOffset, ParseError> {
-    let hour = split.get(hour_field).unwrap_or(&"").parse::<i32>()?;
-    let minute = split.get(minute_field).unwrap_or(&"0").parse::<i32>()?;
+    let hour_str = split.get(hour_field).unwrap_or(&"");
+    let hour = hour_str.parse::<i32>()?;
+    let minute = match split.get(minute_field) {
+        Some(s) if !s.is_empty() => s.parse::<i32>()?,
+        _ => 0,
+    };
 
-    if let Some(offset) = FixedOffset::east_opt(hour * 3600 + hour.signum() * minute * 60) {
+    // Sign is taken from the hour string so that offsets such as -00:30 are negative
+    let sign = if hour_str.starts_with('-') { -1 } else { 1 };
+    if let Some(offset) = FixedOffset::east_opt(hour * 3600 + sign * minute * 60) {
         Ok(offset)
     } else {
         Err(ParseError::InvalidSentence(format!(
@@ -321,6 +710,7 @@ fn parse_time_with_fractions(hhmmss: &str) -> Result<(u32, u32, u32, u32), Parse
 }
 
 /// Parse Utc date from YYYY MM DD hh mm ss
+#[cfg(feature = "time")]
 pub(crate) fn parse_ymdhs(
     year: i32,
     month: u32,
@@ -332,7 +722,19 @@ pub(crate) fn parse_ymdhs(
     parse_valid_utc(year, month, day, hour, min, sec, 0)
 }
 
-/// Using _opt on Utc. Will catch invalid Date (ex: month > 12).
+/// Validate hour, minute and second and convert them to `Timestamp`. Second 60 is accepted for
+/// leap seconds.
+#[cfg(not(feature = "time"))]
+pub(crate) fn parse_valid_hms(hour: u32, min: u32, sec: u32) -> Result<Timestamp, ParseError> {
+    if hour < 24 && min < 60 && sec <= 60 {
+        Ok((hour as u8, min as u8, sec as u8))
+    } else {
+        Err(format!("Invalid time of day h:{} m:{} s:{}", hour, min, sec).into())
+    }
+}
+
+/// Uses the `_opt` constructors, which catch invalid dates (e.g. month > 12).
+#[cfg(feature = "time")]
 pub fn parse_valid_utc(
     year: i32,
     month: u32,
@@ -342,14 +744,12 @@ pub fn parse_valid_utc(
     sec: u32,
     nano: u32,
 ) -> Result<DateTime<Utc>, ParseError> {
-    let opt_utc = Utc
-        .ymd_opt(year, month, day)
-        .and_hms_nano_opt(hour, min, sec, nano);
+    let opt_utc = NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|date| date.and_hms_nano_opt(hour, min, sec, nano))
+        .map(|naive| Utc.from_utc_datetime(&naive));
     match opt_utc {
-        chrono::LocalResult::Single(valid_utc) | chrono::LocalResult::Ambiguous(valid_utc, _) => {
-            Ok(valid_utc)
-        }
-        chrono::LocalResult::None => Err(format!(
+        Some(valid_utc) => Ok(valid_utc),
+        None => Err(format!(
             "Failed to parse Utc Date from y:{} m:{} d:{} h:{} m:{} s:{}",
             year, month, day, hour, min, sec
         )
@@ -363,11 +763,66 @@ fn pick_s2(s: &str, i: usize) -> &str {
     s.get(i..end).unwrap_or("")
 }
 
+/// Return seconds since midnight of the given timestamp, in range `0..=86399`. A leap second,
+/// which chrono represents as 23:59:59 with over a second of nanoseconds, is counted as 86399 and
+/// thus can't be told apart from the second before it.
+#[cfg(feature = "time")]
+pub fn seconds_of_day(dt: &Timestamp) -> u32 {
+    dt.num_seconds_from_midnight()
+}
+
+/// Return seconds since midnight of the given timestamp, in range `0..=86400`. The value 86400 is
+/// returned only for a leap second 23:59:60, which is accepted when parsing time without the
+/// `time` feature.
+#[cfg(not(feature = "time"))]
+pub fn seconds_of_day(dt: &Timestamp) -> u32 {
+    let (hour, minute, second) = *dt;
+    hour as u32 * 3600 + minute as u32 * 60 + second as u32
+}
+
+/// Test whether the position is plausible: the latitude is within ±90 degrees, the longitude
+/// is within ±180 degrees and the position isn't exactly (0, 0), which receivers often report
+/// before they have a fix.
+pub fn sanity_check_position(lat: f64, lon: f64) -> bool {
+    (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) && !(lat == 0.0 && lon == 0.0)
+}
+
+/// Wrap course angles from 360 up to 720 degrees into range `[0, 360)`, e.g. 360.0 to 0.0, as
+/// some encoders send 360 for north. Values of 720 or more are considered corrupt, so `None` is
+/// returned and a warning is recorded. Negative values are returned as given.
+pub(crate) fn wrap_full_circle(degrees: f64, store: &mut ParseContext) -> Option<f64> {
+    if degrees >= 720.0 {
+        warn!("Course out of range: {}", degrees);
+        store.add_warning(ParseWarning::CourseOutOfRange(degrees));
+        None
+    } else if degrees >= 360.0 {
+        Some(degrees - 360.0)
+    } else {
+        Some(degrees)
+    }
+}
+
+/// Wrap an angle in degrees into range `[0, 360)`.
+pub(crate) fn normalize_angle(degrees: f64) -> f64 {
+    let wrapped = degrees % 360.0;
+    let wrapped = if wrapped < 0.0 {
+        wrapped + 360.0
+    } else {
+        wrapped
+    };
+    // Tiny negative values round to 360.0, and -0.0 is returned as 0.0
+    if wrapped >= 360.0 {
+        0.0
+    } else {
+        wrapped + 0.0
+    }
+}
+
 /// Parse latitude from two string.
 /// Argument `lat_string` expects format DDMM.MMM representing latitude.
 /// Argument `hemisphere` expects "N" for north or "S" for south. If `hemisphere` value
 /// is something else, north is quietly used as a fallback.
-pub(crate) fn parse_latitude_ddmm_mmm(
+pub fn parse_latitude_ddmm_mmm(
     lat_string: &str,
     hemisphere: &str,
 ) -> Result<Option<f64>, ParseError> {
@@ -408,10 +863,10 @@ pub(crate) fn parse_latitude_ddmm_mmm(
 /// Argument `lon_string` expects format DDDMM.MMM representing longitude.
 /// Argument `hemisphere` expects "E" for east or "W" for west. If `hemisphere` value is
 /// something else, east is quietly used as a fallback.
-pub(crate) fn parse_longitude_dddmm_mmm(
+pub fn parse_longitude_dddmm_mmm(
     lon_string: &str,
     hemisphere: &str,
-) -> Result<Option<f64>, String> {
+) -> Result<Option<f64>, ParseError> {
     // DDDMM.MMM
     if lon_string.is_empty() {
         return Ok(None);
@@ -426,10 +881,7 @@ pub(crate) fn parse_longitude_dddmm_mmm(
             .map(|c| c.is_ascii_digit())
             .unwrap_or(false))
     {
-        return Err(format!(
-            "Failed to parse longitude (DDDMM.MMM) from {}",
-            lon_string
-        ));
+        return Err(format!("Failed to parse longitude (DDDMM.MMM) from {}", lon_string).into());
     }
     let end = 6 + byte_string
         .iter()
@@ -448,6 +900,43 @@ pub(crate) fn parse_longitude_dddmm_mmm(
     }))
 }
 
+/// Format latitude in degrees to DDMM.MMMM string and hemisphere character ('N' or 'S'). This
+/// is the inverse of `parse_latitude_ddmm_mmm`.
+pub fn format_latitude(deg: f64) -> (String, char) {
+    let (d, m) = split_degrees_minutes(deg);
+    let hemisphere = if deg < 0.0 && (d, m) != (0, 0) {
+        'S'
+    } else {
+        'N'
+    };
+    (
+        format!("{:02}{:02}.{:04}", d, m / 10000, m % 10000),
+        hemisphere,
+    )
+}
+
+/// Format longitude in degrees to DDDMM.MMMM string and hemisphere character ('E' or 'W').
+/// This is the inverse of `parse_longitude_dddmm_mmm`.
+pub fn format_longitude(deg: f64) -> (String, char) {
+    let (d, m) = split_degrees_minutes(deg);
+    let hemisphere = if deg < 0.0 && (d, m) != (0, 0) {
+        'W'
+    } else {
+        'E'
+    };
+    (
+        format!("{:03}{:02}.{:04}", d, m / 10000, m % 10000),
+        hemisphere,
+    )
+}
+
+/// Split absolute value of the given degrees to whole degrees and minutes in 1/10000 units.
+/// Rounding is done before the split so that minutes never reach 60.
+fn split_degrees_minutes(deg: f64) -> (u64, u64) {
+    let total = (deg.abs() * 600_000.0).round() as u64;
+    (total / 600_000, total % 600_000)
+}
+
 /// Parse latitude from two string.
 /// Argument `lat_string` expects a latitude offset in minutes
 /// Argument `hemisphere` expects "N" for north or "S" for south. If `hemisphere` value
@@ -498,6 +987,54 @@ pub(crate) fn parse_longitude_m_m(
 mod test {
     use super::*;
 
+    #[test]
+    fn test_peek_ais_message_type() {
+        assert_eq!(
+            peek_ais_message_type("15RTgt0PAso;90TKcjM8h6g208CQ"),
+            Some(1)
+        );
+        assert_eq!(
+            peek_ais_message_type("H42O55i18tMET00000000000000"),
+            Some(24)
+        );
+        assert_eq!(peek_ais_message_type("W"), Some(39));
+        assert_eq!(peek_ais_message_type("`"), Some(40));
+        assert_eq!(peek_ais_message_type("w"), Some(63));
+        assert_eq!(peek_ais_message_type("X"), None);
+        assert_eq!(peek_ais_message_type(""), None);
+    }
+
+    #[test]
+    fn test_finalize_sentence() {
+        assert_eq!(
+            finalize_sentence("GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,"),
+            "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r\n"
+        );
+        assert_eq!(
+            finalize_sentence("AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0"),
+            "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A\r\n"
+        );
+
+        // Existing delimiter is kept and a wrong checksum and terminator are replaced
+        assert_eq!(
+            finalize_sentence("$HEHDT,274.07,T*00\n"),
+            finalize_sentence("HEHDT,274.07,T")
+        );
+
+        let mut p = NmeaParser::new();
+        p.set_require_checksum(true);
+        match p.parse_sentence(&finalize_sentence(
+            "GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,",
+        )) {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.satellite_count, Some(8));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
     #[test]
     fn test_parse_payload() {
         match parse_payload("w7b0P1") {
@@ -518,6 +1055,17 @@ mod test {
                 assert_eq!(e, "OK");
             }
         }
+
+        // Invalid characters
+        assert_eq!(
+            parse_payload("w7b,P1"),
+            Err("Invalid character in AIS payload: ','".to_string())
+        );
+        assert!(parse_payload("w7bXP1").is_err());
+        assert!(parse_payload("w7b\u{e4}P1").is_err());
+        assert!(parse_payload("w7b P1").is_err());
+        assert!(parse_payload("w7b~P1").is_err());
+        assert!(parse_payload("w7b\nP1").is_err());
     }
 
     #[test]
@@ -556,6 +1104,68 @@ mod test {
         assert_eq!(pick_string(&bv, 0, bv.len() / 6), "?AG_4:!");
     }
 
+    #[test]
+    fn test_snap_time_of_day() {
+        let t = |d, h, m, s| Utc.with_ymd_and_hms(2024, 3, d, h, m, s).single();
+        let time_of_day = |h, m, s| Utc.with_ymd_and_hms(2000, 1, 1, h, m, s).unwrap();
+
+        // Same day
+        let reference = t(1, 12, 0, 0).unwrap();
+        assert_eq!(
+            snap_time_of_day(reference, time_of_day(12, 0, 5)),
+            t(1, 12, 0, 5)
+        );
+        assert_eq!(
+            snap_time_of_day(reference, time_of_day(1, 0, 0)),
+            t(1, 1, 0, 0)
+        );
+
+        // Time of day after midnight, reference before it
+        let reference = t(1, 23, 59, 59).unwrap();
+        assert_eq!(
+            snap_time_of_day(reference, time_of_day(0, 0, 1)),
+            t(2, 0, 0, 1)
+        );
+
+        // Time of day before midnight, reference after it
+        let reference = t(2, 0, 0, 1).unwrap();
+        assert_eq!(
+            snap_time_of_day(reference, time_of_day(23, 59, 59)),
+            t(1, 23, 59, 59)
+        );
+    }
+
+    #[test]
+    fn test_snap_utc_second() {
+        let t = |h, m, s| Utc.with_ymd_and_hms(2024, 3, 1, h, m, s).single();
+        let reference = t(12, 0, 30).unwrap();
+
+        // Within the same minute
+        assert_eq!(snap_utc_second(reference, 20), t(12, 0, 20));
+        assert_eq!(snap_utc_second(reference, 45), t(12, 0, 45));
+
+        // Reference late in the minute, message second wrapped to the next minute
+        let reference = t(12, 0, 58).unwrap();
+        assert_eq!(snap_utc_second(reference, 2), t(12, 1, 2));
+        assert_eq!(snap_utc_second(reference, 59), t(12, 0, 59));
+
+        // Reference early in the minute, message second from the previous minute
+        let reference = t(12, 1, 1).unwrap();
+        assert_eq!(snap_utc_second(reference, 59), t(12, 0, 59));
+        assert_eq!(snap_utc_second(reference, 0), t(12, 1, 0));
+
+        // Wrap over midnight
+        let reference = t(23, 59, 55).unwrap();
+        assert_eq!(
+            snap_utc_second(reference, 5),
+            Utc.with_ymd_and_hms(2024, 3, 2, 0, 0, 5).single()
+        );
+
+        // Second not available
+        assert_eq!(snap_utc_second(reference, 60), None);
+        assert_eq!(snap_utc_second(reference, 63), None);
+    }
+
     #[test]
     fn test_pick_eta() {
         // Valid case
@@ -816,8 +1426,228 @@ mod test {
             FixedOffset::east_opt(-4 * 3600 - 30 * 60)
         );
 
+        // Empty minutes
+        let s: Vec<&str> = ",,,,,-1,".split(',').collect();
+        assert_eq!(
+            pick_timezone_with_fields(&s, 5, 6).ok(),
+            FixedOffset::east_opt(-3600)
+        );
+
         // Invalid time zone
         let s: Vec<&str> = ",,,,,+25,00".split(',').collect();
         assert!(!pick_timezone_with_fields(&s, 5, 6).is_ok());
     }
+
+    #[test]
+    fn test_format_lat_lon() {
+        assert_eq!(format_latitude(48.1173), ("4807.0380".to_string(), 'N'));
+        assert_eq!(format_latitude(-33.8568), ("3351.4080".to_string(), 'S'));
+        assert_eq!(format_latitude(0.0), ("0000.0000".to_string(), 'N'));
+        assert_eq!(format_longitude(11.516667), ("01131.0000".to_string(), 'E'));
+        assert_eq!(format_longitude(-151.2153), ("15112.9180".to_string(), 'W'));
+
+        // Minutes rounded up to 60 carry over to degrees
+        assert_eq!(format_latitude(59.9999999), ("6000.0000".to_string(), 'N'));
+
+        // Round trip
+        for deg in [0.0, 48.1173, -33.8568, 60.169857, -89.99995, 89.5].iter() {
+            let (s, h) = format_latitude(*deg);
+            let parsed = parse_latitude_ddmm_mmm(&s, &h.to_string())
+                .unwrap()
+                .unwrap();
+            assert::close(parsed, *deg, 0.000001);
+        }
+        for deg in [0.0, 11.516667, -151.2153, 24.938379, -179.99995, 179.5].iter() {
+            let (s, h) = format_longitude(*deg);
+            let parsed = parse_longitude_dddmm_mmm(&s, &h.to_string())
+                .unwrap()
+                .unwrap();
+            assert::close(parsed, *deg, 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_fragment_info() {
+        assert_eq!(
+            fragment_info(
+                "!AIVDM,2,1,5,A,53nFBv01SJ<thHp6220H4heHTf2222222222221?50:454o<`9QSlUDp,0*09"
+            ),
+            Some((2, 1, Some(5)))
+        );
+        assert_eq!(
+            fragment_info("!AIVDM,2,2,5,A,888888888888880,2*27"),
+            Some((2, 2, Some(5)))
+        );
+        assert_eq!(
+            fragment_info("!AIVDO,1,1,,B,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"),
+            Some((1, 1, None))
+        );
+        assert_eq!(
+            fragment_info("\\s:2573135*0F\\!BSVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"),
+            Some((1, 1, None))
+        );
+
+        // Inconsistent or missing fragment fields
+        assert_eq!(
+            fragment_info("!AIVDM,0,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"),
+            None
+        );
+        assert_eq!(
+            fragment_info("!AIVDM,2,3,1,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"),
+            None
+        );
+        assert_eq!(
+            fragment_info("!AIVDM,x,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"),
+            None
+        );
+        assert_eq!(fragment_info("!AIVDM"), None);
+
+        // Not VDM/VDO
+        assert_eq!(
+            fragment_info("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47"),
+            None
+        );
+        assert_eq!(fragment_info(""), None);
+    }
+
+    #[test]
+    fn test_is_within_length_limit() {
+        let gga = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        assert!(is_within_length_limit(gga));
+        assert!(is_within_length_limit(&format!("{}\r\n", gga)));
+
+        // 85 characters including CR LF
+        let long_gga =
+            "$GPGGA,123519.000,4807.0380000,N,01131.0000000,E,1,08,0.9,545.4,M,46.9,M,0.0,000*47";
+        assert!(!is_within_length_limit(long_gga));
+        assert!(!is_within_length_limit(&format!("{}\r\n", long_gga)));
+
+        // 80 characters before CR LF
+        assert!(is_within_length_limit(&format!(
+            "$GPTXT,{}",
+            "x".repeat(73)
+        )));
+        assert!(!is_within_length_limit(&format!(
+            "$GPTXT,{}",
+            "x".repeat(74)
+        )));
+
+        // Prefix before the start delimiter isn't counted
+        assert!(is_within_length_limit(&format!(
+            "\\s:r003669945,c:1241544035*4A\\{}",
+            gga
+        )));
+
+        // AIS is exempt
+        assert!(is_within_length_limit(&format!(
+            "!AIVDM,1,1,,A,{},0",
+            "1".repeat(80)
+        )));
+    }
+
+    #[test]
+    fn test_validate_sentence() {
+        // Valid, with and without checksum and CR LF
+        let gga = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        assert_eq!(validate_sentence(gga), Ok(()));
+        assert!(is_valid_sentence(gga));
+        assert!(is_valid_sentence(&format!("{}\r\n", gga)));
+        assert!(is_valid_sentence(
+            "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,"
+        ));
+        assert!(is_valid_sentence(
+            "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"
+        ));
+
+        // Bad checksum
+        let bad = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48";
+        assert!(!is_valid_sentence(bad));
+        assert_eq!(
+            validate_sentence(bad),
+            Err(ParseError::Checksum(ChecksumError::Mismatch {
+                calculated: 0x47,
+                given: *b"48"
+            }))
+        );
+        assert_eq!(
+            validate_sentence(bad).unwrap_err().to_string(),
+            "Corrupted NMEA sentence: checksum \"47\" != \"48\""
+        );
+        assert_eq!(
+            validate_sentence("$GPGGA,123519*4"),
+            Err(ParseError::Checksum(ChecksumError::Truncated))
+        );
+
+        // Embedded control character
+        let ctrl = "$GPGGA,123519,4807.038,N,\x0701131.000,E,1,08,0.9,545.4,M,46.9,M,,";
+        assert!(!is_valid_sentence(ctrl));
+        assert_eq!(
+            validate_sentence(ctrl),
+            Err(ParseError::Structure(StructureError::InvalidCharacter(25)))
+        );
+
+        // Over-length
+        let long = format!("$GPTXT,{}", "A".repeat(74));
+        assert!(!is_valid_sentence(&long));
+        assert_eq!(
+            validate_sentence(&long),
+            Err(ParseError::Structure(StructureError::TooLong(83)))
+        );
+        assert_eq!(
+            validate_sentence(&long).unwrap_err().to_string(),
+            "Invalid NMEA sentence: sentence length 83 exceeds the maximum of 82"
+        );
+        assert!(is_valid_sentence(&long[0..80]));
+
+        // Missing start character
+        assert!(!is_valid_sentence(
+            "GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47"
+        ));
+        assert!(!is_valid_sentence(""));
+        assert_eq!(
+            validate_sentence("GPGGA,123519"),
+            Err(ParseError::Structure(StructureError::NoStartDelimiter))
+        );
+        assert!(!is_valid_sentence("x$GPGGA,123519"));
+    }
+
+    #[test]
+    fn test_validate_sentence_prefix() {
+        // UTF-8 byte order mark and ANSI color code are skipped
+        let gga = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        assert_eq!(validate_sentence(&format!("\u{feff}{}", gga)), Ok(()));
+        assert!(is_valid_sentence(&format!(
+            "\x1b[32m{}\r\n",
+            "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"
+        )));
+        assert_eq!(
+            strip_sentence_prefix("\u{feff}$GPGGA,123519"),
+            Some(("$GPGGA,123519", 3))
+        );
+        assert_eq!(strip_sentence_prefix(gga), None);
+
+        // Too long prefix
+        assert!(!is_valid_sentence(&format!(
+            "\x1b[38;5;208;48;5;17m{}",
+            gga
+        )));
+
+        // The rest isn't valid, which is reported as a missing start delimiter
+        let bad = "\u{feff}$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48";
+        assert_eq!(
+            validate_sentence(bad),
+            Err(ParseError::Structure(StructureError::NoStartDelimiter))
+        );
+    }
+
+    #[test]
+    fn test_sanity_check_position() {
+        assert!(sanity_check_position(60.169857, 24.938379));
+        assert!(sanity_check_position(-90.0, 180.0));
+        assert!(sanity_check_position(0.0, 24.9));
+        assert!(!sanity_check_position(0.0, 0.0));
+        assert!(!sanity_check_position(95.2, 11.5));
+        assert!(!sanity_check_position(60.1, -180.5));
+        assert!(!sanity_check_position(f64::NAN, 24.9));
+    }
 }
@@ -0,0 +1,113 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use core::fmt;
+use alloc::string::String;
+
+/// Non-fatal problem found while parsing a sentence. The sentence is parsed despite the problem,
+/// typically with the affected value missing. Warnings of the latest parse call are available
+/// with `NmeaParser::take_warnings()`; they are also logged.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// Fragment number of a two-fragment VDM/VDO message is other than 1 or 2
+    UnexpectedFragmentNumber {
+        /// Fragment number
+        fragment_number: u8,
+
+        /// Fragment count
+        fragment_count: u8,
+    },
+
+    /// Sequential message ID is missing from a multi-fragment VDM/VDO sentence
+    MissingMessageId,
+
+    /// VDM/VDO message has more fragments than supported (2)
+    UnsupportedFragmentCount(u8),
+
+    /// AIS special manoeuvre indicator has a reserved value
+    UnrecognizedManeuverIndicator(u8),
+
+    /// AIS ship and cargo type is out of range (over 99)
+    UnexpectedShipType(u8),
+
+    /// AIS position fix type has a reserved value (9-14)
+    UnrecognizedPositionFixType(u8),
+
+    /// AIS UTC date and time isn't a valid date
+    InvalidUtcDateTime(String),
+
+    /// Unit of a distance field isn't recognized, so the value is left out
+    UnknownUnit(String),
+
+    /// MSS beacon frequency is outside 283.5-325.0 kHz
+    MssFrequencyOutOfRange(f64),
+
+    /// MSS beacon bit rate isn't one of 25, 50, 100 or 200 bits/s
+    InvalidMssBitRate(u32),
+
+    /// RMC or VTG course of 720 degrees or more is considered corrupt, so the value is left out
+    CourseOutOfRange(f64),
+
+    /// Bytes, e.g. a UTF-8 byte order mark or an ANSI escape sequence, preceding the start
+    /// delimiter were skipped (count of bytes)
+    SkippedPrefix(usize),
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseWarning::UnexpectedFragmentNumber {
+                fragment_number,
+                fragment_count,
+            } => write!(
+                f,
+                "Unexpected NMEA fragment number: {}/{}",
+                fragment_number, fragment_count
+            ),
+            ParseWarning::MissingMessageId => write!(f, "NMEA message ID missing"),
+            ParseWarning::UnsupportedFragmentCount(count) => write!(
+                f,
+                "NMEA sentence fragment count greater ({}) than supported 2",
+                count
+            ),
+            ParseWarning::UnrecognizedManeuverIndicator(raw) => {
+                write!(f, "Unrecognized Maneuver Indicator value: {}", raw)
+            }
+            ParseWarning::UnexpectedShipType(raw) => {
+                write!(f, "Unexpected ship and cargo type: {}", raw)
+            }
+            ParseWarning::UnrecognizedPositionFixType(raw) => {
+                write!(f, "Unrecognized position fix type: {}", raw)
+            }
+            ParseWarning::InvalidUtcDateTime(s) => write!(f, "{}", s),
+            ParseWarning::UnknownUnit(unit) => write!(f, "Unknown unit: {}", unit),
+            ParseWarning::MssFrequencyOutOfRange(freq) => {
+                write!(f, "MSS beacon frequency out of range: {}", freq)
+            }
+            ParseWarning::InvalidMssBitRate(rate) => {
+                write!(f, "Invalid MSS beacon bit rate: {}", rate)
+            }
+            ParseWarning::CourseOutOfRange(degrees) => {
+                write!(f, "Course out of range: {}", degrees)
+            }
+            ParseWarning::SkippedPrefix(len) => {
+                write!(f, "Skipped {} bytes before the start delimiter", len)
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Allocation counts of sentence parsing. A counting global allocator is used, which requires
+//! unsafe code and hence lives outside the library crate.
+
+use nmea_parser::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Count allocations made while parsing the given sentence.
+fn count_allocations(p: &mut NmeaParser, sentence: &str) -> usize {
+    let before = ALLOCATIONS.with(|a| a.get());
+    let result = p.parse_sentence(sentence);
+    let after = ALLOCATIONS.with(|a| a.get());
+    assert!(result.is_ok());
+    after - before
+}
+
+#[test]
+fn test_allocations_per_sentence() {
+    let mut p = NmeaParser::new();
+    let gga = count_allocations(
+        &mut p,
+        "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47",
+    );
+    let vdm = count_allocations(&mut p, "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A");
+
+    // The sentence type is dispatched without allocating; the remaining allocations are made by
+    // the handlers (field splitting and the result). The bounds catch regressions without
+    // depending on the allocation behaviour of a particular toolchain.
+    assert!(gga <= 4, "GGA: {} allocations", gga);
+    assert!(vdm <= 3, "VDM: {} allocations", vdm);
+}
+
+#[test]
+fn test_filtered_ais_types_are_not_decoded() {
+    let vdm = "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A";
+    let mut p = NmeaParser::new();
+    let decoded = count_allocations(&mut p, vdm);
+
+    let mut filter = MessageFilter::new();
+    filter.exclude_ais_types(&[1, 2, 3]);
+    p.set_message_filter(filter);
+    let filtered = count_allocations(&mut p, vdm);
+
+    // Only the fields are copied; no payload bits or result are allocated
+    assert!(filtered <= 2, "Filtered VDM: {} allocations", filtered);
+    assert!(decoded <= 3, "Decoded VDM: {} allocations", decoded);
+    assert!(
+        filtered < decoded,
+        "Filtered VDM: {}, decoded: {}",
+        filtered,
+        decoded
+    );
+    assert_eq!(p.parse_sentence(vdm), Ok(ParsedMessage::Ignored));
+
+    // Included types are decoded as before
+    let mut filter = MessageFilter::new();
+    filter.exclude_ais_types(&[5, 24]);
+    p.set_message_filter(filter);
+    assert_eq!(count_allocations(&mut p, vdm), decoded);
+}
+
+#[test]
+fn test_stateless_parse_does_not_clone_the_parser() {
+    let gga = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+    let mut p = NmeaParser::new();
+    for fid in 0..8 {
+        p.register_binary_decoder(1, fid, |_: &bitvec::prelude::BitVec| {
+            Ok(ParsedMessage::Ignored)
+        });
+    }
+    p.set_enabled_types(&["GGA", "RMC", "VDM"]);
+    let stateful = count_allocations(&mut p, gga);
+
+    // Decoders and the filter aren't cloned, so the call allocates no more than `parse_sentence()`
+    let before = ALLOCATIONS.with(|a| a.get());
+    let result = p.parse_sentence_stateless(gga);
+    let after = ALLOCATIONS.with(|a| a.get());
+    assert!(result.is_ok());
+    let stateless = after - before;
+    assert!(
+        stateless <= stateful,
+        "Stateless GGA: {} allocations, stateful: {}",
+        stateless,
+        stateful
+    );
+}
+
+#[test]
+fn test_checksum_errors_do_not_allocate() {
+    let mut p = NmeaParser::new();
+    let long = format!("$GPTXT,{}", "A".repeat(80));
+    let before = ALLOCATIONS.with(|a| a.get());
+    let mismatch =
+        p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48");
+    let invalid = validate_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4B");
+    let no_delimiter = validate_sentence("GPGGA,123519,4807.038,N");
+    let too_long = validate_sentence(&long);
+    let after = ALLOCATIONS.with(|a| a.get());
+    assert_eq!(after - before, 0);
+    assert_eq!(
+        mismatch,
+        Err(ParseError::Checksum(ChecksumError::Mismatch {
+            calculated: 0x47,
+            given: *b"48"
+        }))
+    );
+    assert!(invalid.is_err());
+    assert_eq!(
+        no_delimiter,
+        Err(ParseError::Structure(StructureError::NoStartDelimiter))
+    );
+    assert_eq!(
+        too_long,
+        Err(ParseError::Structure(StructureError::TooLong(89)))
+    );
+
+    p.set_require_checksum(true);
+    let before = ALLOCATIONS.with(|a| a.get());
+    let missing = p.parse_sentence("$HEHDT,274.07,T");
+    let after = ALLOCATIONS.with(|a| a.get());
+    assert_eq!(after - before, 0);
+    assert_eq!(missing, Err(ParseError::Checksum(ChecksumError::Missing)));
+}
+
+#[test]
+fn test_validation_does_not_allocate() {
+    let before = ALLOCATIONS.with(|a| a.get());
+    let valid =
+        is_valid_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47");
+    let invalid =
+        is_valid_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48");
+    let fragment = fragment_info("!AIVDM,2,1,5,A,53nFBv01SJ<thHp6220H4heHTf2222222,0*09");
+    let after = ALLOCATIONS.with(|a| a.get());
+    assert!(valid);
+    assert!(!invalid);
+    assert_eq!(fragment, Some((2, 1, Some(5))));
+    assert_eq!(after - before, 0);
+}
@@ -0,0 +1,107 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! `NmeaParser` shared between threads with `Mutex` and `RwLock`.
+
+#![cfg(feature = "ais")]
+
+use std::sync::{Arc, Barrier, Mutex, RwLock};
+use std::thread;
+
+use nmea_parser::*;
+
+/// Two-fragment AIS type 5 messages of four vessels. Both threads get one fragment of each
+/// message, so every message is completed by whichever thread happens to be second.
+const THREAD_1: [&str; 4] = [
+    "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+    "!AIVDM,2,2,2,A,88888888880,2*26",
+    "!AIVDM,2,1,3,A,53LBbi02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*5B",
+    "!AIVDM,2,2,4,A,88888888880,2*20",
+];
+const THREAD_2: [&str; 4] = [
+    "!AIVDM,2,1,2,A,55?MbV@2;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*6F",
+    "!AIVDM,2,2,1,A,88888888880,2*25",
+    "!AIVDM,2,1,4,A,539KdV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*19",
+    "!AIVDM,2,2,3,A,88888888880,2*27",
+];
+
+#[test]
+fn test_interleaved_fragments() {
+    for _ in 0..100 {
+        let parser = Arc::new(Mutex::new(NmeaParser::new()));
+        let barrier = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = [THREAD_1, THREAD_2]
+            .iter()
+            .map(|sentences| {
+                let parser = parser.clone();
+                let barrier = barrier.clone();
+                let sentences = *sentences;
+                thread::spawn(move || {
+                    barrier.wait();
+                    sentences
+                        .iter()
+                        .map(|s| parser.lock().unwrap().parse_sentence(s).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut mmsis: Vec<u32> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .filter_map(|msg| match msg {
+                ParsedMessage::VesselStaticData(vsd) => {
+                    assert_eq!(vsd.name.unwrap().to_string(), "EVER DIADEM");
+                    Some(vsd.mmsi)
+                }
+                ParsedMessage::Incomplete => None,
+                other => panic!("unexpected result: {:?}", other),
+            })
+            .collect();
+        mmsis.sort();
+        assert_eq!(mmsis, vec![211217560, 230992580, 351759000, 351759001]);
+    }
+}
+
+#[test]
+fn test_stateless_under_read_lock() {
+    let parser = Arc::new(RwLock::new(NmeaParser::new()));
+    parser.write().unwrap().set_require_checksum(true);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let parser = parser.clone();
+            thread::spawn(move || {
+                let p = parser.read().unwrap();
+                assert!(p.parse_sentence_stateless("$HEHDT,274.07,T").is_err());
+                p.parse_sentence_stateless("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+                    .unwrap()
+            })
+        })
+        .collect();
+    for h in handles {
+        assert!(h.join().unwrap().as_vessel_dynamic().is_some());
+    }
+
+    // Multi-fragment sentences need the write lock
+    let mut p = parser.write().unwrap();
+    let first = "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C";
+    assert!(matches!(
+        p.parse_sentence_stateless(first),
+        Err(ParseError::RequiresState(_))
+    ));
+    assert_eq!(p.parse_sentence(first), Ok(ParsedMessage::Incomplete));
+    assert!(p.take_warnings().is_empty());
+}
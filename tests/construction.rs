@@ -0,0 +1,178 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Constructs the result structs from outside the crate with struct update syntax from
+//! `Default`, as downstream code does in tests and synthetic data generators.
+
+#![cfg(all(feature = "ais", feature = "gnss"))]
+
+use nmea_parser::*;
+
+#[test]
+fn test_struct_update_syntax() {
+    // Struct update syntax fails to compile if any of the remaining fields is private or the
+    // struct is `#[non_exhaustive]`, which only applies outside the defining crate
+    let _ = ais::VesselDynamicData {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::VesselStaticData {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::BaseStationReport {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::BinaryAddressedMessage {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::StandardSarAircraftPositionReport {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::UtcDateInquiry {
+        source_mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::AddressedSafetyRelatedMessage {
+        source_mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::SafetyRelatedAcknowledgement {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::SafetyRelatedBroadcastMessage {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::Interrogation {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::AssignmentModeCommand {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::DgnssBroadcastBinaryMessage {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::ExtendedClassBPositionReport {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::DataLinkManagementMessage {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::AidToNavigationReport {
+        high_position_accuracy: true,
+        ..Default::default()
+    };
+    let _ = ais::ChannelManagement {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::GroupAssignmentCommand {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::SingleSlotBinaryMessage {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = ais::MultipleSlotBinaryMessage {
+        mmsi: 1,
+        ..Default::default()
+    };
+    let _ = gnss::AlmData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::DbsData {
+        ..Default::default()
+    };
+    let _ = gnss::DptData {
+        ..Default::default()
+    };
+    let _ = gnss::DtmData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::GgaData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::GllData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::GnsData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::GsaData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::GsvData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::HdtData {
+        ..Default::default()
+    };
+    let _ = gnss::MssData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::MtwData {
+        ..Default::default()
+    };
+    let _ = gnss::MwvData {
+        ..Default::default()
+    };
+    let _ = gnss::RmbData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::RmcData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::StnData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::VbwData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::VhwData {
+        ..Default::default()
+    };
+    let _ = gnss::VtgData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+    let _ = gnss::ZdaData {
+        source: gnss::NavigationSystem::Gps,
+        ..Default::default()
+    };
+}
@@ -0,0 +1,80 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Runs the sentence corpus in `tests/data/corpus.nmea` through a single parser and compares
+//! the summary with `tests/data/corpus_expected.txt`. The corpus mixes multi-fragment and
+//! out-of-order AIS messages, GSV groups, GNSS sentences, bad checksums and unsupported
+//! sentence types, so it catches problems in the state carried between sentences. When a
+//! change alters the summary on purpose, update the expected file with the printed output.
+
+#![cfg(all(feature = "time", feature = "ais", feature = "gnss"))]
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use nmea_parser::*;
+
+const CORPUS: &str = include_str!("data/corpus.nmea");
+const EXPECTED: &str = include_str!("data/corpus_expected.txt");
+
+/// Parse the corpus and summarize the results as sorted lines.
+fn summarize(corpus: &str) -> String {
+    let mut p = NmeaParser::new();
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut names: BTreeSet<(u32, String)> = BTreeSet::new();
+    let mut sentences = 0;
+
+    for line in corpus.lines().filter(|l| !l.trim().is_empty()) {
+        sentences += 1;
+        let key = match p.parse_sentence(line) {
+            Ok(msg) => {
+                if let ParsedMessage::VesselStaticData(vsd) = &msg {
+                    if let Some(name) = &vsd.name {
+                        names.insert((vsd.mmsi, name.to_string()));
+                    }
+                }
+                format!("kind {}", msg.kind())
+            }
+            Err(e) => {
+                let class = match e.inner_error() {
+                    ParseError::UnsupportedSentenceType(_) => "UnsupportedSentenceType",
+                    ParseError::CorruptedSentence(_) => "CorruptedSentence",
+                    ParseError::Checksum(_) => "Checksum",
+                    ParseError::InvalidSentence(_) => "InvalidSentence",
+                    _ => "Other",
+                };
+                format!("error {}", class)
+            }
+        };
+        *counts.entry(key).or_insert(0) += 1;
+    }
+
+    let mut summary = format!("sentences {}\n", sentences);
+    for (key, count) in counts {
+        summary += &format!("{} {}\n", key, count);
+    }
+    for (mmsi, name) in names {
+        summary += &format!("name {} {}\n", mmsi, name);
+    }
+    summary
+}
+
+#[test]
+fn test_corpus() {
+    let actual = summarize(CORPUS);
+    if actual.trim_end() != EXPECTED.trim_end() {
+        panic!("corpus summary doesn't match expected:\n{}", actual);
+    }
+}
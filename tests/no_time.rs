@@ -0,0 +1,54 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Timestamps without the `time` feature. Run with
+//! `cargo test --no-default-features --features ais,gnss --test no_time`; the other tests
+//! require the default features.
+
+#![cfg(all(not(feature = "time"), feature = "ais", feature = "gnss"))]
+
+use nmea_parser::*;
+
+#[test]
+fn test_gnss_time_of_day() {
+    let mut p = NmeaParser::new();
+    match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47") {
+        Ok(ParsedMessage::Gga(gga)) => {
+            assert_eq!(gga.timestamp, Some((12, 35, 19)));
+            assert_eq!(gga.time_of_day_secs(), Some(45319));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match p.parse_sentence("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67") {
+        Ok(ParsedMessage::Rmc(rmc)) => assert_eq!(rmc.timestamp, Some((22, 54, 46))),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match p.parse_sentence("$GPZDA,223000.00,31,12,2019,+02,00*47") {
+        Ok(ParsedMessage::Zda(zda)) => assert_eq!(zda.timestamp_utc, Some((22, 30, 0))),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_ais_time_of_day() {
+    let mut p = NmeaParser::new();
+    match p.parse_sentence("!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D") {
+        Ok(ParsedMessage::BaseStationReport(bsr)) => {
+            assert_eq!(bsr.timestamp, Some((19, 57, 39)))
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
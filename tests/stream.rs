@@ -0,0 +1,80 @@
+/*
+Copyright 2024 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Feeds a short capture of mixed GNSS and AIS sentences through a single parser and checks the
+//! sequence of results, which covers dispatch and multi-sentence reassembly end to end. Unlike
+//! `corpus.rs`, the order of the results matters here.
+
+use nmea_parser::*;
+
+/// Capture of a receiver with a GNSS and an AIS talker. Includes a GSV group, an AIS type 5
+/// message in order and another in reverse fragment order, a corrupted line and CR LF endings.
+const CAPTURE: &str = "\
+$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r
+$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67\r
+!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A\r
+$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*74\r
+$GPGSV,3,2,11,14,25,170,00,16,57,208,39,18,67,296,40,19,40,246,00*74\r
+!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C\r
+$GPGSV,3,3,11,22,42,067,42,24,14,311,43,27,05,244,00,,,,*4D\r
+!AIVDM,2,2,1,A,88888888880,2*25\r
+$HEHDT,274.07,T\r
+$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48\r
+!AIVDM,2,2,3,A,88888888880,2*27\r
+$GAGLL,4916.45,N,12311.12,W,225444,A,D*48\r
+!AIVDM,2,1,3,A,53LBbi02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*5B\r
+!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D\r
+$GPZDA,072914.00,31,05,2018,,*63\r
+";
+
+/// Summarize a parse result as the message kind or the error.
+fn describe(result: Result<ParsedMessage, ParseError>) -> String {
+    match result {
+        Ok(ParsedMessage::VesselStaticData(vsd)) => format!("VesselStaticData {}", vsd.mmsi),
+        Ok(msg) => msg.kind().to_string(),
+        Err(ParseError::Checksum(_)) => "Checksum error".to_string(),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+#[test]
+fn test_mixed_stream() {
+    let mut p = NmeaParser::new();
+    let results: Vec<String> = CAPTURE
+        .lines()
+        .map(|line| describe(p.parse_sentence(line)))
+        .collect();
+    assert_eq!(
+        results,
+        vec![
+            "Gga",
+            "Rmc",
+            "VesselDynamicData",
+            "Incomplete",
+            "Incomplete",
+            "Incomplete",
+            "Gsv",
+            "VesselStaticData 351759000",
+            "Hdt",
+            "Checksum error",
+            "Incomplete",
+            "Gll",
+            "VesselStaticData 230992580",
+            "Incomplete",
+            "Zda",
+        ]
+    );
+}